@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX "recipes_category_id_idx";
+
+ALTER TABLE "categories"
+  DROP COLUMN "slug",
+  DROP COLUMN "description",
+  DROP COLUMN "image",
+  DROP COLUMN "sort_order";
@@ -0,0 +1,27 @@
+-- Your SQL goes here
+
+ALTER TABLE "categories"
+  ADD COLUMN "slug" text,
+  ADD COLUMN "description" text NOT NULL DEFAULT '',
+  ADD COLUMN "image" text NOT NULL DEFAULT '',
+  ADD COLUMN "sort_order" integer NOT NULL DEFAULT 0;
+
+UPDATE "categories"
+SET "slug" = trim(both '-' from lower(regexp_replace("name", '[^[:alnum:]]+', '-', 'g')));
+
+UPDATE "categories" AS c
+SET "slug" = c."uuid"
+WHERE c."slug" = '';
+
+UPDATE "categories" AS c
+SET "slug" = c."slug" || '-' || c."id"
+WHERE EXISTS (
+  SELECT 1 FROM "categories" AS o
+  WHERE o."slug" = c."slug" AND o."id" < c."id"
+);
+
+ALTER TABLE "categories"
+  ALTER COLUMN "slug" SET NOT NULL,
+  ADD CONSTRAINT "categories_slug_key" UNIQUE ("slug");
+
+CREATE INDEX "recipes_category_id_idx" ON "recipes" ("category_id");
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX "recipes_allergens_idx";
+ALTER TABLE "recipes" DROP COLUMN "allergens";
+DROP TABLE "allergens";
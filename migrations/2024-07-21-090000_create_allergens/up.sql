@@ -0,0 +1,30 @@
+-- Your SQL goes here
+
+CREATE TABLE "allergens" (
+  "id" SERIAL PRIMARY KEY,
+  "code" text UNIQUE NOT NULL,
+  "name" text NOT NULL,
+  "custom" boolean NOT NULL DEFAULT TRUE,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- The 14 allergens that must be declared under EU Regulation 1169/2011.
+INSERT INTO "allergens" ("code", "name", "custom") VALUES
+  ('gluten', 'Cereals containing gluten', FALSE),
+  ('crustaceans', 'Crustaceans', FALSE),
+  ('eggs', 'Eggs', FALSE),
+  ('fish', 'Fish', FALSE),
+  ('peanuts', 'Peanuts', FALSE),
+  ('soybeans', 'Soybeans', FALSE),
+  ('milk', 'Milk', FALSE),
+  ('nuts', 'Tree nuts', FALSE),
+  ('celery', 'Celery', FALSE),
+  ('mustard', 'Mustard', FALSE),
+  ('sesame', 'Sesame seeds', FALSE),
+  ('sulphites', 'Sulphur dioxide and sulphites', FALSE),
+  ('lupin', 'Lupin', FALSE),
+  ('molluscs', 'Molluscs', FALSE);
+
+ALTER TABLE "recipes" ADD COLUMN "allergens" text[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX "recipes_allergens_idx" ON "recipes" USING GIN ("allergens");
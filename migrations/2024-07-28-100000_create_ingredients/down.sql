@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX "recipes_diets_idx";
+ALTER TABLE "recipes" DROP COLUMN "diets";
+DROP TABLE "ingredients";
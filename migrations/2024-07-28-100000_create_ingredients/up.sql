@@ -0,0 +1,64 @@
+-- Your SQL goes here
+
+CREATE TABLE "ingredients" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "name" text UNIQUE NOT NULL,
+  "aliases" text[] NOT NULL DEFAULT '{}',
+  "animal" boolean NOT NULL DEFAULT FALSE,
+  "meat" boolean NOT NULL DEFAULT FALSE,
+  "gluten" boolean NOT NULL DEFAULT FALSE,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO "ingredients" ("uuid", "name", "aliases", "animal", "meat", "gluten") VALUES
+  (gen_random_uuid()::text, 'beef', '{steak,"ground beef",mince}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'pork', '{ham,sausage}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'bacon', '{}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'chicken', '{"chicken breast","chicken thigh"}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'turkey', '{}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'lamb', '{mutton}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'fish', '{cod,"white fish"}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'salmon', '{}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'tuna', '{}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'shrimp', '{prawn,prawns}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'anchovy', '{anchovies,"fish sauce"}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'gelatin', '{gelatine}', TRUE, TRUE, FALSE),
+  (gen_random_uuid()::text, 'egg', '{eggs,"egg yolk","egg white"}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'milk', '{"whole milk","skim milk"}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'butter', '{}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'cream', '{"heavy cream","sour cream"}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'cheese', '{parmesan,mozzarella,cheddar,feta}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'yogurt', '{yoghurt}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'honey', '{}', TRUE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'wheat flour', '{flour,"plain flour","all-purpose flour"}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'bread', '{breadcrumbs,"bread crumbs"}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'pasta', '{spaghetti,penne,noodles}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'couscous', '{bulgur}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'barley', '{}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'rye', '{}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'soy sauce', '{}', FALSE, FALSE, TRUE),
+  (gen_random_uuid()::text, 'rice', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'potato', '{potatoes}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'tomato', '{tomatoes}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'onion', '{onions,shallot}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'garlic', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'carrot', '{carrots}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'bell pepper', '{paprika}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'spinach', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'mushroom', '{mushrooms}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'lemon', '{lemons,"lemon juice"}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'apple', '{apples}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'tofu', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'lentils', '{lentil}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'chickpeas', '{chickpea}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'beans', '{bean}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'olive oil', '{oil,"vegetable oil"}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'sugar', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'salt', '{}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'black pepper', '{pepper}', FALSE, FALSE, FALSE),
+  (gen_random_uuid()::text, 'water', '{}', FALSE, FALSE, FALSE);
+
+ALTER TABLE "recipes" ADD COLUMN "diets" text[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX "recipes_diets_idx" ON "recipes" USING GIN ("diets");
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "recipes" DROP COLUMN "nutrients.computed";
+
+ALTER TABLE "ingredients"
+  DROP COLUMN "proteins",
+  DROP COLUMN "fats",
+  DROP COLUMN "carbohydrates",
+  DROP COLUMN "fiber",
+  DROP COLUMN "kcal",
+  DROP COLUMN "grams_per_piece",
+  DROP COLUMN "density";
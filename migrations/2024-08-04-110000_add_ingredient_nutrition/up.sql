@@ -0,0 +1,71 @@
+-- Your SQL goes here
+
+-- Nutrition values are per 100 g, density is in g/ml.
+ALTER TABLE "ingredients"
+  ADD COLUMN "proteins" double precision,
+  ADD COLUMN "fats" double precision,
+  ADD COLUMN "carbohydrates" double precision,
+  ADD COLUMN "fiber" double precision,
+  ADD COLUMN "kcal" double precision,
+  ADD COLUMN "grams_per_piece" double precision,
+  ADD COLUMN "density" double precision;
+
+UPDATE "ingredients" AS i
+SET "kcal" = v.kcal,
+    "proteins" = v.proteins,
+    "fats" = v.fats,
+    "carbohydrates" = v.carbohydrates,
+    "fiber" = v.fiber,
+    "grams_per_piece" = v.grams_per_piece,
+    "density" = v.density
+FROM (VALUES
+  ('beef', 250.0, 26.0, 15.0, 0.0, 0.0, NULL::double precision, NULL::double precision),
+  ('pork', 242.0, 27.0, 14.0, 0.0, 0.0, NULL, NULL),
+  ('bacon', 541.0, 37.0, 42.0, 1.4, 0.0, NULL, NULL),
+  ('chicken', 165.0, 31.0, 3.6, 0.0, 0.0, NULL, NULL),
+  ('turkey', 135.0, 30.0, 1.0, 0.0, 0.0, NULL, NULL),
+  ('lamb', 294.0, 25.0, 21.0, 0.0, 0.0, NULL, NULL),
+  ('fish', 82.0, 18.0, 0.7, 0.0, 0.0, NULL, NULL),
+  ('salmon', 208.0, 20.0, 13.0, 0.0, 0.0, NULL, NULL),
+  ('tuna', 132.0, 28.0, 1.3, 0.0, 0.0, NULL, NULL),
+  ('shrimp', 99.0, 24.0, 0.3, 0.2, 0.0, NULL, NULL),
+  ('anchovy', 131.0, 20.0, 4.8, 0.0, 0.0, NULL, NULL),
+  ('gelatin', 335.0, 86.0, 0.1, 0.0, 0.0, NULL, NULL),
+  ('egg', 143.0, 12.6, 9.5, 0.7, 0.0, 50.0, NULL),
+  ('milk', 61.0, 3.2, 3.3, 4.8, 0.0, NULL, 1.03),
+  ('butter', 717.0, 0.9, 81.0, 0.1, 0.0, NULL, 0.91),
+  ('cream', 340.0, 2.8, 36.0, 2.7, 0.0, NULL, 1.0),
+  ('cheese', 402.0, 25.0, 33.0, 1.3, 0.0, NULL, NULL),
+  ('yogurt', 61.0, 3.5, 3.3, 4.7, 0.0, NULL, 1.03),
+  ('honey', 304.0, 0.3, 0.0, 82.0, 0.2, NULL, 1.42),
+  ('wheat flour', 364.0, 10.0, 1.0, 76.0, 2.7, NULL, 0.53),
+  ('bread', 265.0, 9.0, 3.2, 49.0, 2.7, 30.0, NULL),
+  ('pasta', 371.0, 13.0, 1.5, 75.0, 3.2, NULL, NULL),
+  ('couscous', 376.0, 13.0, 0.6, 77.0, 5.0, NULL, 0.7),
+  ('barley', 354.0, 12.5, 2.3, 73.5, 17.3, NULL, 0.8),
+  ('rye', 338.0, 10.0, 1.6, 76.0, 15.0, NULL, NULL),
+  ('soy sauce', 53.0, 8.0, 0.6, 4.9, 0.8, NULL, 1.2),
+  ('rice', 365.0, 7.1, 0.7, 80.0, 1.3, NULL, 0.85),
+  ('potato', 77.0, 2.0, 0.1, 17.0, 2.2, 170.0, NULL),
+  ('tomato', 18.0, 0.9, 0.2, 3.9, 1.2, 120.0, NULL),
+  ('onion', 40.0, 1.1, 0.1, 9.3, 1.7, 110.0, NULL),
+  ('garlic', 149.0, 6.4, 0.5, 33.0, 2.1, 5.0, NULL),
+  ('carrot', 41.0, 0.9, 0.2, 9.6, 2.8, 60.0, NULL),
+  ('bell pepper', 31.0, 1.0, 0.3, 6.0, 2.1, 120.0, NULL),
+  ('spinach', 23.0, 2.9, 0.4, 3.6, 2.2, NULL, NULL),
+  ('mushroom', 22.0, 3.1, 0.3, 3.3, 1.0, 18.0, NULL),
+  ('lemon', 29.0, 1.1, 0.3, 9.3, 2.8, 60.0, NULL),
+  ('apple', 52.0, 0.3, 0.2, 14.0, 2.4, 180.0, NULL),
+  ('tofu', 76.0, 8.0, 4.8, 1.9, 0.3, NULL, NULL),
+  ('lentils', 352.0, 25.0, 1.1, 63.0, 11.0, NULL, 0.85),
+  ('chickpeas', 378.0, 20.0, 6.0, 63.0, 12.0, NULL, 0.8),
+  ('beans', 333.0, 24.0, 0.8, 60.0, 25.0, NULL, 0.8),
+  ('olive oil', 884.0, 0.0, 100.0, 0.0, 0.0, NULL, 0.91),
+  ('sugar', 387.0, 0.0, 0.0, 100.0, 0.0, NULL, 0.85),
+  ('salt', 0.0, 0.0, 0.0, 0.0, 0.0, NULL, 1.2),
+  ('black pepper', 251.0, 10.0, 3.3, 64.0, 25.0, NULL, 0.5),
+  ('water', 0.0, 0.0, 0.0, 0.0, 0.0, NULL, 1.0)
+) AS v (name, kcal, proteins, fats, carbohydrates, fiber, grams_per_piece, density)
+WHERE i."name" = v.name;
+
+ALTER TABLE "recipes" ADD COLUMN "nutrients.computed" boolean NOT NULL DEFAULT FALSE;
@@ -0,0 +1,31 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "ingredients"
+  DROP COLUMN "saturated_fat",
+  DROP COLUMN "sugars",
+  DROP COLUMN "sodium",
+  DROP COLUMN "cholesterol";
+
+ALTER TABLE "recipes" RENAME COLUMN "nutrients_computed" TO "nutrients.computed";
+
+ALTER TABLE "recipes"
+  ADD COLUMN "nutrients.proteins" bigint,
+  ADD COLUMN "nutrients.fats" double precision,
+  ADD COLUMN "nutrients.carbohydrates" double precision,
+  ADD COLUMN "nutrients.fiber" bigint,
+  ADD COLUMN "nutrients.kcal" bigint;
+
+UPDATE "recipes"
+SET "nutrients.proteins" = round(("nutrients"->>'proteins')::double precision),
+    "nutrients.fats" = ("nutrients"->>'fats')::double precision,
+    "nutrients.carbohydrates" = ("nutrients"->>'carbohydrates')::double precision,
+    "nutrients.fiber" = round(("nutrients"->>'fiber')::double precision),
+    "nutrients.kcal" = round(("nutrients"->>'kcal')::double precision);
+
+ALTER TABLE "recipes"
+  ALTER COLUMN "nutrients.proteins" SET NOT NULL,
+  ALTER COLUMN "nutrients.fats" SET NOT NULL,
+  ALTER COLUMN "nutrients.carbohydrates" SET NOT NULL,
+  ALTER COLUMN "nutrients.fiber" SET NOT NULL,
+  ALTER COLUMN "nutrients.kcal" SET NOT NULL,
+  DROP COLUMN "nutrients";
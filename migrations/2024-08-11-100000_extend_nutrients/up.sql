@@ -0,0 +1,35 @@
+-- Your SQL goes here
+
+-- Recipe nutrients move from flattened columns into a single per serving
+-- document so the profile can grow without further schema changes.
+ALTER TABLE "recipes" ADD COLUMN "nutrients" jsonb;
+
+UPDATE "recipes"
+SET "nutrients" = jsonb_build_object(
+  'kcal', "nutrients.kcal"::double precision,
+  'proteins', "nutrients.proteins"::double precision,
+  'fats', "nutrients.fats",
+  'saturated_fat', 0,
+  'carbohydrates', "nutrients.carbohydrates",
+  'sugars', 0,
+  'fiber', "nutrients.fiber"::double precision,
+  'sodium', 0,
+  'cholesterol', 0,
+  'micronutrients', '{}'::jsonb
+);
+
+ALTER TABLE "recipes"
+  ALTER COLUMN "nutrients" SET NOT NULL,
+  DROP COLUMN "nutrients.proteins",
+  DROP COLUMN "nutrients.fats",
+  DROP COLUMN "nutrients.carbohydrates",
+  DROP COLUMN "nutrients.fiber",
+  DROP COLUMN "nutrients.kcal";
+
+ALTER TABLE "recipes" RENAME COLUMN "nutrients.computed" TO "nutrients_computed";
+
+ALTER TABLE "ingredients"
+  ADD COLUMN "saturated_fat" double precision,
+  ADD COLUMN "sugars" double precision,
+  ADD COLUMN "sodium" double precision,
+  ADD COLUMN "cholesterol" double precision;
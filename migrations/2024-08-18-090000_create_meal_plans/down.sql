@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "meal_plan_slots";
+
+DROP TABLE "meal_plans";
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+
+CREATE TABLE "meal_plans" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL,
+  "name" text NOT NULL DEFAULT '',
+  "start_date" date NOT NULL,
+  "end_date" date NOT NULL,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW(),
+  CHECK ("start_date" <= "end_date")
+);
+
+CREATE INDEX "meal_plans_owner_id_idx" ON "meal_plans" ("owner_id", "start_date");
+
+CREATE TABLE "meal_plan_slots" (
+  "id" SERIAL PRIMARY KEY,
+  "meal_plan_id" integer NOT NULL REFERENCES "meal_plans" ("id") ON DELETE CASCADE,
+  "date" date NOT NULL,
+  "meal" text NOT NULL,
+  "recipe_id" text NOT NULL,
+  "servings" bigint CHECK ("servings" > 0)
+);
+
+CREATE INDEX "meal_plan_slots_meal_plan_id_idx" ON "meal_plan_slots" ("meal_plan_id");
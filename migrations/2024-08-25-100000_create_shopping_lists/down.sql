@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "shopping_list_items";
+
+DROP TABLE "shopping_lists";
+
+ALTER TABLE "ingredients" DROP COLUMN "aisle";
@@ -0,0 +1,37 @@
+-- Your SQL goes here
+
+ALTER TABLE "ingredients" ADD COLUMN "aisle" text NOT NULL DEFAULT 'other';
+
+UPDATE "ingredients" SET "aisle" = 'meat' WHERE "name" IN ('beef', 'pork', 'bacon', 'chicken', 'turkey', 'lamb');
+UPDATE "ingredients" SET "aisle" = 'fish' WHERE "name" IN ('fish', 'salmon', 'tuna', 'shrimp', 'anchovy');
+UPDATE "ingredients" SET "aisle" = 'dairy' WHERE "name" IN ('egg', 'milk', 'butter', 'cream', 'cheese', 'yogurt', 'tofu');
+UPDATE "ingredients" SET "aisle" = 'bakery' WHERE "name" IN ('bread');
+UPDATE "ingredients" SET "aisle" = 'grains' WHERE "name" IN ('pasta', 'couscous', 'barley', 'rye', 'rice', 'lentils', 'chickpeas', 'beans');
+UPDATE "ingredients" SET "aisle" = 'produce' WHERE "name" IN ('potato', 'tomato', 'onion', 'garlic', 'carrot', 'bell pepper', 'spinach', 'mushroom', 'lemon', 'apple');
+UPDATE "ingredients" SET "aisle" = 'baking' WHERE "name" IN ('wheat flour', 'sugar', 'gelatin', 'honey');
+UPDATE "ingredients" SET "aisle" = 'condiments' WHERE "name" IN ('olive oil', 'soy sauce', 'salt', 'black pepper');
+UPDATE "ingredients" SET "aisle" = 'beverages' WHERE "name" IN ('water');
+
+CREATE TABLE "shopping_lists" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL,
+  "name" text NOT NULL DEFAULT '',
+  "meal_plan_id" text,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "shopping_lists_owner_id_idx" ON "shopping_lists" ("owner_id");
+
+CREATE TABLE "shopping_list_items" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "shopping_list_id" integer NOT NULL REFERENCES "shopping_lists" ("id") ON DELETE CASCADE,
+  "name" text NOT NULL,
+  "quantity" double precision,
+  "unit" text,
+  "aisle" text NOT NULL,
+  "checked" boolean NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX "shopping_list_items_shopping_list_id_idx" ON "shopping_list_items" ("shopping_list_id");
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+CREATE TABLE "pantry_items" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL,
+  "name" text NOT NULL,
+  "quantity" double precision CHECK ("quantity" > 0),
+  "unit" text,
+  "expires_on" date,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "pantry_items_owner_id_idx" ON "pantry_items" ("owner_id", "expires_on");
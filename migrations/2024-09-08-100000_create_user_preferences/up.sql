@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+CREATE TABLE "user_preferences" (
+  "id" SERIAL PRIMARY KEY,
+  "owner_id" text UNIQUE NOT NULL,
+  "diets" text[] NOT NULL DEFAULT '{}',
+  "exclude_allergens" text[] NOT NULL DEFAULT '{}',
+  "categories" text[] NOT NULL DEFAULT '{}',
+  "disliked_ingredients" text[] NOT NULL DEFAULT '{}',
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
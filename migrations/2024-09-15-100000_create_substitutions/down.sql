@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "substitutions";
+
+DELETE FROM "ingredients" WHERE "name" IN (
+  'oat milk', 'soy milk', 'margarine', 'coconut oil', 'coconut cream',
+  'nutritional yeast', 'flaxseed', 'rice flour', 'tamari', 'maple syrup'
+);
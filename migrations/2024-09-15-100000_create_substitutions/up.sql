@@ -0,0 +1,52 @@
+-- Your SQL goes here
+
+-- Dictionary entries for the seeded substitutes, nutrition values per 100 g.
+INSERT INTO "ingredients" (
+  "uuid", "name", "aliases", "animal", "meat", "gluten", "aisle",
+  "kcal", "proteins", "fats", "saturated_fat", "carbohydrates", "sugars", "fiber",
+  "sodium", "cholesterol", "grams_per_piece", "density"
+) VALUES
+  (gen_random_uuid()::text, 'oat milk', '{"oat drink"}', FALSE, FALSE, FALSE, 'dairy', 46.0, 1.0, 1.5, 0.2, 6.7, 4.0, 0.8, 40.0, 0.0, NULL, 1.03),
+  (gen_random_uuid()::text, 'soy milk', '{"soy drink"}', FALSE, FALSE, FALSE, 'dairy', 54.0, 3.3, 1.8, 0.2, 6.3, 4.0, 0.6, 51.0, 0.0, NULL, 1.03),
+  (gen_random_uuid()::text, 'margarine', '{}', FALSE, FALSE, FALSE, 'dairy', 717.0, 0.2, 80.0, 15.0, 0.7, 0.0, 0.0, 700.0, 0.0, NULL, 0.91),
+  (gen_random_uuid()::text, 'coconut oil', '{}', FALSE, FALSE, FALSE, 'condiments', 892.0, 0.0, 99.0, 82.0, 0.0, 0.0, 0.0, 0.0, 0.0, NULL, 0.92),
+  (gen_random_uuid()::text, 'coconut cream', '{}', FALSE, FALSE, FALSE, 'dairy', 330.0, 3.6, 35.0, 31.0, 6.7, 3.3, 2.2, 4.0, 0.0, NULL, 1.0),
+  (gen_random_uuid()::text, 'nutritional yeast', '{}', FALSE, FALSE, FALSE, 'condiments', 325.0, 50.0, 5.0, 0.7, 36.0, 0.0, 25.0, 100.0, 0.0, NULL, NULL),
+  (gen_random_uuid()::text, 'flaxseed', '{"ground flaxseed",linseed}', FALSE, FALSE, FALSE, 'baking', 534.0, 18.0, 42.0, 3.7, 29.0, 1.6, 27.0, 30.0, 0.0, NULL, 0.5),
+  (gen_random_uuid()::text, 'rice flour', '{}', FALSE, FALSE, FALSE, 'baking', 366.0, 6.0, 1.4, 0.4, 80.0, 0.1, 2.4, 0.0, 0.0, NULL, 0.6),
+  (gen_random_uuid()::text, 'tamari', '{}', FALSE, FALSE, FALSE, 'condiments', 60.0, 10.5, 0.1, 0.0, 5.6, 1.7, 0.8, 5600.0, 0.0, NULL, 1.2),
+  (gen_random_uuid()::text, 'maple syrup', '{}', FALSE, FALSE, FALSE, 'baking', 260.0, 0.0, 0.1, 0.0, 67.0, 60.0, 0.0, 12.0, 0.0, NULL, 1.32)
+ON CONFLICT ("name") DO NOTHING;
+
+-- A substitute replaces `ratio` times the amount of the ingredient, in
+-- `unit` when set and in the unit of the recipe line otherwise. `avoids`
+-- lists the restrictions the swap satisfies, e.g. dairy, eggs, gluten, meat
+-- or animal.
+CREATE TABLE "substitutions" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "ingredient" text NOT NULL,
+  "substitute" text NOT NULL,
+  "ratio" double precision NOT NULL CHECK ("ratio" > 0),
+  "unit" text,
+  "avoids" text[] NOT NULL DEFAULT '{}',
+  "notes" text NOT NULL DEFAULT '',
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE ("ingredient", "substitute")
+);
+
+CREATE INDEX "substitutions_avoids_idx" ON "substitutions" USING GIN ("avoids");
+
+INSERT INTO "substitutions" ("uuid", "ingredient", "substitute", "ratio", "unit", "avoids", "notes") VALUES
+  (gen_random_uuid()::text, 'butter', 'margarine', 1.0, NULL, '{dairy,milk,animal}', ''),
+  (gen_random_uuid()::text, 'butter', 'coconut oil', 0.8, NULL, '{dairy,milk,animal}', 'Adds a mild coconut taste.'),
+  (gen_random_uuid()::text, 'milk', 'oat milk', 1.0, NULL, '{dairy,milk,animal}', ''),
+  (gen_random_uuid()::text, 'milk', 'soy milk', 1.0, NULL, '{dairy,milk,animal}', 'Contains soy.'),
+  (gen_random_uuid()::text, 'cream', 'coconut cream', 1.0, NULL, '{dairy,milk,animal}', ''),
+  (gen_random_uuid()::text, 'cheese', 'nutritional yeast', 0.3, NULL, '{dairy,milk,animal}', 'Gives a cheesy taste but does not melt.'),
+  (gen_random_uuid()::text, 'egg', 'flaxseed', 1.0, 'tbsp', '{eggs,animal}', 'Mix each tablespoon with three tablespoons of water and let it set for five minutes.'),
+  (gen_random_uuid()::text, 'honey', 'maple syrup', 1.0, NULL, '{animal}', ''),
+  (gen_random_uuid()::text, 'wheat flour', 'rice flour', 1.0, NULL, '{gluten}', 'Doughs are less elastic.'),
+  (gen_random_uuid()::text, 'soy sauce', 'tamari', 1.0, NULL, '{gluten}', ''),
+  (gen_random_uuid()::text, 'beef', 'lentils', 1.0, NULL, '{meat,animal}', 'Use cooked lentils.'),
+  (gen_random_uuid()::text, 'chicken', 'tofu', 1.0, NULL, '{meat,animal}', 'Use firm tofu.');
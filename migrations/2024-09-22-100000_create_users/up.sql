@@ -0,0 +1,26 @@
+-- Your SQL goes here
+
+CREATE TABLE "users" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "email" text UNIQUE NOT NULL,
+  "name" text NOT NULL DEFAULT '',
+  "password_hash" text NOT NULL,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- A login. Access tokens name the session they were issued for, so revoking
+-- it logs them out too. Only a hash of the refresh token is kept.
+CREATE TABLE "sessions" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "user_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "refresh_token_hash" text UNIQUE NOT NULL,
+  "expires_at" TIMESTAMP NOT NULL,
+  "revoked_at" TIMESTAMP,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "sessions_user_id_idx" ON "sessions" ("user_id");
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "recipes" DROP COLUMN "author_id";
+ALTER TABLE "users" DROP COLUMN "role";
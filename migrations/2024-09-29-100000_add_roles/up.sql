@@ -0,0 +1,10 @@
+-- Your SQL goes here
+
+ALTER TABLE "users" ADD COLUMN "role" text NOT NULL DEFAULT 'viewer'
+  CHECK ("role" IN ('viewer', 'author', 'editor', 'admin'));
+
+-- Recipes created before accounts existed have no author and can only be
+-- changed by editors.
+ALTER TABLE "recipes" ADD COLUMN "author_id" text;
+
+CREATE INDEX "recipes_author_id_idx" ON "recipes" ("author_id");
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+-- Credentials for machine clients. Only a hash of the key is kept, the
+-- prefix identifies it in listings.
+CREATE TABLE "api_keys" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "name" text NOT NULL DEFAULT '',
+  "prefix" text NOT NULL,
+  "key_hash" text UNIQUE NOT NULL,
+  "scopes" text[] NOT NULL DEFAULT '{}',
+  -- requests per minute
+  "rate_limit" integer NOT NULL DEFAULT 60 CHECK ("rate_limit" > 0),
+  "last_used_at" TIMESTAMP,
+  "revoked_at" TIMESTAMP,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "api_keys_owner_id_idx" ON "api_keys" ("owner_id");
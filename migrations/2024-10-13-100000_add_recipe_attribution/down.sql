@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "recipes" DROP COLUMN "license";
+ALTER TABLE "recipes" DROP COLUMN "attribution";
+ALTER TABLE "recipes" DROP COLUMN "source_url";
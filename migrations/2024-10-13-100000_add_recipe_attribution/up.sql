@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+-- Where a recipe was taken from and under which terms, so it can be
+-- credited.
+ALTER TABLE "recipes" ADD COLUMN "source_url" text;
+ALTER TABLE "recipes" ADD COLUMN "attribution" text NOT NULL DEFAULT '';
+ALTER TABLE "recipes" ADD COLUMN "license" text NOT NULL DEFAULT '';
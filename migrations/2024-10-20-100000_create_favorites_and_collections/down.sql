@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "collection_items";
+DROP TABLE "collections";
+DROP TABLE "favorites";
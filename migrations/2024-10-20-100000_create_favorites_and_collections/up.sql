@@ -0,0 +1,32 @@
+-- Your SQL goes here
+
+CREATE TABLE "favorites" (
+  "id" SERIAL PRIMARY KEY,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "recipe_id" text NOT NULL,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE ("owner_id", "recipe_id")
+);
+
+-- A named list of recipes. Anyone knowing the share token can view it.
+CREATE TABLE "collections" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "name" text NOT NULL,
+  "description" text NOT NULL DEFAULT '',
+  "share_token" text UNIQUE,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "collections_owner_id_idx" ON "collections" ("owner_id");
+
+CREATE TABLE "collection_items" (
+  "id" SERIAL PRIMARY KEY,
+  "collection_id" integer NOT NULL REFERENCES "collections" ("id") ON DELETE CASCADE,
+  "recipe_id" text NOT NULL,
+  "position" integer NOT NULL,
+  "note" text NOT NULL DEFAULT '',
+  UNIQUE ("collection_id", "recipe_id")
+);
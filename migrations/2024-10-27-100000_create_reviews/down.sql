@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "recipes" DROP COLUMN "rating_count";
+ALTER TABLE "recipes" DROP COLUMN "rating_average";
+DROP TABLE "reviews";
@@ -0,0 +1,24 @@
+-- Your SQL goes here
+
+-- One review per user and recipe. Flagged reviews wait for a moderator,
+-- hidden ones are left out of listings and the rating of the recipe.
+CREATE TABLE "reviews" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "recipe_id" text NOT NULL,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "rating" integer NOT NULL CHECK ("rating" BETWEEN 1 AND 5),
+  "body" text NOT NULL DEFAULT '',
+  "flagged" boolean NOT NULL DEFAULT false,
+  "flag_reason" text NOT NULL DEFAULT '',
+  "hidden" boolean NOT NULL DEFAULT false,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE ("recipe_id", "owner_id")
+);
+
+CREATE INDEX "reviews_flagged_idx" ON "reviews" ("flagged") WHERE "flagged";
+
+-- Kept in sync with the visible reviews, NULL while there are none.
+ALTER TABLE "recipes" ADD COLUMN "rating_average" double precision;
+ALTER TABLE "recipes" ADD COLUMN "rating_count" bigint NOT NULL DEFAULT 0;
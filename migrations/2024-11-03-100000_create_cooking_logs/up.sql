@@ -0,0 +1,19 @@
+-- Your SQL goes here
+
+-- A recipe a user cooked. Nutrients are the eaten amount, copied from the
+-- recipe when logging so later recipe changes don't rewrite the history.
+CREATE TABLE "cooking_logs" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "recipe_id" text NOT NULL,
+  "recipe_title" text NOT NULL,
+  "cooked_on" date NOT NULL,
+  "servings" double precision NOT NULL,
+  "notes" text NOT NULL DEFAULT '',
+  "photo" bytea NOT NULL DEFAULT '',
+  "nutrients" jsonb NOT NULL,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "cooking_logs_owner_id_cooked_on_idx" ON "cooking_logs" ("owner_id", "cooked_on");
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "nutrition_goals";
+DROP TABLE "food_entries";
+ALTER TABLE "cooking_logs" DROP COLUMN "meal";
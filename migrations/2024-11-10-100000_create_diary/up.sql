@@ -0,0 +1,29 @@
+-- Your SQL goes here
+
+ALTER TABLE "cooking_logs" ADD COLUMN "meal" text;
+
+-- Food eaten that is not a recipe, e.g. an apple. Nutrients are the eaten
+-- amount.
+CREATE TABLE "food_entries" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "owner_id" text NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "eaten_on" date NOT NULL,
+  "meal" text,
+  "name" text NOT NULL,
+  "nutrients" jsonb NOT NULL,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX "food_entries_owner_id_eaten_on_idx" ON "food_entries" ("owner_id", "eaten_on");
+
+-- Daily goals, macronutrients in grams.
+CREATE TABLE "nutrition_goals" (
+  "id" SERIAL PRIMARY KEY,
+  "owner_id" text UNIQUE NOT NULL REFERENCES "users" ("uuid") ON DELETE CASCADE,
+  "kcal" double precision NOT NULL,
+  "proteins" double precision,
+  "fats" double precision,
+  "carbohydrates" double precision,
+  "updated_at"  TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+
+-- Recipes created before the workflow existed stay public, new ones start
+-- as drafts.
+ALTER TABLE "recipes" ADD COLUMN "status" text NOT NULL DEFAULT 'published';
+ALTER TABLE "recipes" ALTER COLUMN "status" SET DEFAULT 'draft';
+
+CREATE INDEX "recipes_status_idx" ON "recipes" ("status");
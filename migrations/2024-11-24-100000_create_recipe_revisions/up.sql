@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+-- Content of a recipe after each change, never updated. Recipes changed
+-- before this table existed get their previous content as the first
+-- revision on their next change.
+CREATE TABLE "recipe_revisions" (
+  "id" SERIAL PRIMARY KEY,
+  "uuid" text UNIQUE NOT NULL,
+  "recipe_id" text NOT NULL REFERENCES "recipes" ("uuid") ON DELETE CASCADE,
+  "number" integer NOT NULL,
+  "author_id" text,
+  "snapshot" jsonb NOT NULL,
+  "created_at" TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE ("recipe_id", "number")
+);
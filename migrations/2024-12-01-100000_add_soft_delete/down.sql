@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "categories" DROP COLUMN "deleted_at";
+ALTER TABLE "recipes" DROP COLUMN "deleted_at";
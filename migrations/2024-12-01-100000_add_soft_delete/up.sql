@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+ALTER TABLE "recipes" ADD COLUMN "deleted_at" timestamp;
+ALTER TABLE "categories" ADD COLUMN "deleted_at" timestamp;
+
+CREATE INDEX "recipes_deleted_at_idx" ON "recipes" ("deleted_at");
+CREATE INDEX "categories_deleted_at_idx" ON "categories" ("deleted_at");
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "shopping_lists" DROP COLUMN "missing_recipes";
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE "shopping_lists" ADD COLUMN "missing_recipes" text[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+-- Slugs of deleted categories are free to reuse.
+ALTER TABLE "categories" DROP CONSTRAINT "categories_slug_key";
+CREATE UNIQUE INDEX "categories_slug_key" ON "categories" ("slug") WHERE "deleted_at" IS NULL;
@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, State},
+    routing::{delete, get},
+    Json, Router,
+};
+
+use crate::{
+    model::{AllergenSearchQuery, CreateAllergenCommand, DeleteAllergenCommand},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/:code", delete(delete_allergen_handler))
+        .route(
+            "/",
+            get(search_allergen_handler).post(create_allergen_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_allergen_handler(
+    State(state): State<AppState>,
+) -> Result<Json<api_model::SearchResult<api_model::Allergen>>, AppError> {
+    let res = state
+        .allergen_service
+        .search(AllergenSearchQuery { codes: None })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_allergen_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateAllergen>,
+) -> Result<Json<api_model::Allergen>, AppError> {
+    let mut cmd: CreateAllergenCommand = item.into();
+    cmd.actor = user.actor();
+
+    let res = state.allergen_service.create(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_allergen_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(code): Path<String>,
+) -> Result<Json<api_model::Allergen>, AppError> {
+    let res = state
+        .allergen_service
+        .delete(DeleteAllergenCommand {
+            code,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Json, Router,
+};
+
+use crate::{
+    model::{ApiKeysQuery, CreateApiKeyCommand, RevokeApiKeyCommand, RotateApiKeyCommand},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/:id", delete(revoke_api_key_handler))
+        .route("/:id/rotate", post(rotate_api_key_handler))
+        .route("/", get(list_api_keys_handler).post(create_api_key_handler))
+        .with_state(state)
+}
+
+async fn list_api_keys_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<Vec<api_model::ApiKey>>, AppError> {
+    let res = state
+        .api_key_service
+        .list(ApiKeysQuery { owner_id: user.id })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into_iter().map(|key| key.into()).collect()))
+}
+
+async fn create_api_key_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateApiKey>,
+) -> Result<(StatusCode, Json<api_model::IssuedApiKey>), AppError> {
+    let mut cmd: CreateApiKeyCommand = item.try_into().map_err(AppError)?;
+    cmd.actor = user.actor();
+
+    let res = state.api_key_service.create(cmd).await.map_err(AppError)?;
+
+    Ok((StatusCode::CREATED, Json(res.into())))
+}
+
+async fn rotate_api_key_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::IssuedApiKey>, AppError> {
+    let res = state
+        .api_key_service
+        .rotate(RotateApiKeyCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn revoke_api_key_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::ApiKey>, AppError> {
+    let res = state
+        .api_key_service
+        .revoke(RevokeApiKeyCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::{
+    model::{Identity, LogoutCommand},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/register", post(register_handler))
+        .route("/login", post(login_handler))
+        .route("/refresh", post(refresh_handler))
+        .route("/logout", post(logout_handler))
+        .route("/me", get(me_handler))
+        .with_state(state)
+}
+
+async fn register_handler(
+    State(state): State<AppState>,
+    Json(item): Json<api_model::Register>,
+) -> Result<(StatusCode, Json<api_model::AuthTokens>), AppError> {
+    let res = state
+        .auth_service
+        .register(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok((StatusCode::CREATED, Json(res.into())))
+}
+
+async fn login_handler(
+    State(state): State<AppState>,
+    Json(item): Json<api_model::Login>,
+) -> Result<Json<api_model::AuthTokens>, AppError> {
+    let res = state
+        .auth_service
+        .login(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn refresh_handler(
+    State(state): State<AppState>,
+    Json(item): Json<api_model::RefreshToken>,
+) -> Result<Json<api_model::AuthTokens>, AppError> {
+    let res = state
+        .auth_service
+        .refresh(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn logout_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    item: Option<Json<api_model::Logout>>,
+) -> Result<StatusCode, AppError> {
+    state
+        .auth_service
+        .logout(LogoutCommand {
+            user_id: user.id,
+            session_id: user.session_id,
+            all: item.is_some_and(|Json(item)| item.all),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn me_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<api_model::User>, AppError> {
+    let res = state
+        .auth_service
+        .me(&Identity {
+            user_id: user.id,
+            session_id: user.session_id,
+            role: user.role,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
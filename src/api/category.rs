@@ -1,15 +1,21 @@
 use axum::{
     extract::{Path, State},
     routing::get,
-    Json, Router,
+    Form, Json, Router,
 };
 
 use crate::{
-    model::{CategoryQuery, CategorySearchQuery, DeleteCategoryCommand, UpdateCategoryCommand},
+    model::{
+        CategoryQuery, CategorySlugQuery, CreateCategoryCommand, DeleteCategoryCommand,
+        UpdateCategoryCommand,
+    },
     state::AppState,
 };
 
-use super::model::{self as api_model, AppError};
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
 
 pub fn build(state: AppState) -> Router {
     Router::new()
@@ -19,6 +25,7 @@ pub fn build(state: AppState) -> Router {
                 .put(update_category_handler)
                 .delete(delete_category_handler),
         )
+        .route("/by-slug/:slug", get(fetch_category_by_slug_handler))
         .route(
             "/",
             get(search_category_handler).post(create_category_handler),
@@ -28,25 +35,26 @@ pub fn build(state: AppState) -> Router {
 
 async fn search_category_handler(
     State(state): State<AppState>,
+    Form(item): Form<api_model::CategorySearchQuery>,
 ) -> Result<Json<api_model::SearchResult<api_model::Category>>, AppError> {
     let res = state
         .category_service
-        .search(CategorySearchQuery { ids: None })
+        .search(item.into())
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn create_category_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(item): Json<api_model::CreateCategory>,
 ) -> Result<Json<api_model::Category>, AppError> {
-    let res = state
-        .category_service
-        .create(item.into())
-        .await
-        .map_err(|e| AppError(e))?;
+    let mut cmd: CreateCategoryCommand = item.into();
+    cmd.actor = user.actor();
+
+    let res = state.category_service.create(cmd).await.map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
@@ -59,37 +67,52 @@ async fn fetch_category_handler(
         .category_service
         .fetch(CategoryQuery { id })
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_category_by_slug_handler(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<api_model::Category>, AppError> {
+    let res = state
+        .category_service
+        .fetch_by_slug(CategorySlugQuery { slug })
+        .await
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn update_category_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<String>,
     Json(item): Json<api_model::UpdateCategory>,
 ) -> Result<Json<api_model::Category>, AppError> {
     let mut cmd: UpdateCategoryCommand = item.into();
     cmd.id = id;
+    cmd.actor = user.actor();
 
-    let res = state
-        .category_service
-        .update(cmd)
-        .await
-        .map_err(|e| AppError(e))?;
+    let res = state.category_service.update(cmd).await.map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn delete_category_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<String>,
 ) -> Result<Json<api_model::Category>, AppError> {
     let res = state
         .category_service
-        .delete(DeleteCategoryCommand { id })
+        .delete(DeleteCategoryCommand {
+            id,
+            actor: user.actor(),
+        })
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
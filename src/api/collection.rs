@@ -0,0 +1,133 @@
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CollectionQuery, CollectionSearchQuery, CreateCollectionCommand, DeleteCollectionCommand,
+        SharedCollectionQuery, UpdateCollectionCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/me/collections/:id",
+            get(fetch_collection_handler)
+                .put(update_collection_handler)
+                .delete(delete_collection_handler),
+        )
+        .route(
+            "/me/collections",
+            get(search_collections_handler).post(create_collection_handler),
+        )
+        .route(
+            "/shared/collections/:token",
+            get(fetch_shared_collection_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_collections_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::CollectionSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Collection>>, AppError> {
+    let mut q: CollectionSearchQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.collection_service.search(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_collection_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateCollection>,
+) -> Result<Json<api_model::Collection>, AppError> {
+    let mut cmd: CreateCollectionCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state
+        .collection_service
+        .create(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_collection_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Collection>, AppError> {
+    let res = state
+        .collection_service
+        .fetch(CollectionQuery {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_collection_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::UpdateCollection>,
+) -> Result<Json<api_model::Collection>, AppError> {
+    let mut cmd: UpdateCollectionCommand = item.into();
+    cmd.id = id;
+    cmd.owner_id = user.id;
+
+    let res = state
+        .collection_service
+        .update(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_collection_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Collection>, AppError> {
+    let res = state
+        .collection_service
+        .delete(DeleteCollectionCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_shared_collection_handler(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Json<api_model::Collection>, AppError> {
+    let res = state
+        .collection_service
+        .fetch_shared(SharedCollectionQuery { share_token: token })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
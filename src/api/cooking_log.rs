@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, State},
+    routing::{delete, get},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{CookingLogSearchQuery, DeleteCookingLogCommand, LogCookingCommand},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/me/cooking-log/:id", delete(delete_cooking_log_handler))
+        .route(
+            "/me/cooking-log",
+            get(search_cooking_log_handler).post(log_cooking_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_cooking_log_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::CookingLogSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::CookingLogEntry>>, AppError> {
+    let mut q: CookingLogSearchQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state
+        .cooking_log_service
+        .search(q)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn log_cooking_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::LogCooking>,
+) -> Result<Json<api_model::CookingLogEntry>, AppError> {
+    let mut cmd: LogCookingCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state.cooking_log_service.log(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_cooking_log_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::CookingLogEntry>, AppError> {
+    let res = state
+        .cooking_log_service
+        .delete(DeleteCookingLogCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
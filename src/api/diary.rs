@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        DeleteFoodEntryCommand, DiaryDayQuery, IntakeQuery, LogFoodCommand, ReportQuery,
+        SetGoalsCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/me/diary", get(diary_day_handler))
+        .route("/me/diary/foods", post(log_food_handler))
+        .route("/me/diary/foods/:id", delete(delete_food_handler))
+        .route(
+            "/me/goals",
+            get(fetch_goals_handler)
+                .put(set_goals_handler)
+                .delete(clear_goals_handler),
+        )
+        .route("/me/intake", get(intake_handler))
+        .route("/me/reports", get(report_handler))
+        .with_state(state)
+}
+
+async fn diary_day_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::DiaryDayQuery>,
+) -> Result<Json<api_model::DiaryDay>, AppError> {
+    let mut q: DiaryDayQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.diary_service.day(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn log_food_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::LogFood>,
+) -> Result<Json<api_model::FoodEntry>, AppError> {
+    let mut cmd: LogFoodCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state.diary_service.log_food(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_food_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::FoodEntry>, AppError> {
+    let res = state
+        .diary_service
+        .delete_food(DeleteFoodEntryCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_goals_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<Option<api_model::NutritionTargets>>, AppError> {
+    let res = state.diary_service.goals(user.id).await.map_err(AppError)?;
+
+    Ok(Json(res.map(api_model::NutritionTargets::from)))
+}
+
+async fn set_goals_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::NutritionTargets>,
+) -> Result<Json<api_model::NutritionTargets>, AppError> {
+    let res = state
+        .diary_service
+        .set_goals(SetGoalsCommand {
+            owner_id: user.id,
+            goals: item.into(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn clear_goals_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<StatusCode, AppError> {
+    state
+        .diary_service
+        .clear_goals(user.id)
+        .await
+        .map_err(AppError)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Nutrients eaten per day according to the diary.
+async fn intake_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::IntakeQuery>,
+) -> Result<Json<api_model::IntakeSummary>, AppError> {
+    let mut q: IntakeQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.diary_service.intake(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn report_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::ReportQuery>,
+) -> Result<Json<api_model::Report>, AppError> {
+    let mut q: ReportQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.diary_service.report(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{FavoriteCommand, FavoritesQuery},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/:recipe_id",
+            put(add_favorite_handler).delete(remove_favorite_handler),
+        )
+        .route("/", get(list_favorites_handler))
+        .with_state(state)
+}
+
+async fn list_favorites_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::FavoritesQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Recipe>>, AppError> {
+    let mut q: FavoritesQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.favorite_service.list(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn add_favorite_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(recipe_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    state
+        .favorite_service
+        .add(FavoriteCommand {
+            owner_id: user.id,
+            recipe_id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn remove_favorite_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(recipe_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    state
+        .favorite_service
+        .remove(FavoriteCommand {
+            owner_id: user.id,
+            recipe_id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,177 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderMap, Method},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{
+    model::{Actor, ApiKeyScope, Role, ServiceError},
+    state::AppState,
+};
+
+use super::model::AppError;
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// The user a request is made for, authenticated by the access token in the
+/// `Authorization: Bearer` header or by an API key. Rejects requests without
+/// valid credentials.
+#[derive(Debug, Clone)]
+pub struct CurrentUser {
+    pub id: String,
+    /// Empty when authenticated by an API key.
+    pub session_id: String,
+    pub role: Role,
+}
+
+impl CurrentUser {
+    pub fn actor(&self) -> Actor {
+        Actor {
+            user_id: self.id.clone(),
+            role: self.role,
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for CurrentUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<CurrentUser>() {
+            return Ok(user.clone());
+        }
+
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| {
+                AppError(ServiceError::Unauthorized("missing bearer token".into()).into())
+            })?;
+
+        let identity = state
+            .auth_service
+            .authenticate(token)
+            .await
+            .map_err(AppError)?;
+
+        Ok(CurrentUser {
+            id: identity.user_id,
+            session_id: identity.session_id,
+            role: identity.role,
+        })
+    }
+}
+
+/// The [`CurrentUser`] when the request carries credentials, `None` for
+/// anonymous requests. Credentials that don't authenticate are rejected
+/// rather than read as anonymous.
+#[derive(Debug, Clone)]
+pub struct OptionalUser(pub Option<CurrentUser>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for OptionalUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if parts.extensions.get::<CurrentUser>().is_none()
+            && !parts.headers.contains_key(header::AUTHORIZATION)
+        {
+            return Ok(Self(None));
+        }
+
+        CurrentUser::from_request_parts(parts, state)
+            .await
+            .map(|user| Self(Some(user)))
+    }
+}
+
+/// Authenticates API keys given as `X-Api-Key: <key>` or
+/// `Authorization: ApiKey <key>`, enforces their scopes and rate limits and
+/// hands the owner on as [`CurrentUser`]. Requests without a key pass through.
+pub async fn authenticate_api_key(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(secret) = api_key(req.headers()) else {
+        return Ok(next.run(req).await);
+    };
+
+    let identity = state
+        .api_key_service
+        .authenticate(&secret)
+        .await
+        .map_err(AppError)?;
+
+    let scope = required_scope(req.method(), req.uri().path()).ok_or_else(|| {
+        AppError(ServiceError::Forbidden("endpoint is not available to API keys".into()).into())
+    })?;
+    if !identity.scopes.contains(&scope) {
+        return Err(AppError(
+            ServiceError::Forbidden(format!("{} scope required", scope.code())).into(),
+        ));
+    }
+
+    req.extensions_mut().insert(CurrentUser {
+        id: identity.user_id,
+        session_id: String::new(),
+        role: identity.role,
+    });
+
+    Ok(next.run(req).await)
+}
+
+fn api_key(headers: &HeaderMap) -> Option<String> {
+    let value = match headers.get(API_KEY_HEADER) {
+        Some(value) => value.to_str().ok(),
+        None => headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("ApiKey ")),
+    };
+
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// The scope an API key needs for a route, `None` for routes keys can't use.
+fn required_scope(method: &Method, path: &str) -> Option<ApiKeyScope> {
+    let read = method == Method::GET || path == "/recipes/nutrients/calculate";
+    let within = |prefix: &str| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+
+    if within("/recipes/categories") {
+        return Some(if read {
+            ApiKeyScope::ReadRecipes
+        } else {
+            ApiKeyScope::ManageCategories
+        });
+    }
+
+    if within("/recipes") {
+        return Some(if read {
+            ApiKeyScope::ReadRecipes
+        } else {
+            ApiKeyScope::WriteRecipes
+        });
+    }
+
+    None
+}
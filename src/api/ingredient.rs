@@ -0,0 +1,148 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CreateIngredientCommand, DeleteIngredientCommand, ImportIngredientsCommand,
+        IngredientQuery, ReclassifyRecipesCommand, UpdateIngredientCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/import", post(import_ingredients_handler))
+        .route("/reclassify-recipes", post(reclassify_recipes_handler))
+        .route(
+            "/:id",
+            get(fetch_ingredient_handler)
+                .put(update_ingredient_handler)
+                .delete(delete_ingredient_handler),
+        )
+        .route(
+            "/",
+            get(search_ingredient_handler).post(create_ingredient_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_ingredient_handler(
+    State(state): State<AppState>,
+    Form(item): Form<api_model::IngredientSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Ingredient>>, AppError> {
+    let res = state
+        .ingredient_service
+        .search(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_ingredient_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateIngredient>,
+) -> Result<Json<api_model::Ingredient>, AppError> {
+    let mut cmd: CreateIngredientCommand = item.into();
+    cmd.actor = user.actor();
+
+    let res = state
+        .ingredient_service
+        .create(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_ingredient_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Ingredient>, AppError> {
+    let res = state
+        .ingredient_service
+        .fetch(IngredientQuery { id })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_ingredient_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::UpdateIngredient>,
+) -> Result<Json<api_model::Ingredient>, AppError> {
+    let mut cmd: UpdateIngredientCommand = item.into();
+    cmd.id = id;
+    cmd.actor = user.actor();
+
+    let res = state
+        .ingredient_service
+        .update(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_ingredient_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Ingredient>, AppError> {
+    let res = state
+        .ingredient_service
+        .delete(DeleteIngredientCommand {
+            id,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn reclassify_recipes_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<api_model::ReclassifyResult>, AppError> {
+    let changed = state
+        .recipe_service
+        .reclassify(ReclassifyRecipesCommand {
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(api_model::ReclassifyResult { changed }))
+}
+
+/// Accepts a CSV dump as the raw request body, e.g.
+/// `curl --data-binary @nutrition.csv /ingredients/import`.
+async fn import_ingredients_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    body: String,
+) -> Result<Json<api_model::ImportResult>, AppError> {
+    let imported = state
+        .ingredient_service
+        .import_csv(ImportIngredientsCommand {
+            data: body,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(api_model::ImportResult { imported }))
+}
@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CreateMealPlanCommand, DeleteMealPlanCommand, GenerateMealPlanCommand, MealPlanQuery,
+        MealPlanSearchQuery, UpdateMealPlanCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/:id",
+            get(fetch_meal_plan_handler)
+                .put(update_meal_plan_handler)
+                .delete(delete_meal_plan_handler),
+        )
+        .route("/generate", post(generate_meal_plan_handler))
+        .route("/:id/summary", get(meal_plan_summary_handler))
+        .route(
+            "/",
+            get(search_meal_plans_handler).post(create_meal_plan_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_meal_plans_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::MealPlanSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::MealPlan>>, AppError> {
+    let mut q: MealPlanSearchQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.meal_plan_service.search(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_meal_plan_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateMealPlan>,
+) -> Result<Json<api_model::MealPlan>, AppError> {
+    let mut cmd: CreateMealPlanCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state
+        .meal_plan_service
+        .create(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_meal_plan_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::MealPlan>, AppError> {
+    let res = state
+        .meal_plan_service
+        .fetch(MealPlanQuery {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_meal_plan_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::UpdateMealPlan>,
+) -> Result<Json<api_model::MealPlan>, AppError> {
+    let mut cmd: UpdateMealPlanCommand = item.into();
+    cmd.id = id;
+    cmd.owner_id = user.id;
+
+    let res = state
+        .meal_plan_service
+        .update(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_meal_plan_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::MealPlan>, AppError> {
+    let res = state
+        .meal_plan_service
+        .delete(DeleteMealPlanCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn meal_plan_summary_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::MealPlanSummary>, AppError> {
+    let res = state
+        .meal_plan_service
+        .summary(MealPlanQuery {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn generate_meal_plan_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::GenerateMealPlan>,
+) -> Result<Json<api_model::GeneratedMealPlan>, AppError> {
+    let mut cmd: GenerateMealPlanCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state
+        .meal_plan_service
+        .generate(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
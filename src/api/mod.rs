@@ -1,8 +1,25 @@
+mod allergen;
+mod api_key;
+mod auth;
 mod category;
+mod collection;
+mod cooking_log;
+mod diary;
+mod favorite;
+mod identity;
+mod ingredient;
+mod meal_plan;
 mod model;
+mod pantry;
 mod recipe;
+mod recommendation;
+mod review;
+mod revision;
+mod shopping_list;
+mod substitution;
+mod user;
 
-use axum::{routing::get, Router};
+use axum::{middleware, routing::get, Router};
 use tower_http::cors::{self, CorsLayer};
 
 use crate::state::AppState;
@@ -15,10 +32,31 @@ pub fn new_api(state: AppState) -> Router {
 
     Router::new()
         .route("/ping", get(ping))
+        .nest("/allergens", allergen::build(state.clone()))
+        .nest("/api-keys", api_key::build(state.clone()))
+        .nest("/auth", auth::build(state.clone()))
+        .nest("/me/favorites", favorite::build(state.clone()))
+        .nest("/ingredients", ingredient::build(state.clone()))
+        .nest("/meal-plans", meal_plan::build(state.clone()))
+        .nest("/pantry", pantry::build(state.clone()))
+        .nest("/shopping-lists", shopping_list::build(state.clone()))
+        .nest("/substitutions", substitution::build(state.clone()))
+        .nest("/users", user::build(state.clone()))
         .nest(
             "/recipes",
-            recipe::build(state.clone()).nest("/categories", category::build(state.clone())),
+            recipe::build(state.clone())
+                .merge(review::build(state.clone()))
+                .merge(revision::build(state.clone()))
+                .nest("/categories", category::build(state.clone())),
         )
+        .merge(recommendation::build(state.clone()))
+        .merge(collection::build(state.clone()))
+        .merge(cooking_log::build(state.clone()))
+        .merge(diary::build(state.clone()))
+        .layer(middleware::from_fn_with_state(
+            state,
+            identity::authenticate_api_key,
+        ))
         .layer(cors)
 }
 
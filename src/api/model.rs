@@ -1,9 +1,10 @@
-use std::error::Error;
+use std::{collections::BTreeMap, error::Error};
 
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::response::Response;
 use axum::Json;
+use chrono::{Days, NaiveDate, NaiveDateTime, Utc};
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -13,14 +14,24 @@ use crate::model;
 #[serde(rename_all = "camelCase")]
 pub struct Category {
     pub id: String,
+    pub slug: String,
     pub name: String,
+    pub description: String,
+    pub image: String,
+    pub sort_order: i32,
+    pub recipe_count: i64,
 }
 
 impl From<model::Category> for Category {
     fn from(value: model::Category) -> Self {
         Self {
             id: value.id,
+            slug: value.slug,
             name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
+            recipe_count: value.recipe_count,
         }
     }
 }
@@ -28,26 +39,113 @@ impl From<model::Category> for Category {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateCategory {
+    #[serde(default)]
+    pub slug: String,
     pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub image: String,
+    #[serde(default)]
+    pub sort_order: i32,
 }
 
-impl Into<model::CreateCategoryCommand> for CreateCategory {
-    fn into(self) -> model::CreateCategoryCommand {
-        model::CreateCategoryCommand { name: self.name }
+impl From<CreateCategory> for model::CreateCategoryCommand {
+    fn from(value: CreateCategory) -> Self {
+        model::CreateCategoryCommand {
+            slug: value.slug,
+            name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
+            actor: model::Actor::default(),
+        }
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCategory {
-    pub name: String,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub sort_order: Option<i32>,
 }
 
-impl Into<model::UpdateCategoryCommand> for UpdateCategory {
-    fn into(self) -> model::UpdateCategoryCommand {
+impl From<UpdateCategory> for model::UpdateCategoryCommand {
+    fn from(value: UpdateCategory) -> Self {
         model::UpdateCategoryCommand {
             id: String::default(),
-            name: self.name,
+            actor: model::Actor::default(),
+            slug: value.slug,
+            name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<SortDirection> for model::SortDirection {
+    fn from(value: SortDirection) -> Self {
+        match value {
+            SortDirection::Asc => model::SortDirection::Asc,
+            SortDirection::Desc => model::SortDirection::Desc,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CategorySort {
+    #[default]
+    Order,
+    Name,
+    RecipeCount,
+}
+
+impl From<CategorySort> for model::CategorySort {
+    fn from(value: CategorySort) -> Self {
+        match value {
+            CategorySort::Order => model::CategorySort::Order,
+            CategorySort::Name => model::CategorySort::Name,
+            CategorySort::RecipeCount => model::CategorySort::RecipeCount,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySearchQuery {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub sort: CategorySort,
+    #[serde(default)]
+    pub order: SortDirection,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<CategorySearchQuery> for model::CategorySearchQuery {
+    fn from(value: CategorySearchQuery) -> Self {
+        model::CategorySearchQuery {
+            ids: None,
+            name: value.name.filter(|name| !name.trim().is_empty()),
+            sort: value.sort.into(),
+            direction: value.order.into(),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
         }
     }
 }
@@ -65,8 +163,19 @@ pub struct Recipe {
     pub servings: i64,
     pub category: Category,
     pub ingredients: Vec<String>,
+    pub allergens: Vec<Allergen>,
+    pub diets: Vec<Diet>,
     pub nutrients: Nutrients,
+    pub nutrients_computed: bool,
     pub guideline: String,
+    pub author_id: Option<String>,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    pub is_favorite: bool,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
+    pub status: RecipeStatus,
 }
 
 impl From<model::Recipe> for Recipe {
@@ -81,30 +190,409 @@ impl From<model::Recipe> for Recipe {
             servings: value.servings,
             category: value.category.into(),
             ingredients: value.ingredients,
+            allergens: value
+                .allergens
+                .into_iter()
+                .map(|item| item.into())
+                .collect(),
+            diets: value.diets.into_iter().map(|item| item.into()).collect(),
             nutrients: value.nutrients.into(),
+            nutrients_computed: value.nutrients_computed,
             guideline: value.guideline,
+            author_id: value.author_id,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            is_favorite: value.is_favorite,
+            rating_average: value.rating_average,
+            rating_count: value.rating_count,
+            status: value.status.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecipeStatus {
+    #[default]
+    Draft,
+    InReview,
+    Published,
+    Archived,
+}
+
+impl From<RecipeStatus> for model::RecipeStatus {
+    fn from(value: RecipeStatus) -> Self {
+        match value {
+            RecipeStatus::Draft => model::RecipeStatus::Draft,
+            RecipeStatus::InReview => model::RecipeStatus::InReview,
+            RecipeStatus::Published => model::RecipeStatus::Published,
+            RecipeStatus::Archived => model::RecipeStatus::Archived,
+        }
+    }
+}
+
+impl From<model::RecipeStatus> for RecipeStatus {
+    fn from(value: model::RecipeStatus) -> Self {
+        match value {
+            model::RecipeStatus::Draft => RecipeStatus::Draft,
+            model::RecipeStatus::InReview => RecipeStatus::InReview,
+            model::RecipeStatus::Published => RecipeStatus::Published,
+            model::RecipeStatus::Archived => RecipeStatus::Archived,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Allergen {
+    pub code: String,
+    pub name: String,
+    pub custom: bool,
+}
+
+impl From<model::Allergen> for Allergen {
+    fn from(value: model::Allergen) -> Self {
+        Self {
+            code: value.code,
+            name: value.name,
+            custom: value.custom,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAllergen {
+    pub code: String,
+    pub name: String,
+}
+
+impl From<CreateAllergen> for model::CreateAllergenCommand {
+    fn from(value: CreateAllergen) -> Self {
+        model::CreateAllergenCommand {
+            code: value.code,
+            name: value.name,
+            actor: model::Actor::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Diet {
+    Vegan,
+    Vegetarian,
+    Keto,
+    GlutenFree,
+}
+
+impl From<model::Diet> for Diet {
+    fn from(value: model::Diet) -> Self {
+        match value {
+            model::Diet::Vegan => Diet::Vegan,
+            model::Diet::Vegetarian => Diet::Vegetarian,
+            model::Diet::Keto => Diet::Keto,
+            model::Diet::GlutenFree => Diet::GlutenFree,
+        }
+    }
+}
+
+impl From<Diet> for model::Diet {
+    fn from(value: Diet) -> Self {
+        match value {
+            Diet::Vegan => model::Diet::Vegan,
+            Diet::Vegetarian => model::Diet::Vegetarian,
+            Diet::Keto => model::Diet::Keto,
+            Diet::GlutenFree => model::Diet::GlutenFree,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ingredient {
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub animal: bool,
+    pub meat: bool,
+    pub gluten: bool,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub aisle: String,
+}
+
+impl From<model::Ingredient> for Ingredient {
+    fn from(value: model::Ingredient) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            aliases: value.aliases,
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            nutrients: value.nutrients.map(|item| item.into()),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            aisle: value.aisle,
+        }
+    }
+}
+
+/// Nutrition values per 100 g.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientNutrients {
+    pub proteins: f64,
+    pub fats: f64,
+    #[serde(default)]
+    pub saturated_fat: f64,
+    pub carbohydrates: f64,
+    #[serde(default)]
+    pub sugars: f64,
+    pub fiber: f64,
+    /// Milligrams.
+    #[serde(default)]
+    pub sodium: f64,
+    /// Milligrams.
+    #[serde(default)]
+    pub cholesterol: f64,
+    pub kcal: f64,
+}
+
+impl From<model::IngredientNutrients> for IngredientNutrients {
+    fn from(value: model::IngredientNutrients) -> Self {
+        Self {
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value.sodium,
+            cholesterol: value.cholesterol,
+            kcal: value.kcal,
+        }
+    }
+}
+
+impl From<IngredientNutrients> for model::IngredientNutrients {
+    fn from(value: IngredientNutrients) -> Self {
+        model::IngredientNutrients {
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value.sodium,
+            cholesterol: value.cholesterol,
+            kcal: value.kcal,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateIngredient {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub animal: bool,
+    #[serde(default)]
+    pub meat: bool,
+    #[serde(default)]
+    pub gluten: bool,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    /// Store section, `other` when omitted.
+    pub aisle: Option<String>,
+}
+
+impl From<CreateIngredient> for model::CreateIngredientCommand {
+    fn from(value: CreateIngredient) -> Self {
+        model::CreateIngredientCommand {
+            name: value.name,
+            aliases: value.aliases,
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            nutrients: value.nutrients.map(|item| item.into()),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            aisle: value.aisle,
+            actor: model::Actor::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateIngredient {
+    pub name: Option<String>,
+    pub aliases: Option<Vec<String>>,
+    pub animal: Option<bool>,
+    pub meat: Option<bool>,
+    pub gluten: Option<bool>,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub aisle: Option<String>,
+}
+
+impl From<UpdateIngredient> for model::UpdateIngredientCommand {
+    fn from(value: UpdateIngredient) -> Self {
+        model::UpdateIngredientCommand {
+            id: String::default(),
+            name: value.name,
+            aliases: value.aliases,
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            nutrients: value.nutrients.map(|item| item.into()),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            aisle: value.aisle,
+            actor: model::Actor::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub imported: usize,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientSearchQuery {
+    pub name: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<IngredientSearchQuery> for model::IngredientSearchQuery {
+    fn from(value: IngredientSearchQuery) -> Self {
+        model::IngredientSearchQuery {
+            name: value.name.filter(|name| !name.trim().is_empty()),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclassifyResult {
+    pub changed: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeResult {
+    pub recipes: i64,
+    pub categories: i64,
+}
+
+impl From<model::PurgeResult> for PurgeResult {
+    fn from(value: model::PurgeResult) -> Self {
+        Self {
+            recipes: value.recipes,
+            categories: value.categories,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NutrientBasis {
+    #[default]
+    PerServing,
+    PerRecipe,
+    #[serde(rename = "per100g")]
+    Per100g,
+}
+
+impl From<NutrientBasis> for model::NutrientBasis {
+    fn from(value: NutrientBasis) -> Self {
+        match value {
+            NutrientBasis::PerServing => model::NutrientBasis::PerServing,
+            NutrientBasis::PerRecipe => model::NutrientBasis::PerRecipe,
+            NutrientBasis::Per100g => model::NutrientBasis::Per100g,
+        }
+    }
+}
+
+impl From<model::NutrientBasis> for NutrientBasis {
+    fn from(value: model::NutrientBasis) -> Self {
+        match value {
+            model::NutrientBasis::PerServing => NutrientBasis::PerServing,
+            model::NutrientBasis::PerRecipe => NutrientBasis::PerRecipe,
+            model::NutrientBasis::Per100g => NutrientBasis::Per100g,
         }
     }
 }
 
+/// Sodium in milligrams to salt in grams.
+fn salt_from_sodium(sodium: f64) -> f64 {
+    (sodium * 2.5 / 10.0).round() / 100.0
+}
+
+/// Salt in grams to sodium in milligrams.
+fn sodium_from_salt(salt: f64) -> f64 {
+    salt * 400.0
+}
+
+/// Nutrient profile. Sodium and cholesterol are in milligrams, `salt` is
+/// derived from sodium and only used on input when `sodium` is missing.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Nutrients {
-    pub proteins: i64,
+    #[serde(default)]
+    pub basis: NutrientBasis,
+    pub serving_weight: Option<f64>,
+    pub kcal: f64,
+    pub proteins: f64,
     pub fats: f64,
+    #[serde(default)]
+    pub saturated_fat: f64,
     pub carbohydrates: f64,
-    pub fiber: i64,
-    pub kcal: i64,
+    #[serde(default)]
+    pub sugars: f64,
+    pub fiber: f64,
+    pub sodium: Option<f64>,
+    pub salt: Option<f64>,
+    #[serde(default)]
+    pub cholesterol: f64,
+    #[serde(default)]
+    pub micronutrients: BTreeMap<String, f64>,
 }
 
-impl Into<model::Nutrients> for Nutrients {
-    fn into(self) -> model::Nutrients {
+impl From<Nutrients> for model::Nutrients {
+    fn from(value: Nutrients) -> Self {
         model::Nutrients {
-            proteins: self.proteins,
-            fats: self.fats,
-            carbohydrates: self.carbohydrates,
-            fiber: self.fiber,
-            kcal: self.kcal,
+            basis: value.basis.into(),
+            serving_weight: value.serving_weight,
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value
+                .sodium
+                .or(value.salt.map(sodium_from_salt))
+                .unwrap_or_default(),
+            cholesterol: value.cholesterol,
+            micronutrients: value.micronutrients,
         }
     }
 }
@@ -112,11 +600,19 @@ impl Into<model::Nutrients> for Nutrients {
 impl From<model::Nutrients> for Nutrients {
     fn from(value: model::Nutrients) -> Self {
         Self {
+            basis: value.basis.into(),
+            serving_weight: value.serving_weight,
+            kcal: value.kcal,
             proteins: value.proteins,
             fats: value.fats,
+            saturated_fat: value.saturated_fat,
             carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
             fiber: value.fiber,
-            kcal: value.kcal,
+            sodium: Some(value.sodium),
+            salt: Some(salt_from_sodium(value.sodium)),
+            cholesterol: value.cholesterol,
+            micronutrients: value.micronutrients,
         }
     }
 }
@@ -132,23 +628,38 @@ pub struct CreateRecipe {
     pub servings: i64,
     pub category: String,
     pub ingredients: Vec<String>,
-    pub nutrients: Nutrients,
+    #[serde(default)]
+    pub allergens: Vec<String>,
+    /// Manual nutrients. When omitted they are computed from the ingredients.
+    pub nutrients: Option<Nutrients>,
     pub guideline: String,
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub attribution: String,
+    #[serde(default)]
+    pub license: String,
 }
 
-impl Into<model::CreateRecipeCommand> for CreateRecipe {
-    fn into(self) -> model::CreateRecipeCommand {
+impl From<CreateRecipe> for model::CreateRecipeCommand {
+    fn from(value: CreateRecipe) -> Self {
         model::CreateRecipeCommand {
-            cover: self.cover.into_bytes(),
-            title: self.title,
-            description: self.description,
-            time_to_cook: self.time_to_cook,
-            difficulty: self.difficulty,
-            servings: self.servings,
-            category: self.category,
-            ingredients: self.ingredients,
-            nutrients: self.nutrients.into(),
-            guideline: self.guideline,
+            cover: value.cover.into_bytes(),
+            title: value.title,
+            description: value.description,
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty,
+            servings: value.servings,
+            category: value.category,
+            ingredients: value.ingredients,
+            allergens: value.allergens,
+            diets: Vec::new(),
+            compute_nutrients: value.nutrients.is_none(),
+            nutrients: value.nutrients.unwrap_or_default().into(),
+            guideline: value.guideline,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            actor: model::Actor::default(),
         }
     }
 }
@@ -164,27 +675,40 @@ pub struct UpdateRecipe {
     pub servings: Option<i64>,
     pub category: Option<String>,
     pub ingredients: Option<Vec<String>>,
+    pub allergens: Option<Vec<String>>,
+    #[serde(default)]
     pub nutrients: UpdateNutrients,
+    /// `true` recomputes nutrients from the ingredients on every change,
+    /// `false` keeps the manual values.
+    pub compute_nutrients: Option<bool>,
     pub guideline: Option<String>,
+    /// An empty value removes the source.
+    pub source_url: Option<String>,
+    pub attribution: Option<String>,
+    pub license: Option<String>,
 }
 
-impl Into<model::UpdateRecipeCommand> for UpdateRecipe {
-    fn into(self) -> model::UpdateRecipeCommand {
+impl From<UpdateRecipe> for model::UpdateRecipeCommand {
+    fn from(value: UpdateRecipe) -> Self {
         model::UpdateRecipeCommand {
             id: String::default(),
-            cover: match self.cover {
-                Some(item) => Some(item.into_bytes()),
-                None => None,
-            },
-            title: self.title,
-            description: self.description,
-            time_to_cook: self.time_to_cook,
-            difficulty: self.difficulty,
-            servings: self.servings,
-            category: self.category,
-            ingredients: self.ingredients,
-            nutrients: self.nutrients.into(),
-            guideline: self.guideline,
+            actor: model::Actor::default(),
+            cover: value.cover.map(String::into_bytes),
+            title: value.title,
+            description: value.description,
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty,
+            servings: value.servings,
+            category: value.category,
+            ingredients: value.ingredients,
+            allergens: value.allergens,
+            diets: None,
+            nutrients: value.nutrients.into(),
+            compute_nutrients: value.compute_nutrients,
+            guideline: value.guideline,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
         }
     }
 }
@@ -192,21 +716,70 @@ impl Into<model::UpdateRecipeCommand> for UpdateRecipe {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateNutrients {
-    pub proteins: Option<i64>,
+    /// Basis of the submitted values.
+    #[serde(default)]
+    pub basis: NutrientBasis,
+    pub serving_weight: Option<f64>,
+    pub kcal: Option<f64>,
+    pub proteins: Option<f64>,
     pub fats: Option<f64>,
+    pub saturated_fat: Option<f64>,
     pub carbohydrates: Option<f64>,
-    pub fiber: Option<i64>,
-    pub kcal: Option<i64>,
+    pub sugars: Option<f64>,
+    pub fiber: Option<f64>,
+    pub sodium: Option<f64>,
+    pub salt: Option<f64>,
+    pub cholesterol: Option<f64>,
+    pub micronutrients: Option<BTreeMap<String, f64>>,
 }
 
-impl Into<model::UpdateNutrients> for UpdateNutrients {
-    fn into(self) -> model::UpdateNutrients {
+impl From<UpdateNutrients> for model::UpdateNutrients {
+    fn from(value: UpdateNutrients) -> Self {
         model::UpdateNutrients {
-            proteins: self.proteins,
-            fats: self.fats,
-            carbohydrates: self.carbohydrates,
-            fiber: self.fiber,
-            kcal: self.kcal,
+            basis: value.basis.into(),
+            serving_weight: value.serving_weight,
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value.sodium.or(value.salt.map(sodium_from_salt)),
+            cholesterol: value.cholesterol,
+            micronutrients: value.micronutrients,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalculateNutrients {
+    pub ingredients: Vec<String>,
+    pub servings: i64,
+}
+
+impl From<CalculateNutrients> for model::CalculateNutrientsQuery {
+    fn from(value: CalculateNutrients) -> Self {
+        model::CalculateNutrientsQuery {
+            ingredients: value.ingredients,
+            servings: value.servings,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NutrientsCalculation {
+    pub nutrients: Nutrients,
+    pub unmatched: Vec<String>,
+}
+
+impl From<model::NutrientsCalculation> for NutrientsCalculation {
+    fn from(value: model::NutrientsCalculation) -> Self {
+        Self {
+            nutrients: value.nutrients.into(),
+            unmatched: value.unmatched,
         }
     }
 }
@@ -215,16 +788,93 @@ impl Into<model::UpdateNutrients> for UpdateNutrients {
 #[serde(rename_all = "camelCase")]
 pub struct RecipeSearchQuery {
     pub category_id: Option<String>,
+    /// Comma separated allergen codes, e.g. `excludeAllergens=milk,eggs`.
+    pub exclude_allergens: Option<String>,
+    /// Comma separated dietary labels every recipe must have, e.g.
+    /// `diets=vegan,gluten-free`. Unknown labels are ignored.
+    pub diets: Option<String>,
+    pub max_time_to_cook: Option<i64>,
+    /// Only recipes using pantry items of the current user expiring within
+    /// this many days.
+    pub expiring_within_days: Option<i64>,
+    pub author_id: Option<String>,
+    /// Only recipes of the current user.
+    #[serde(default)]
+    pub mine: bool,
+    pub min_rating: Option<f64>,
+    /// Recipes of other users are only found once published, unless the
+    /// current user is an editor.
+    pub status: Option<RecipeStatus>,
+    #[serde(default)]
+    pub sort: RecipeSort,
+    #[serde(default)]
+    pub order: SortDirection,
+    #[serde(default)]
+    pub basis: NutrientBasis,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecipeSort {
+    #[default]
+    Created,
+    Title,
+    Rating,
+}
+
+impl From<RecipeSort> for model::RecipeSort {
+    fn from(value: RecipeSort) -> Self {
+        match value {
+            RecipeSort::Created => model::RecipeSort::Created,
+            RecipeSort::Title => model::RecipeSort::Title,
+            RecipeSort::Rating => model::RecipeSort::Rating,
+        }
+    }
 }
 
-impl Into<model::RecipeSearchQuery> for RecipeSearchQuery {
-    fn into(self) -> model::RecipeSearchQuery {
+impl From<RecipeSearchQuery> for model::RecipeSearchQuery {
+    fn from(value: RecipeSearchQuery) -> Self {
         model::RecipeSearchQuery {
-            category_id: self.category_id,
+            ids: None,
+            category_id: value.category_id,
+            exclude_allergens: value.exclude_allergens.map(|v| split_list(&v)),
+            diets: value.diets.map(|v| {
+                split_list(&v)
+                    .iter()
+                    .filter_map(|code| model::Diet::from_code(code))
+                    .collect()
+            }),
+            max_time_to_cook: value.max_time_to_cook,
+            ingredients: None,
+            author_id: value.author_id,
+            min_rating: value.min_rating,
+            status: value.status.map(model::RecipeStatus::from),
+            visibility: model::RecipeVisibility::default(),
+            sort: value.sort.into(),
+            direction: value.order.into(),
+            basis: value.basis.into(),
+            viewer_id: None,
         }
     }
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeFetchQuery {
+    #[serde(default)]
+    pub basis: NutrientBasis,
+}
+
+/// Splits a comma separated query parameter, dropping empty entries.
+pub(super) fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResult<T> {
@@ -254,10 +904,26 @@ struct ErrResponse {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let status = match self.0.downcast_ref::<model::ServiceError>() {
+            Some(model::ServiceError::Unauthorized(_)) => StatusCode::UNAUTHORIZED,
+            Some(model::ServiceError::Forbidden(_)) => StatusCode::FORBIDDEN,
+            Some(model::ServiceError::Invalid(_)) => StatusCode::BAD_REQUEST,
+            Some(model::ServiceError::RateLimited(_)) => StatusCode::TOO_MANY_REQUESTS,
+            None => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrResponse {
+                        msg: format!("Something went wrong: {}", self.0),
+                    }),
+                )
+                    .into_response()
+            }
+        };
+
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            status,
             Json(ErrResponse {
-                msg: format!("Something went wrong: {}", self.0),
+                msg: self.0.to_string(),
             }),
         )
             .into_response()
@@ -272,3 +938,1942 @@ where
         Self(err.into())
     }
 }
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LabelFormat {
+    #[default]
+    Eu,
+    Us,
+}
+
+impl From<LabelFormat> for model::LabelFormat {
+    fn from(value: LabelFormat) -> Self {
+        match value {
+            LabelFormat::Eu => model::LabelFormat::Eu,
+            LabelFormat::Us => model::LabelFormat::Us,
+        }
+    }
+}
+
+impl From<model::LabelFormat> for LabelFormat {
+    fn from(value: model::LabelFormat) -> Self {
+        match value {
+            model::LabelFormat::Eu => LabelFormat::Eu,
+            model::LabelFormat::Us => LabelFormat::Us,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LabelOutput {
+    #[default]
+    Json,
+    Svg,
+    Html,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NutritionLabelQuery {
+    #[serde(default)]
+    pub format: LabelFormat,
+    #[serde(default, rename = "as")]
+    pub output: LabelOutput,
+    /// Daily energy intake in kcal the percentages refer to.
+    pub reference_kcal: Option<f64>,
+}
+
+impl From<NutritionLabelQuery> for model::NutritionLabelQuery {
+    fn from(value: NutritionLabelQuery) -> Self {
+        model::NutritionLabelQuery {
+            recipe_id: String::default(),
+            format: value.format.into(),
+            reference_kcal: value.reference_kcal,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NutritionLabel {
+    pub format: LabelFormat,
+    pub title: String,
+    pub servings: i64,
+    pub serving_weight: Option<f64>,
+    pub reference_kcal: f64,
+    pub rows: Vec<LabelRow>,
+}
+
+impl From<model::NutritionLabel> for NutritionLabel {
+    fn from(value: model::NutritionLabel) -> Self {
+        Self {
+            format: value.format.into(),
+            title: value.title,
+            servings: value.servings,
+            serving_weight: value.serving_weight,
+            reference_kcal: value.reference_kcal,
+            rows: value.rows.into_iter().map(LabelRow::from).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelRow {
+    pub name: String,
+    pub unit: String,
+    pub indent: bool,
+    pub per_serving: f64,
+    pub per_100g: Option<f64>,
+    /// Percent of the reference intake per serving.
+    pub daily_value: Option<f64>,
+}
+
+impl From<model::LabelRow> for LabelRow {
+    fn from(value: model::LabelRow) -> Self {
+        Self {
+            name: value.name,
+            unit: value.unit,
+            indent: value.indent,
+            per_serving: value.per_serving,
+            per_100g: value.per_100g,
+            daily_value: value.daily_value,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Meal {
+    #[default]
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+impl From<Meal> for model::Meal {
+    fn from(value: Meal) -> Self {
+        match value {
+            Meal::Breakfast => model::Meal::Breakfast,
+            Meal::Lunch => model::Meal::Lunch,
+            Meal::Dinner => model::Meal::Dinner,
+            Meal::Snack => model::Meal::Snack,
+        }
+    }
+}
+
+impl From<model::Meal> for Meal {
+    fn from(value: model::Meal) -> Self {
+        match value {
+            model::Meal::Breakfast => Meal::Breakfast,
+            model::Meal::Lunch => Meal::Lunch,
+            model::Meal::Dinner => Meal::Dinner,
+            model::Meal::Snack => Meal::Snack,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MealSlot {
+    pub date: NaiveDate,
+    pub meal: Meal,
+    pub recipe_id: String,
+    /// Servings eaten, one when omitted.
+    pub servings: Option<i64>,
+}
+
+impl From<MealSlot> for model::MealSlot {
+    fn from(value: MealSlot) -> Self {
+        model::MealSlot {
+            date: value.date,
+            meal: value.meal.into(),
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+impl From<model::MealSlot> for MealSlot {
+    fn from(value: model::MealSlot) -> Self {
+        Self {
+            date: value.date,
+            meal: value.meal.into(),
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MealPlan {
+    pub id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub slots: Vec<MealSlot>,
+}
+
+impl From<model::MealPlan> for MealPlan {
+    fn from(value: model::MealPlan) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            start_date: value.start_date,
+            end_date: value.end_date,
+            slots: value.slots.into_iter().map(MealSlot::from).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMealPlan {
+    #[serde(default)]
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default)]
+    pub slots: Vec<MealSlot>,
+}
+
+impl From<CreateMealPlan> for model::CreateMealPlanCommand {
+    fn from(value: CreateMealPlan) -> Self {
+        model::CreateMealPlanCommand {
+            owner_id: String::default(),
+            name: value.name,
+            start_date: value.start_date,
+            end_date: value.end_date,
+            slots: value.slots.into_iter().map(model::MealSlot::from).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMealPlan {
+    pub name: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Replaces all slots of the plan.
+    pub slots: Option<Vec<MealSlot>>,
+}
+
+impl From<UpdateMealPlan> for model::UpdateMealPlanCommand {
+    fn from(value: UpdateMealPlan) -> Self {
+        model::UpdateMealPlanCommand {
+            id: String::default(),
+            owner_id: String::default(),
+            name: value.name,
+            start_date: value.start_date,
+            end_date: value.end_date,
+            slots: value
+                .slots
+                .map(|slots| slots.into_iter().map(model::MealSlot::from).collect()),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MealPlanSearchQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<MealPlanSearchQuery> for model::MealPlanSearchQuery {
+    fn from(value: MealPlanSearchQuery) -> Self {
+        model::MealPlanSearchQuery {
+            owner_id: String::default(),
+            from: value.from,
+            to: value.to,
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MealPlanSummary {
+    pub id: String,
+    pub days: Vec<PeriodSummary>,
+    pub weeks: Vec<PeriodSummary>,
+    pub total: Nutrients,
+    pub missing_recipes: Vec<String>,
+}
+
+impl From<model::MealPlanSummary> for MealPlanSummary {
+    fn from(value: model::MealPlanSummary) -> Self {
+        Self {
+            id: value.id,
+            days: value.days.into_iter().map(PeriodSummary::from).collect(),
+            weeks: value.weeks.into_iter().map(PeriodSummary::from).collect(),
+            total: value.total.into(),
+            missing_recipes: value.missing_recipes,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodSummary {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub nutrients: Nutrients,
+}
+
+impl From<model::PeriodSummary> for PeriodSummary {
+    fn from(value: model::PeriodSummary) -> Self {
+        Self {
+            start_date: value.start_date,
+            end_date: value.end_date,
+            nutrients: value.nutrients.into(),
+        }
+    }
+}
+
+/// Daily targets, macronutrients in grams.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NutritionTargets {
+    pub kcal: f64,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+}
+
+impl From<NutritionTargets> for model::NutritionTargets {
+    fn from(value: NutritionTargets) -> Self {
+        model::NutritionTargets {
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            carbohydrates: value.carbohydrates,
+        }
+    }
+}
+
+impl From<model::NutritionTargets> for NutritionTargets {
+    fn from(value: model::NutritionTargets) -> Self {
+        Self {
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            carbohydrates: value.carbohydrates,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateMealPlan {
+    #[serde(default)]
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub days: i64,
+    /// Meals to plan per day, breakfast, lunch and dinner when empty.
+    #[serde(default)]
+    pub meals: Vec<Meal>,
+    pub targets: NutritionTargets,
+    #[serde(default)]
+    pub diets: Vec<Diet>,
+    #[serde(default)]
+    pub exclude_allergens: Vec<String>,
+    #[serde(default)]
+    pub exclude_ingredients: Vec<String>,
+    pub max_time_to_cook: Option<i64>,
+}
+
+impl From<GenerateMealPlan> for model::GenerateMealPlanCommand {
+    fn from(value: GenerateMealPlan) -> Self {
+        model::GenerateMealPlanCommand {
+            owner_id: String::default(),
+            name: value.name,
+            start_date: value.start_date,
+            days: value.days,
+            meals: value.meals.into_iter().map(model::Meal::from).collect(),
+            targets: value.targets.into(),
+            diets: value.diets.into_iter().map(model::Diet::from).collect(),
+            exclude_allergens: value.exclude_allergens,
+            exclude_ingredients: value.exclude_ingredients,
+            max_time_to_cook: value.max_time_to_cook,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedMealPlan {
+    pub plan: MealPlan,
+    pub days: Vec<DayDeviation>,
+}
+
+impl From<model::GeneratedMealPlan> for GeneratedMealPlan {
+    fn from(value: model::GeneratedMealPlan) -> Self {
+        Self {
+            plan: value.plan.into(),
+            days: value.days.into_iter().map(DayDeviation::from).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayDeviation {
+    pub date: NaiveDate,
+    pub nutrients: Nutrients,
+    /// Planned minus target amounts.
+    pub deviation: NutritionTargets,
+}
+
+impl From<model::DayDeviation> for DayDeviation {
+    fn from(value: model::DayDeviation) -> Self {
+        Self {
+            date: value.date,
+            nutrients: value.nutrients.into(),
+            deviation: value.deviation.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShoppingList {
+    pub id: String,
+    pub name: String,
+    pub meal_plan_id: Option<String>,
+    pub aisles: Vec<Aisle>,
+    pub missing_recipes: Vec<String>,
+}
+
+impl From<model::ShoppingList> for ShoppingList {
+    fn from(value: model::ShoppingList) -> Self {
+        let mut aisles: Vec<Aisle> = Vec::new();
+
+        for item in value.items {
+            match aisles.last_mut() {
+                Some(aisle) if aisle.name == item.aisle => aisle.items.push(item.into()),
+                _ => aisles.push(Aisle {
+                    name: item.aisle.clone(),
+                    items: vec![item.into()],
+                }),
+            }
+        }
+
+        Self {
+            id: value.id,
+            name: value.name,
+            meal_plan_id: value.meal_plan_id,
+            aisles,
+            missing_recipes: value.missing_recipes,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Aisle {
+    pub name: String,
+    pub items: Vec<ShoppingListItem>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShoppingListItem {
+    pub id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    /// `g`, `kg`, `ml`, `l` or `pcs`.
+    pub unit: Option<String>,
+    pub checked: bool,
+}
+
+impl From<model::ShoppingListItem> for ShoppingListItem {
+    fn from(value: model::ShoppingListItem) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            checked: value.checked,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeServings {
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
+
+impl From<RecipeServings> for model::RecipeServings {
+    fn from(value: RecipeServings) -> Self {
+        model::RecipeServings {
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+/// Either `recipes` or `mealPlanId` is given, the meal plan wins.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateShoppingList {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub recipes: Vec<RecipeServings>,
+    pub meal_plan_id: Option<String>,
+    /// Keeps items already in the pantry on the list.
+    #[serde(default)]
+    pub ignore_pantry: bool,
+}
+
+impl From<CreateShoppingList> for model::CreateShoppingListCommand {
+    fn from(value: CreateShoppingList) -> Self {
+        model::CreateShoppingListCommand {
+            owner_id: String::default(),
+            name: value.name,
+            recipes: value
+                .recipes
+                .into_iter()
+                .map(model::RecipeServings::from)
+                .collect(),
+            meal_plan_id: value.meal_plan_id,
+            ignore_pantry: value.ignore_pantry,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckShoppingListItem {
+    pub checked: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShoppingListSearchQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<ShoppingListSearchQuery> for model::ShoppingListSearchQuery {
+    fn from(value: ShoppingListSearchQuery) -> Self {
+        model::ShoppingListSearchQuery {
+            owner_id: String::default(),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PantryItem {
+    pub id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl From<model::PantryItem> for PantryItem {
+    fn from(value: model::PantryItem) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            expires_on: value.expires_on,
+        }
+    }
+}
+
+/// `unit` is one of `mg`, `g`, `kg`, `oz`, `lb`, `ml`, `l`, `tsp`, `tbsp`,
+/// `cup` or `pcs`, pieces when a quantity comes without one.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePantryItem {
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl TryFrom<CreatePantryItem> for model::CreatePantryItemCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: CreatePantryItem) -> Result<Self, Self::Error> {
+        Ok(model::CreatePantryItemCommand {
+            owner_id: String::default(),
+            name: value.name,
+            quantity: value.quantity,
+            unit: parse_unit(value.unit)?,
+            expires_on: value.expires_on,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePantryItem {
+    pub name: Option<String>,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl TryFrom<UpdatePantryItem> for model::UpdatePantryItemCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UpdatePantryItem) -> Result<Self, Self::Error> {
+        Ok(model::UpdatePantryItemCommand {
+            id: String::default(),
+            owner_id: String::default(),
+            name: value.name,
+            quantity: value.quantity,
+            unit: parse_unit(value.unit)?,
+            expires_on: value.expires_on,
+        })
+    }
+}
+
+fn parse_unit(code: Option<String>) -> Result<Option<model::Unit>, Box<dyn Error>> {
+    code.map(|code| model::Unit::from_code(&code).ok_or_else(|| format!("unknown unit {}", code)))
+        .transpose()
+        .map_err(Into::into)
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PantrySearchQuery {
+    pub expires_before: Option<NaiveDate>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<PantrySearchQuery> for model::PantrySearchQuery {
+    fn from(value: PantrySearchQuery) -> Self {
+        model::PantrySearchQuery {
+            owner_id: String::default(),
+            expires_before: value.expires_before,
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookRecipe {
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
+
+impl From<CookRecipe> for model::CookRecipeCommand {
+    fn from(value: CookRecipe) -> Self {
+        model::CookRecipeCommand {
+            owner_id: String::default(),
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPreferences {
+    #[serde(default)]
+    pub diets: Vec<Diet>,
+    #[serde(default)]
+    pub exclude_allergens: Vec<String>,
+    /// Ids of preferred categories.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub disliked_ingredients: Vec<String>,
+}
+
+impl From<model::UserPreferences> for UserPreferences {
+    fn from(value: model::UserPreferences) -> Self {
+        Self {
+            diets: value.diets.into_iter().map(Diet::from).collect(),
+            exclude_allergens: value.exclude_allergens,
+            categories: value.categories,
+            disliked_ingredients: value.disliked_ingredients,
+        }
+    }
+}
+
+impl From<UserPreferences> for model::UserPreferences {
+    fn from(value: UserPreferences) -> Self {
+        model::UserPreferences {
+            owner_id: String::default(),
+            diets: value.diets.into_iter().map(model::Diet::from).collect(),
+            exclude_allergens: value.exclude_allergens,
+            categories: value.categories,
+            disliked_ingredients: value.disliked_ingredients,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationQuery {
+    pub limit: Option<i64>,
+}
+
+impl From<RecommendationQuery> for model::RecommendationQuery {
+    fn from(value: RecommendationQuery) -> Self {
+        model::RecommendationQuery {
+            owner_id: String::default(),
+            limit: value.limit,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecommendationReason {
+    #[default]
+    Profile,
+    Popular,
+}
+
+impl From<model::RecommendationReason> for RecommendationReason {
+    fn from(value: model::RecommendationReason) -> Self {
+        match value {
+            model::RecommendationReason::Profile => RecommendationReason::Profile,
+            model::RecommendationReason::Popular => RecommendationReason::Popular,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    pub recipe: Recipe,
+    pub score: f64,
+    pub reason: RecommendationReason,
+}
+
+impl From<model::Recommendation> for Recommendation {
+    fn from(value: model::Recommendation) -> Self {
+        Self {
+            recipe: value.recipe.into(),
+            score: value.score,
+            reason: value.reason.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarRecipesQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSimilarity {
+    pub ingredients: f64,
+    pub category: f64,
+    pub tags: f64,
+    pub nutrients: f64,
+    pub time: f64,
+    pub difficulty: f64,
+    pub score: f64,
+}
+
+impl From<model::RecipeSimilarity> for RecipeSimilarity {
+    fn from(value: model::RecipeSimilarity) -> Self {
+        Self {
+            ingredients: value.ingredients,
+            category: value.category,
+            tags: value.tags,
+            nutrients: value.nutrients,
+            time: value.time,
+            difficulty: value.difficulty,
+            score: value.score,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarRecipe {
+    pub recipe: Recipe,
+    pub similarity: RecipeSimilarity,
+}
+
+impl From<model::SimilarRecipe> for SimilarRecipe {
+    fn from(value: model::SimilarRecipe) -> Self {
+        Self {
+            recipe: value.recipe.into(),
+            similarity: value.similarity.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Substitution {
+    pub id: String,
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<String>,
+    pub avoids: Vec<String>,
+    pub notes: String,
+}
+
+impl From<model::Substitution> for Substitution {
+    fn from(value: model::Substitution) -> Self {
+        Self {
+            id: value.id,
+            ingredient: value.ingredient,
+            substitute: value.substitute,
+            ratio: value.ratio,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            avoids: value.avoids,
+            notes: value.notes,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubstitution {
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub avoids: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl TryFrom<CreateSubstitution> for model::CreateSubstitutionCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: CreateSubstitution) -> Result<Self, Self::Error> {
+        Ok(model::CreateSubstitutionCommand {
+            ingredient: value.ingredient,
+            substitute: value.substitute,
+            ratio: value.ratio,
+            unit: parse_unit(value.unit)?,
+            avoids: value.avoids,
+            notes: value.notes,
+            actor: model::Actor::default(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSubstitution {
+    pub ingredient: Option<String>,
+    pub substitute: Option<String>,
+    pub ratio: Option<f64>,
+    pub unit: Option<String>,
+    pub avoids: Option<Vec<String>>,
+    pub notes: Option<String>,
+}
+
+impl TryFrom<UpdateSubstitution> for model::UpdateSubstitutionCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UpdateSubstitution) -> Result<Self, Self::Error> {
+        Ok(model::UpdateSubstitutionCommand {
+            id: String::default(),
+            ingredient: value.ingredient,
+            substitute: value.substitute,
+            ratio: value.ratio,
+            unit: parse_unit(value.unit)?,
+            avoids: value.avoids,
+            notes: value.notes,
+            actor: model::Actor::default(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubstitutionSearchQuery {
+    pub ingredient: Option<String>,
+    /// Comma separated restrictions, e.g. `avoids=dairy,eggs`.
+    pub avoids: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<SubstitutionSearchQuery> for model::SubstitutionSearchQuery {
+    fn from(value: SubstitutionSearchQuery) -> Self {
+        model::SubstitutionSearchQuery {
+            ingredient: value.ingredient.filter(|name| !name.trim().is_empty()),
+            avoids: value.avoids.map(|v| split_list(&v)),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSubstitutionsQuery {
+    /// Comma separated restrictions, e.g. `avoid=dairy,gluten`.
+    #[serde(default)]
+    pub avoid: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedSwap {
+    pub substitution: Substitution,
+    pub line: String,
+}
+
+impl From<model::SuggestedSwap> for SuggestedSwap {
+    fn from(value: model::SuggestedSwap) -> Self {
+        Self {
+            substitution: value.substitution.into(),
+            line: value.line,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineSubstitutions {
+    pub line: String,
+    pub ingredient: String,
+    pub suggestions: Vec<SuggestedSwap>,
+}
+
+impl From<model::LineSubstitutions> for LineSubstitutions {
+    fn from(value: model::LineSubstitutions) -> Self {
+        Self {
+            line: value.line,
+            ingredient: value.ingredient,
+            suggestions: value
+                .suggestions
+                .into_iter()
+                .map(SuggestedSwap::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSubstitutions {
+    pub recipe: Recipe,
+    pub lines: Vec<LineSubstitutions>,
+    pub nutrients_delta: Nutrients,
+}
+
+impl From<model::RecipeSubstitutions> for RecipeSubstitutions {
+    fn from(value: model::RecipeSubstitutions) -> Self {
+        Self {
+            recipe: value.recipe.into(),
+            lines: value
+                .lines
+                .into_iter()
+                .map(LineSubstitutions::from)
+                .collect(),
+            nutrients_delta: value.nutrients_delta.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: String,
+    pub email: String,
+    pub name: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<model::User> for User {
+    fn from(value: model::User) -> Self {
+        Self {
+            id: value.id,
+            email: value.email,
+            name: value.name,
+            role: value.role.code().to_string(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRole {
+    pub role: String,
+}
+
+impl TryFrom<SetRole> for model::SetRoleCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: SetRole) -> Result<Self, Self::Error> {
+        let role = model::Role::from_code(&value.role)
+            .ok_or_else(|| model::ServiceError::Invalid(format!("unknown role {}", value.role)))?;
+
+        Ok(model::SetRoleCommand {
+            role,
+            ..model::SetRoleCommand::default()
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Register {
+    pub email: String,
+    pub password: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+impl From<Register> for model::RegisterCommand {
+    fn from(value: Register) -> Self {
+        model::RegisterCommand {
+            email: value.email,
+            password: value.password,
+            name: value.name,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Login {
+    pub email: String,
+    pub password: String,
+}
+
+impl From<Login> for model::LoginCommand {
+    fn from(value: Login) -> Self {
+        model::LoginCommand {
+            email: value.email,
+            password: value.password,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshToken {
+    pub refresh_token: String,
+}
+
+impl From<RefreshToken> for model::RefreshTokenCommand {
+    fn from(value: RefreshToken) -> Self {
+        model::RefreshTokenCommand {
+            refresh_token: value.refresh_token,
+        }
+    }
+}
+
+/// `all` logs out every session of the user instead of the current one.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Logout {
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub user: User,
+}
+
+impl From<model::AuthTokens> for AuthTokens {
+    fn from(value: model::AuthTokens) -> Self {
+        Self {
+            access_token: value.access_token,
+            refresh_token: value.refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: value.expires_in,
+            user: value.user.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub rate_limit: i32,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<model::ApiKey> for ApiKey {
+    fn from(value: model::ApiKey) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            scopes: value
+                .scopes
+                .iter()
+                .map(|scope| scope.code().to_string())
+                .collect(),
+            rate_limit: value.rate_limit,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// A key with its secret, returned once on creation and rotation.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiKey {
+    pub key: ApiKey,
+    pub secret: String,
+}
+
+impl From<model::IssuedApiKey> for IssuedApiKey {
+    fn from(value: model::IssuedApiKey) -> Self {
+        Self {
+            key: value.key.into(),
+            secret: value.secret,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKey {
+    #[serde(default)]
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// Requests per minute.
+    pub rate_limit: Option<i32>,
+}
+
+impl TryFrom<CreateApiKey> for model::CreateApiKeyCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: CreateApiKey) -> Result<Self, Self::Error> {
+        let scopes = value
+            .scopes
+            .iter()
+            .map(|code| {
+                model::ApiKeyScope::from_code(code)
+                    .ok_or_else(|| model::ServiceError::Invalid(format!("unknown scope {}", code)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(model::CreateApiKeyCommand {
+            actor: model::Actor::default(),
+            name: value.name,
+            scopes,
+            rate_limit: value.rate_limit,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoritesQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<FavoritesQuery> for model::FavoritesQuery {
+    fn from(value: FavoritesQuery) -> Self {
+        model::FavoritesQuery {
+            owner_id: String::default(),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionItem {
+    pub recipe_id: String,
+    #[serde(default)]
+    pub note: String,
+    /// Unset when the recipe no longer exists.
+    #[serde(default, skip_deserializing)]
+    pub recipe: Option<Recipe>,
+}
+
+impl From<CollectionItem> for model::CollectionItem {
+    fn from(value: CollectionItem) -> Self {
+        model::CollectionItem {
+            recipe_id: value.recipe_id,
+            note: value.note,
+            recipe: None,
+        }
+    }
+}
+
+impl From<model::CollectionItem> for CollectionItem {
+    fn from(value: model::CollectionItem) -> Self {
+        Self {
+            recipe_id: value.recipe_id,
+            note: value.note,
+            recipe: value.recipe.map(Recipe::from),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub shared: bool,
+    /// Anyone can view the collection at `/shared/collections/{shareToken}`.
+    pub share_token: Option<String>,
+    pub items: Vec<CollectionItem>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<model::Collection> for Collection {
+    fn from(value: model::Collection) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            shared: value.share_token.is_some(),
+            share_token: value.share_token,
+            items: value.items.into_iter().map(CollectionItem::from).collect(),
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCollection {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default)]
+    pub items: Vec<CollectionItem>,
+}
+
+impl From<CreateCollection> for model::CreateCollectionCommand {
+    fn from(value: CreateCollection) -> Self {
+        model::CreateCollectionCommand {
+            owner_id: String::default(),
+            name: value.name,
+            description: value.description,
+            shared: value.shared,
+            items: value
+                .items
+                .into_iter()
+                .map(model::CollectionItem::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCollection {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub shared: Option<bool>,
+    /// Replaces all items, in the given order.
+    pub items: Option<Vec<CollectionItem>>,
+}
+
+impl From<UpdateCollection> for model::UpdateCollectionCommand {
+    fn from(value: UpdateCollection) -> Self {
+        model::UpdateCollectionCommand {
+            id: String::default(),
+            owner_id: String::default(),
+            name: value.name,
+            description: value.description,
+            shared: value.shared,
+            items: value
+                .items
+                .map(|items| items.into_iter().map(model::CollectionItem::from).collect()),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionSearchQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<CollectionSearchQuery> for model::CollectionSearchQuery {
+    fn from(value: CollectionSearchQuery) -> Self {
+        model::CollectionSearchQuery {
+            owner_id: String::default(),
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    pub id: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub rating: i32,
+    pub body: String,
+    pub flagged: bool,
+    pub flag_reason: String,
+    pub hidden: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<model::Review> for Review {
+    fn from(value: model::Review) -> Self {
+        Self {
+            id: value.id,
+            recipe_id: value.recipe_id,
+            owner_id: value.owner_id,
+            rating: value.rating,
+            body: value.body,
+            flagged: value.flagged,
+            flag_reason: value.flag_reason,
+            hidden: value.hidden,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReview {
+    pub rating: i32,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl From<CreateReview> for model::CreateReviewCommand {
+    fn from(value: CreateReview) -> Self {
+        model::CreateReviewCommand {
+            recipe_id: String::default(),
+            owner_id: String::default(),
+            rating: value.rating,
+            body: value.body,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReview {
+    pub rating: Option<i32>,
+    pub body: Option<String>,
+}
+
+impl From<UpdateReview> for model::UpdateReviewCommand {
+    fn from(value: UpdateReview) -> Self {
+        model::UpdateReviewCommand {
+            id: String::default(),
+            recipe_id: String::default(),
+            owner_id: String::default(),
+            rating: value.rating,
+            body: value.body,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagReview {
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerateReview {
+    pub hidden: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSearchQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<ReviewSearchQuery> for model::Pagination {
+    fn from(value: ReviewSearchQuery) -> Self {
+        model::Pagination {
+            limit: value.limit,
+            offset: value.offset,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookingLogEntry {
+    pub id: String,
+    pub recipe_id: String,
+    pub recipe_title: String,
+    pub cooked_on: NaiveDate,
+    pub meal: Option<Meal>,
+    pub servings: f64,
+    pub notes: String,
+    pub photo: String,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<model::CookingLogEntry> for CookingLogEntry {
+    fn from(value: model::CookingLogEntry) -> Self {
+        Self {
+            id: value.id,
+            recipe_id: value.recipe_id,
+            recipe_title: value.recipe_title,
+            cooked_on: value.cooked_on,
+            meal: value.meal.map(Meal::from),
+            servings: value.servings,
+            notes: value.notes,
+            photo: String::from_utf8_lossy(&value.photo).into_owned(),
+            nutrients: value.nutrients.into(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// Defaults to one serving cooked today.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCooking {
+    pub recipe_id: String,
+    pub cooked_on: Option<NaiveDate>,
+    pub meal: Option<Meal>,
+    pub servings: Option<f64>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub photo: String,
+}
+
+impl From<LogCooking> for model::LogCookingCommand {
+    fn from(value: LogCooking) -> Self {
+        model::LogCookingCommand {
+            owner_id: String::default(),
+            recipe_id: value.recipe_id,
+            recipe_title: String::default(),
+            cooked_on: value.cooked_on.unwrap_or_else(|| Utc::now().date_naive()),
+            meal: value.meal.map(model::Meal::from),
+            servings: value.servings.unwrap_or(1.0),
+            notes: value.notes,
+            photo: value.photo.into_bytes(),
+            nutrients: model::Nutrients::default(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookingLogSearchQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<CookingLogSearchQuery> for model::CookingLogSearchQuery {
+    fn from(value: CookingLogSearchQuery) -> Self {
+        model::CookingLogSearchQuery {
+            owner_id: String::default(),
+            from: value.from,
+            to: value.to,
+            pagination: model::Pagination {
+                limit: value.limit,
+                offset: value.offset,
+            },
+        }
+    }
+}
+
+/// Defaults to the seven days up to and including `to`, which defaults to
+/// today.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntakeQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl From<IntakeQuery> for model::IntakeQuery {
+    fn from(value: IntakeQuery) -> Self {
+        let to = value.to.unwrap_or_else(|| Utc::now().date_naive());
+
+        model::IntakeQuery {
+            owner_id: String::default(),
+            from: value
+                .from
+                .unwrap_or_else(|| to.checked_sub_days(Days::new(6)).unwrap_or(to)),
+            to,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntakeSummary {
+    pub days: Vec<PeriodSummary>,
+    pub total: Nutrients,
+}
+
+impl From<model::IntakeSummary> for IntakeSummary {
+    fn from(value: model::IntakeSummary) -> Self {
+        Self {
+            days: value.days.into_iter().map(PeriodSummary::from).collect(),
+            total: value.total.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoodEntry {
+    pub id: String,
+    pub eaten_on: NaiveDate,
+    pub meal: Option<Meal>,
+    pub name: String,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<model::FoodEntry> for FoodEntry {
+    fn from(value: model::FoodEntry) -> Self {
+        Self {
+            id: value.id,
+            eaten_on: value.eaten_on,
+            meal: value.meal.map(Meal::from),
+            name: value.name,
+            nutrients: value.nutrients.into(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// A food eaten today unless `eatenOn` is set. `nutrients` is the eaten
+/// amount.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFood {
+    pub eaten_on: Option<NaiveDate>,
+    pub meal: Option<Meal>,
+    pub name: String,
+    pub nutrients: Nutrients,
+}
+
+impl From<LogFood> for model::LogFoodCommand {
+    fn from(value: LogFood) -> Self {
+        model::LogFoodCommand {
+            owner_id: String::default(),
+            eaten_on: value.eaten_on.unwrap_or_else(|| Utc::now().date_naive()),
+            meal: value.meal.map(model::Meal::from),
+            name: value.name,
+            nutrients: value.nutrients.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiaryEntryKind {
+    #[default]
+    Recipe,
+    Food,
+}
+
+impl From<model::DiaryEntryKind> for DiaryEntryKind {
+    fn from(value: model::DiaryEntryKind) -> Self {
+        match value {
+            model::DiaryEntryKind::Recipe => DiaryEntryKind::Recipe,
+            model::DiaryEntryKind::Food => DiaryEntryKind::Food,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryEntry {
+    pub id: String,
+    pub kind: DiaryEntryKind,
+    pub recipe_id: Option<String>,
+    pub name: String,
+    pub date: NaiveDate,
+    pub meal: Option<Meal>,
+    pub servings: Option<f64>,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<model::DiaryEntry> for DiaryEntry {
+    fn from(value: model::DiaryEntry) -> Self {
+        Self {
+            id: value.id,
+            kind: value.kind.into(),
+            recipe_id: value.recipe_id,
+            name: value.name,
+            date: value.date,
+            meal: value.meal.map(Meal::from),
+            servings: value.servings,
+            nutrients: value.nutrients.into(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// Defaults to today.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryDayQuery {
+    pub date: Option<NaiveDate>,
+}
+
+impl From<DiaryDayQuery> for model::DiaryDayQuery {
+    fn from(value: DiaryDayQuery) -> Self {
+        model::DiaryDayQuery {
+            owner_id: String::default(),
+            date: value.date.unwrap_or_else(|| Utc::now().date_naive()),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryDay {
+    pub date: NaiveDate,
+    pub entries: Vec<DiaryEntry>,
+    pub nutrients: Nutrients,
+    pub goals: Option<NutritionTargets>,
+    pub remaining: Option<NutritionTargets>,
+}
+
+impl From<model::DiaryDay> for DiaryDay {
+    fn from(value: model::DiaryDay) -> Self {
+        Self {
+            date: value.date,
+            entries: value.entries.into_iter().map(DiaryEntry::from).collect(),
+            nutrients: value.nutrients.into(),
+            goals: value.goals.map(NutritionTargets::from),
+            remaining: value.remaining.map(NutritionTargets::from),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportPeriod {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl From<ReportPeriod> for model::ReportPeriod {
+    fn from(value: ReportPeriod) -> Self {
+        match value {
+            ReportPeriod::Day => model::ReportPeriod::Day,
+            ReportPeriod::Week => model::ReportPeriod::Week,
+            ReportPeriod::Month => model::ReportPeriod::Month,
+        }
+    }
+}
+
+impl From<model::ReportPeriod> for ReportPeriod {
+    fn from(value: model::ReportPeriod) -> Self {
+        match value {
+            model::ReportPeriod::Day => ReportPeriod::Day,
+            model::ReportPeriod::Week => ReportPeriod::Week,
+            model::ReportPeriod::Month => ReportPeriod::Month,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportQuery {
+    #[serde(default)]
+    pub period: ReportPeriod,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl From<ReportQuery> for model::ReportQuery {
+    fn from(value: ReportQuery) -> Self {
+        model::ReportQuery {
+            owner_id: String::default(),
+            period: value.period.into(),
+            from: value.from,
+            to: value.to,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Streak {
+    pub current: i64,
+    pub longest: i64,
+}
+
+impl From<model::Streak> for Streak {
+    fn from(value: model::Streak) -> Self {
+        Self {
+            current: value.current,
+            longest: value.longest,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportPeriodSummary {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub days_logged: i64,
+    pub days_on_target: i64,
+    pub total: Nutrients,
+    pub average: Nutrients,
+    pub deviation: Option<NutritionTargets>,
+}
+
+impl From<model::ReportPeriodSummary> for ReportPeriodSummary {
+    fn from(value: model::ReportPeriodSummary) -> Self {
+        Self {
+            start_date: value.start_date,
+            end_date: value.end_date,
+            days_logged: value.days_logged,
+            days_on_target: value.days_on_target,
+            total: value.total.into(),
+            average: value.average.into(),
+            deviation: value.deviation.map(NutritionTargets::from),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub period: ReportPeriod,
+    pub goals: Option<NutritionTargets>,
+    pub periods: Vec<ReportPeriodSummary>,
+    pub logging_streak: Streak,
+    pub goal_streak: Streak,
+}
+
+impl From<model::Report> for Report {
+    fn from(value: model::Report) -> Self {
+        Self {
+            period: value.period.into(),
+            goals: value.goals.map(NutritionTargets::from),
+            periods: value
+                .periods
+                .into_iter()
+                .map(ReportPeriodSummary::from)
+                .collect(),
+            logging_streak: value.logging_streak.into(),
+            goal_streak: value.goal_streak.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeRevision {
+    pub id: String,
+    pub recipe_id: String,
+    pub number: i32,
+    pub author_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub snapshot: RecipeSnapshot,
+}
+
+impl From<model::RecipeRevision> for RecipeRevision {
+    fn from(value: model::RecipeRevision) -> Self {
+        Self {
+            id: value.id,
+            recipe_id: value.recipe_id,
+            number: value.number,
+            author_id: value.author_id,
+            created_at: value.created_at,
+            snapshot: value.snapshot.into(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSnapshot {
+    pub cover: String,
+    pub title: String,
+    pub description: String,
+    pub time_to_cook: i64,
+    pub difficulty: String,
+    pub servings: i64,
+    pub category_id: String,
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
+    pub diets: Vec<Diet>,
+    pub nutrients: Nutrients,
+    pub nutrients_computed: bool,
+    pub guideline: String,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    pub status: Option<RecipeStatus>,
+}
+
+impl From<model::RecipeSnapshot> for RecipeSnapshot {
+    fn from(value: model::RecipeSnapshot) -> Self {
+        Self {
+            cover: String::from_utf8_lossy(&value.cover).into_owned(),
+            title: value.title,
+            description: value.description,
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty,
+            servings: value.servings,
+            category_id: value.category_id,
+            ingredients: value.ingredients,
+            allergens: value.allergens,
+            diets: value.diets.into_iter().map(|item| item.into()).collect(),
+            nutrients: value.nutrients.into(),
+            nutrients_computed: value.nutrients_computed,
+            guideline: value.guideline,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            status: value.status.map(RecipeStatus::from),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionSearchQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<RevisionSearchQuery> for model::Pagination {
+    fn from(value: RevisionSearchQuery) -> Self {
+        model::Pagination {
+            limit: value.limit,
+            offset: value.offset,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionDiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionDiff {
+    pub recipe_id: String,
+    pub from: i32,
+    pub to: i32,
+    pub fields: Vec<FieldChange>,
+    pub lists: Vec<ListChange>,
+}
+
+impl From<model::RevisionDiff> for RevisionDiff {
+    fn from(value: model::RevisionDiff) -> Self {
+        Self {
+            recipe_id: value.recipe_id,
+            from: value.from,
+            to: value.to,
+            fields: value.fields.into_iter().map(FieldChange::from).collect(),
+            lists: value.lists.into_iter().map(ListChange::from).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl From<model::FieldChange> for FieldChange {
+    fn from(value: model::FieldChange) -> Self {
+        Self {
+            field: value.field,
+            before: value.before,
+            after: value.after,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListChange {
+    pub field: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub reordered: bool,
+}
+
+impl From<model::ListChange> for ListChange {
+    fn from(value: model::ListChange) -> Self {
+        Self {
+            field: value.field,
+            added: value.added,
+            removed: value.removed,
+            reordered: value.reordered,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CookRecipeCommand, CreatePantryItemCommand, DeletePantryItemCommand, PantryItemQuery,
+        PantrySearchQuery, UpdatePantryItemCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/:id",
+            get(fetch_pantry_item_handler)
+                .put(update_pantry_item_handler)
+                .delete(delete_pantry_item_handler),
+        )
+        .route("/cook", post(cook_recipe_handler))
+        .route(
+            "/",
+            get(search_pantry_handler).post(create_pantry_item_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_pantry_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::PantrySearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::PantryItem>>, AppError> {
+    let mut q: PantrySearchQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state.pantry_service.search(q).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_pantry_item_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreatePantryItem>,
+) -> Result<Json<api_model::PantryItem>, AppError> {
+    let mut cmd: CreatePantryItemCommand = item.try_into().map_err(AppError)?;
+    cmd.owner_id = user.id;
+
+    let res = state.pantry_service.create(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_pantry_item_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::PantryItem>, AppError> {
+    let res = state
+        .pantry_service
+        .fetch(PantryItemQuery {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_pantry_item_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::UpdatePantryItem>,
+) -> Result<Json<api_model::PantryItem>, AppError> {
+    let mut cmd: UpdatePantryItemCommand = item.try_into().map_err(AppError)?;
+    cmd.id = id;
+    cmd.owner_id = user.id;
+
+    let res = state.pantry_service.update(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_pantry_item_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::PantryItem>, AppError> {
+    let res = state
+        .pantry_service
+        .delete(DeletePantryItemCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+/// Takes the ingredients of a cooked recipe out of the pantry and returns
+/// what is left.
+async fn cook_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CookRecipe>,
+) -> Result<Json<Vec<api_model::PantryItem>>, AppError> {
+    let mut cmd: CookRecipeCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state.pantry_service.cook(cmd).await.map_err(AppError)?;
+
+    Ok(Json(
+        res.into_iter().map(api_model::PantryItem::from).collect(),
+    ))
+}
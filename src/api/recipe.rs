@@ -1,16 +1,30 @@
 use axum::{
     extract::{Path, State},
-    routing::get,
+    http::header,
+    response::{IntoResponse, Response},
+    routing::{get, post},
     Form, Json, Router,
 };
 
 use crate::{
     api::model::AppError,
-    model::{DeleteRecipeCommand, RecipeQuery, UpdateRecipeCommand},
+    model::{
+        CreateRecipeCommand, DeleteRecipeCommand, NutritionLabelQuery, PurgeDeletedCommand,
+        RecipeQuery, RecipeSearchQuery, RecipeSubstitutionsQuery, RecipeTransition,
+        RestoreRecipeCommand, ServiceError, SimilarRecipesQuery, TransitionRecipeCommand,
+        UpdateRecipeCommand,
+    },
+    service::{
+        label::{render_html, render_svg},
+        policy,
+    },
     state::AppState,
 };
 
-use super::model::{self as api_model};
+use super::{
+    identity::{CurrentUser, OptionalUser},
+    model::{self as api_model},
+};
 
 pub fn build(state: AppState) -> Router {
     Router::new()
@@ -20,75 +34,289 @@ pub fn build(state: AppState) -> Router {
                 .put(update_recipe_handler)
                 .delete(delete_recipe_handler),
         )
+        .route("/:id/submit", post(submit_recipe_handler))
+        .route("/:id/withdraw", post(withdraw_recipe_handler))
+        .route("/:id/reject", post(reject_recipe_handler))
+        .route("/:id/publish", post(publish_recipe_handler))
+        .route("/:id/archive", post(archive_recipe_handler))
+        .route("/:id/restore", post(restore_recipe_handler))
+        .route("/:id/nutrition-label", get(nutrition_label_handler))
+        .route("/:id/similar", get(similar_recipes_handler))
+        .route("/:id/substitutions", get(recipe_substitutions_handler))
+        .route("/nutrients/calculate", post(calculate_nutrients_handler))
+        .route("/purge", post(purge_deleted_handler))
         .route("/", get(search_recipes_handler).post(create_recipe_handler))
         .with_state(state)
 }
 
 async fn search_recipes_handler(
     State(state): State<AppState>,
+    OptionalUser(user): OptionalUser,
     Form(item): Form<api_model::RecipeSearchQuery>,
 ) -> Result<Json<api_model::SearchResult<api_model::Recipe>>, AppError> {
-    let res = state
-        .recipe_service
-        .search(item.into())
-        .await
-        .map_err(|e| AppError(e))?;
+    let days = item.expiring_within_days;
+    let mine = item.mine;
+    let mut q: RecipeSearchQuery = item.into();
+    q.viewer_id = user.as_ref().map(|user| user.id.clone());
+    q.visibility = policy::recipe_visibility(user.as_ref().map(|user| user.actor()).as_ref());
+
+    if mine {
+        let user = user.as_ref().ok_or_else(|| {
+            AppError(ServiceError::Unauthorized("mine needs a user".into()).into())
+        })?;
+        q.author_id = Some(user.id.clone());
+    }
+
+    let res = match days {
+        Some(days) => {
+            let user = user.ok_or_else(|| {
+                AppError(
+                    ServiceError::Unauthorized("expiringWithinDays needs a user".into()).into(),
+                )
+            })?;
+
+            state
+                .pantry_service
+                .expiring_recipes(&user.id, days, q)
+                .await
+        }
+        None => state.recipe_service.search(q).await,
+    }
+    .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn create_recipe_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(item): Json<api_model::CreateRecipe>,
 ) -> Result<Json<api_model::Recipe>, AppError> {
-    let res = state
-        .recipe_service
-        .create(item.into())
-        .await
-        .map_err(|e| AppError(e))?;
+    let mut cmd: CreateRecipeCommand = item.into();
+    cmd.actor = user.actor();
+
+    let res = state.recipe_service.create(cmd).await.map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn fetch_recipe_handler(
     State(state): State<AppState>,
+    OptionalUser(user): OptionalUser,
     Path(id): Path<String>,
+    Form(query): Form<api_model::RecipeFetchQuery>,
 ) -> Result<Json<api_model::Recipe>, AppError> {
     let res = state
         .recipe_service
-        .fetch(RecipeQuery { id })
+        .fetch(RecipeQuery {
+            id,
+            basis: query.basis.into(),
+            visibility: policy::recipe_visibility(user.as_ref().map(|user| user.actor()).as_ref()),
+            viewer_id: user.map(|user| user.id),
+            ..RecipeQuery::default()
+        })
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn update_recipe_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<String>,
     Json(item): Json<api_model::UpdateRecipe>,
 ) -> Result<Json<api_model::Recipe>, AppError> {
     let mut cmd: UpdateRecipeCommand = item.into();
     cmd.id = id;
+    cmd.actor = user.actor();
+
+    let res = state.recipe_service.update(cmd).await.map_err(AppError)?;
 
+    Ok(Json(res.into()))
+}
+
+async fn submit_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    transition(state, user, id, RecipeTransition::Submit).await
+}
+
+async fn withdraw_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    transition(state, user, id, RecipeTransition::Withdraw).await
+}
+
+async fn reject_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    transition(state, user, id, RecipeTransition::Reject).await
+}
+
+async fn publish_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    transition(state, user, id, RecipeTransition::Publish).await
+}
+
+async fn archive_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    transition(state, user, id, RecipeTransition::Archive).await
+}
+
+async fn transition(
+    state: AppState,
+    user: CurrentUser,
+    id: String,
+    transition: RecipeTransition,
+) -> Result<Json<api_model::Recipe>, AppError> {
     let res = state
         .recipe_service
-        .update(cmd)
+        .transition(TransitionRecipeCommand {
+            id,
+            actor: user.actor(),
+            transition,
+        })
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
 
 async fn delete_recipe_handler(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<String>,
 ) -> Result<Json<api_model::Recipe>, AppError> {
     let res = state
         .recipe_service
-        .delete(DeleteRecipeCommand { id })
+        .delete(DeleteRecipeCommand {
+            id,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn restore_recipe_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    let res = state
+        .recipe_service
+        .restore(RestoreRecipeCommand {
+            id,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+/// Hard-deletes recipes and categories past their retention right away
+/// instead of waiting for the purge job.
+async fn purge_deleted_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<api_model::PurgeResult>, AppError> {
+    let res = state
+        .recipe_service
+        .purge(PurgeDeletedCommand {
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn calculate_nutrients_handler(
+    State(state): State<AppState>,
+    Json(item): Json<api_model::CalculateNutrients>,
+) -> Result<Json<api_model::NutrientsCalculation>, AppError> {
+    let res = state
+        .recipe_service
+        .calculate_nutrients(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn nutrition_label_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::NutritionLabelQuery>,
+) -> Result<Response, AppError> {
+    let output = item.output;
+    let mut q: NutritionLabelQuery = item.into();
+    q.recipe_id = id;
+
+    let res = state.label_service.label(q).await.map_err(AppError)?;
+
+    Ok(match output {
+        api_model::LabelOutput::Json => Json(api_model::NutritionLabel::from(res)).into_response(),
+        api_model::LabelOutput::Svg => {
+            ([(header::CONTENT_TYPE, "image/svg+xml")], render_svg(&res)).into_response()
+        }
+        api_model::LabelOutput::Html => (
+            [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+            render_html(&res),
+        )
+            .into_response(),
+    })
+}
+
+async fn similar_recipes_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::SimilarRecipesQuery>,
+) -> Result<Json<Vec<api_model::SimilarRecipe>>, AppError> {
+    let res = state
+        .recommendation_service
+        .similar(SimilarRecipesQuery {
+            id,
+            limit: item.limit,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(
+        res.into_iter()
+            .map(api_model::SimilarRecipe::from)
+            .collect(),
+    ))
+}
+
+async fn recipe_substitutions_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::RecipeSubstitutionsQuery>,
+) -> Result<Json<api_model::RecipeSubstitutions>, AppError> {
+    let res = state
+        .substitution_service
+        .for_recipe(RecipeSubstitutionsQuery {
+            recipe_id: id,
+            avoid: api_model::split_list(&item.avoid),
+        })
         .await
-        .map_err(|e| AppError(e))?;
+        .map_err(AppError)?;
 
     Ok(Json(res.into()))
 }
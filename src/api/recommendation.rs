@@ -0,0 +1,68 @@
+use axum::{extract::State, routing::get, Form, Json, Router};
+
+use crate::{
+    model::{RecommendationQuery, UserPreferences},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/recommendations", get(recommendations_handler))
+        .route(
+            "/me/preferences",
+            get(fetch_preferences_handler).put(save_preferences_handler),
+        )
+        .with_state(state)
+}
+
+async fn recommendations_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::RecommendationQuery>,
+) -> Result<Json<Vec<api_model::Recommendation>>, AppError> {
+    let mut q: RecommendationQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state
+        .recommendation_service
+        .recommend(q)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(
+        res.into_iter()
+            .map(api_model::Recommendation::from)
+            .collect(),
+    ))
+}
+
+async fn fetch_preferences_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<api_model::UserPreferences>, AppError> {
+    let res = state
+        .preference_service
+        .fetch(&user.id)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn save_preferences_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::UserPreferences>,
+) -> Result<Json<api_model::UserPreferences>, AppError> {
+    let mut cmd: UserPreferences = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state.preference_service.save(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,161 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post, put},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CreateReviewCommand, DeleteReviewCommand, FlagReviewCommand, FlaggedReviewsQuery,
+        ModerateReviewCommand, ReviewSearchQuery, UpdateReviewCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+/// Routes relative to `/recipes`.
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/reviews/flagged", get(flagged_reviews_handler))
+        .route(
+            "/:id/reviews/:review_id",
+            put(update_review_handler).delete(delete_review_handler),
+        )
+        .route("/:id/reviews/:review_id/flag", post(flag_review_handler))
+        .route(
+            "/:id/reviews/:review_id/moderation",
+            put(moderate_review_handler),
+        )
+        .route(
+            "/:id/reviews",
+            get(search_reviews_handler).post(create_review_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_reviews_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::ReviewSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Review>>, AppError> {
+    let res = state
+        .review_service
+        .search(ReviewSearchQuery {
+            recipe_id: Some(id),
+            flagged: false,
+            pagination: item.into(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn flagged_reviews_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::ReviewSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Review>>, AppError> {
+    let res = state
+        .review_service
+        .flagged(FlaggedReviewsQuery {
+            actor: user.actor(),
+            pagination: item.into(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_review_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::CreateReview>,
+) -> Result<Json<api_model::Review>, AppError> {
+    let mut cmd: CreateReviewCommand = item.into();
+    cmd.recipe_id = id;
+    cmd.owner_id = user.id;
+
+    let res = state.review_service.create(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_review_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, review_id)): Path<(String, String)>,
+    Json(item): Json<api_model::UpdateReview>,
+) -> Result<Json<api_model::Review>, AppError> {
+    let mut cmd: UpdateReviewCommand = item.into();
+    cmd.id = review_id;
+    cmd.recipe_id = id;
+    cmd.owner_id = user.id;
+
+    let res = state.review_service.update(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_review_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, review_id)): Path<(String, String)>,
+) -> Result<Json<api_model::Review>, AppError> {
+    let res = state
+        .review_service
+        .delete(DeleteReviewCommand {
+            id: review_id,
+            recipe_id: id,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn flag_review_handler(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path((id, review_id)): Path<(String, String)>,
+    Json(item): Json<api_model::FlagReview>,
+) -> Result<Json<api_model::Review>, AppError> {
+    let res = state
+        .review_service
+        .flag(FlagReviewCommand {
+            id: review_id,
+            recipe_id: id,
+            reason: item.reason,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn moderate_review_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, review_id)): Path<(String, String)>,
+    Json(item): Json<api_model::ModerateReview>,
+) -> Result<Json<api_model::Review>, AppError> {
+    let res = state
+        .review_service
+        .moderate(ModerateReviewCommand {
+            id: review_id,
+            recipe_id: id,
+            actor: user.actor(),
+            hidden: item.hidden,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
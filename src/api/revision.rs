@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{RevisionDiffQuery, RevisionQuery, RevisionSearchQuery, RollbackRecipeCommand},
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+/// Routes relative to `/recipes`.
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/:id/revisions", get(search_revisions_handler))
+        .route("/:id/revisions/diff", get(diff_revisions_handler))
+        .route("/:id/revisions/:number", get(fetch_revision_handler))
+        .route(
+            "/:id/revisions/:number/rollback",
+            post(rollback_revision_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_revisions_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::RevisionSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::RecipeRevision>>, AppError> {
+    let res = state
+        .revision_service
+        .search(RevisionSearchQuery {
+            recipe_id: id,
+            actor: user.actor(),
+            pagination: item.into(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_revision_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, number)): Path<(String, i32)>,
+) -> Result<Json<api_model::RecipeRevision>, AppError> {
+    let res = state
+        .revision_service
+        .fetch(RevisionQuery {
+            recipe_id: id,
+            number,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn diff_revisions_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Form(item): Form<api_model::RevisionDiffQuery>,
+) -> Result<Json<api_model::RevisionDiff>, AppError> {
+    let res = state
+        .revision_service
+        .diff(RevisionDiffQuery {
+            recipe_id: id,
+            from: item.from,
+            to: item.to,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn rollback_revision_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, number)): Path<(String, i32)>,
+) -> Result<Json<api_model::Recipe>, AppError> {
+    let res = state
+        .revision_service
+        .rollback(RollbackRecipeCommand {
+            recipe_id: id,
+            number,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
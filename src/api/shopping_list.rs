@@ -0,0 +1,120 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, put},
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CheckShoppingListItemCommand, CreateShoppingListCommand, DeleteShoppingListCommand,
+        ShoppingListQuery, ShoppingListSearchQuery,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/:id",
+            get(fetch_shopping_list_handler).delete(delete_shopping_list_handler),
+        )
+        .route("/:id/items/:item_id", put(check_shopping_list_item_handler))
+        .route(
+            "/",
+            get(search_shopping_lists_handler).post(create_shopping_list_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_shopping_lists_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Form(item): Form<api_model::ShoppingListSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::ShoppingList>>, AppError> {
+    let mut q: ShoppingListSearchQuery = item.into();
+    q.owner_id = user.id;
+
+    let res = state
+        .shopping_list_service
+        .search(q)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_shopping_list_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateShoppingList>,
+) -> Result<Json<api_model::ShoppingList>, AppError> {
+    let mut cmd: CreateShoppingListCommand = item.into();
+    cmd.owner_id = user.id;
+
+    let res = state
+        .shopping_list_service
+        .create(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_shopping_list_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::ShoppingList>, AppError> {
+    let res = state
+        .shopping_list_service
+        .fetch(ShoppingListQuery {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn check_shopping_list_item_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, item_id)): Path<(String, String)>,
+    Json(item): Json<api_model::CheckShoppingListItem>,
+) -> Result<Json<api_model::ShoppingList>, AppError> {
+    let res = state
+        .shopping_list_service
+        .check_item(CheckShoppingListItemCommand {
+            shopping_list_id: id,
+            owner_id: user.id,
+            item_id,
+            checked: item.checked,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_shopping_list_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::ShoppingList>, AppError> {
+    let res = state
+        .shopping_list_service
+        .delete(DeleteShoppingListCommand {
+            id,
+            owner_id: user.id,
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Form, Json, Router,
+};
+
+use crate::{
+    model::{
+        CreateSubstitutionCommand, DeleteSubstitutionCommand, SubstitutionQuery,
+        UpdateSubstitutionCommand,
+    },
+    state::AppState,
+};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/:id",
+            get(fetch_substitution_handler)
+                .put(update_substitution_handler)
+                .delete(delete_substitution_handler),
+        )
+        .route(
+            "/",
+            get(search_substitutions_handler).post(create_substitution_handler),
+        )
+        .with_state(state)
+}
+
+async fn search_substitutions_handler(
+    State(state): State<AppState>,
+    Form(item): Form<api_model::SubstitutionSearchQuery>,
+) -> Result<Json<api_model::SearchResult<api_model::Substitution>>, AppError> {
+    let res = state
+        .substitution_service
+        .search(item.into())
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn create_substitution_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(item): Json<api_model::CreateSubstitution>,
+) -> Result<Json<api_model::Substitution>, AppError> {
+    let mut cmd: CreateSubstitutionCommand = item.try_into().map_err(AppError)?;
+    cmd.actor = user.actor();
+
+    let res = state
+        .substitution_service
+        .create(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn fetch_substitution_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Substitution>, AppError> {
+    let res = state
+        .substitution_service
+        .fetch(SubstitutionQuery { id })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn update_substitution_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::UpdateSubstitution>,
+) -> Result<Json<api_model::Substitution>, AppError> {
+    let mut cmd: UpdateSubstitutionCommand = item.try_into().map_err(AppError)?;
+    cmd.id = id;
+    cmd.actor = user.actor();
+
+    let res = state
+        .substitution_service
+        .update(cmd)
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
+
+async fn delete_substitution_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<api_model::Substitution>, AppError> {
+    let res = state
+        .substitution_service
+        .delete(DeleteSubstitutionCommand {
+            id,
+            actor: user.actor(),
+        })
+        .await
+        .map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
@@ -0,0 +1,33 @@
+use axum::{
+    extract::{Path, State},
+    routing::put,
+    Json, Router,
+};
+
+use crate::{model::SetRoleCommand, state::AppState};
+
+use super::{
+    identity::CurrentUser,
+    model::{self as api_model, AppError},
+};
+
+pub fn build(state: AppState) -> Router {
+    Router::new()
+        .route("/:id/role", put(set_role_handler))
+        .with_state(state)
+}
+
+async fn set_role_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(item): Json<api_model::SetRole>,
+) -> Result<Json<api_model::User>, AppError> {
+    let mut cmd: SetRoleCommand = item.try_into().map_err(AppError)?;
+    cmd.actor = user.actor();
+    cmd.user_id = id;
+
+    let res = state.auth_service.set_role(cmd).await.map_err(AppError)?;
+
+    Ok(Json(res.into()))
+}
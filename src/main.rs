@@ -4,12 +4,16 @@ mod repository;
 mod service;
 mod state;
 
+use chrono::Duration;
 use dotenvy::dotenv;
 use std::{env, sync::Arc};
 
 use crate::api::new_api;
 use state::AppState;
-use tokio::net::TcpListener;
+use tokio::{net::TcpListener, time};
+
+/// How often recipes and categories past their retention are purged.
+const PURGE_INTERVAL: time::Duration = time::Duration::from_secs(60 * 60);
 
 #[tokio::main]
 async fn main() {
@@ -17,24 +21,206 @@ async fn main() {
 
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let api_host = env::var("APIHOST").expect("APIHOST must be set");
+    let keto_max_net_carbs = env::var("KETO_MAX_NET_CARBS")
+        .map(|v| v.parse().expect("KETO_MAX_NET_CARBS must be a number"))
+        .unwrap_or(10.0);
+    let reference_kcal = env::var("REFERENCE_INTAKE_KCAL")
+        .map(|v| v.parse().expect("REFERENCE_INTAKE_KCAL must be a number"))
+        .unwrap_or(2000.0);
+
+    let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    let access_token_ttl = env::var("ACCESS_TOKEN_TTL_MINUTES")
+        .map(|v| {
+            v.parse()
+                .expect("ACCESS_TOKEN_TTL_MINUTES must be a number")
+        })
+        .unwrap_or(15);
+    let refresh_token_ttl = env::var("REFRESH_TOKEN_TTL_DAYS")
+        .map(|v| v.parse().expect("REFRESH_TOKEN_TTL_DAYS must be a number"))
+        .unwrap_or(30);
+    let deleted_retention = env::var("DELETED_RETENTION_DAYS")
+        .map(|v| v.parse().expect("DELETED_RETENTION_DAYS must be a number"))
+        .unwrap_or(30);
 
     let db_conn = Arc::new(repository::connect(database_url).await);
 
     let category_storage = Arc::new(repository::CategoryRepository::new(db_conn.clone()).await);
     let recipe_storage = Arc::new(repository::RecipeRepository::new(db_conn.clone()).await);
+    let allergen_storage = Arc::new(repository::AllergenRepository::new(db_conn.clone()).await);
+    let ingredient_storage = Arc::new(repository::IngredientRepository::new(db_conn.clone()).await);
+    let meal_plan_storage = Arc::new(repository::MealPlanRepository::new(db_conn.clone()).await);
+    let shopping_list_storage =
+        Arc::new(repository::ShoppingListRepository::new(db_conn.clone()).await);
+    let pantry_storage = Arc::new(repository::PantryRepository::new(db_conn.clone()).await);
+    let substitution_storage =
+        Arc::new(repository::SubstitutionRepository::new(db_conn.clone()).await);
+    let preference_storage = Arc::new(repository::PreferenceRepository::new(db_conn.clone()).await);
+    let favorite_storage = Arc::new(repository::FavoriteRepository::new(db_conn.clone()).await);
+    let collection_storage = Arc::new(repository::CollectionRepository::new(db_conn.clone()).await);
+    let review_storage = Arc::new(repository::ReviewRepository::new(db_conn.clone()).await);
+    let cooking_log_storage =
+        Arc::new(repository::CookingLogRepository::new(db_conn.clone()).await);
+    let food_entry_storage = Arc::new(repository::FoodEntryRepository::new(db_conn.clone()).await);
+    let nutrition_goal_storage =
+        Arc::new(repository::NutritionGoalRepository::new(db_conn.clone()).await);
+    let revision_storage = Arc::new(repository::RevisionRepository::new(db_conn.clone()).await);
+
+    let user_storage = Arc::new(repository::UserRepository::new(db_conn.clone()).await);
+    let session_storage = Arc::new(repository::SessionRepository::new(db_conn.clone()).await);
+    let api_key_storage = Arc::new(repository::ApiKeyRepository::new(db_conn.clone()).await);
+
+    let auth_service = Arc::new(service::AuthService::new(service::auth::Config {
+        user_storage,
+        session_storage,
+        jwt_secret,
+        access_token_ttl: Duration::minutes(access_token_ttl),
+        refresh_token_ttl: Duration::days(refresh_token_ttl),
+    }));
+
+    let api_key_service = Arc::new(service::ApiKeyService::new(service::api_key::Config {
+        api_key_storage,
+    }));
 
     let category_service = Arc::new(service::CategoryService::new(service::category::Config {
         category_storage,
     }));
 
+    let allergen_service = Arc::new(service::AllergenService::new(service::allergen::Config {
+        allergen_storage,
+    }));
+
+    let ingredient_service = Arc::new(service::IngredientService::new(
+        service::ingredient::Config { ingredient_storage },
+    ));
+
+    let diet_service = Arc::new(service::DietService::new(service::diet::Config {
+        ingredient_service: ingredient_service.clone(),
+        keto_max_net_carbs,
+    }));
+
+    let nutrition_service = Arc::new(service::NutritionService::new(service::nutrition::Config {
+        ingredient_service: ingredient_service.clone(),
+    }));
+
     let recipe_service = Arc::new(service::RecipeService::new(service::recipe::Config {
         category_service: category_service.clone(),
+        allergen_service: allergen_service.clone(),
+        diet_service,
+        nutrition_service: nutrition_service.clone(),
+        ingredient_service: ingredient_service.clone(),
         recipe_storage,
+        favorite_storage: favorite_storage.clone(),
+        deleted_retention: Duration::days(deleted_retention),
+    }));
+
+    let label_service = Arc::new(service::LabelService::new(service::label::Config {
+        recipe_service: recipe_service.clone(),
+        reference_kcal,
+    }));
+
+    let meal_plan_service = Arc::new(service::MealPlanService::new(service::meal_plan::Config {
+        recipe_service: recipe_service.clone(),
+        meal_plan_storage,
+    }));
+
+    let pantry_service = Arc::new(service::PantryService::new(service::pantry::Config {
+        recipe_service: recipe_service.clone(),
+        ingredient_service: ingredient_service.clone(),
+        pantry_storage,
     }));
 
+    let shopping_list_service = Arc::new(service::ShoppingListService::new(
+        service::shopping_list::Config {
+            recipe_service: recipe_service.clone(),
+            meal_plan_service: meal_plan_service.clone(),
+            ingredient_service: ingredient_service.clone(),
+            pantry_service: pantry_service.clone(),
+            shopping_list_storage,
+        },
+    ));
+
+    let preference_service = Arc::new(service::PreferenceService::new(
+        service::preference::Config { preference_storage },
+    ));
+
+    let substitution_service = Arc::new(service::SubstitutionService::new(
+        service::substitution::Config {
+            recipe_service: recipe_service.clone(),
+            ingredient_service: ingredient_service.clone(),
+            nutrition_service,
+            substitution_storage,
+        },
+    ));
+
+    let favorite_service = Arc::new(service::FavoriteService::new(service::favorite::Config {
+        recipe_service: recipe_service.clone(),
+        favorite_storage,
+    }));
+
+    let collection_service = Arc::new(service::CollectionService::new(
+        service::collection::Config {
+            recipe_service: recipe_service.clone(),
+            collection_storage,
+        },
+    ));
+
+    let review_service = Arc::new(service::ReviewService::new(service::review::Config {
+        recipe_service: recipe_service.clone(),
+        review_storage,
+    }));
+
+    let cooking_log_service = Arc::new(service::CookingLogService::new(
+        service::cooking_log::Config {
+            recipe_service: recipe_service.clone(),
+            cooking_log_storage,
+        },
+    ));
+
+    let recommendation_service = Arc::new(service::RecommendationService::new(
+        service::recommendation::Config {
+            recipe_service: recipe_service.clone(),
+            meal_plan_service: meal_plan_service.clone(),
+            ingredient_service: ingredient_service.clone(),
+            preference_service: preference_service.clone(),
+            favorite_service: favorite_service.clone(),
+            review_service: review_service.clone(),
+            cooking_log_service: cooking_log_service.clone(),
+        },
+    ));
+
+    let diary_service = Arc::new(service::DiaryService::new(service::diary::Config {
+        cooking_log_service: cooking_log_service.clone(),
+        food_entry_storage,
+        nutrition_goal_storage,
+    }));
+
+    let revision_service = Arc::new(service::RevisionService::new(service::revision::Config {
+        recipe_service: recipe_service.clone(),
+        revision_storage,
+    }));
+
+    tokio::spawn(purge_deleted(recipe_service.clone()));
+
     let app_state = AppState {
+        auth_service,
+        api_key_service,
         recipe_service,
         category_service,
+        allergen_service,
+        ingredient_service,
+        label_service,
+        meal_plan_service,
+        shopping_list_service,
+        pantry_service,
+        preference_service,
+        recommendation_service,
+        substitution_service,
+        favorite_service,
+        collection_service,
+        review_service,
+        cooking_log_service,
+        diary_service,
+        revision_service,
     };
 
     let myapi = new_api(app_state);
@@ -45,3 +231,15 @@ async fn main() {
 
     axum::serve(listener, myapi).await.unwrap();
 }
+
+async fn purge_deleted(recipe_service: Arc<service::RecipeService>) {
+    let mut interval = time::interval(PURGE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = recipe_service.purge_expired().await {
+            eprintln!("purge failed: {}", err);
+        }
+    }
+}
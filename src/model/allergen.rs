@@ -0,0 +1,26 @@
+use super::user::Actor;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Allergen {
+    pub code: String,
+    pub name: String,
+    pub custom: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateAllergenCommand {
+    pub code: String,
+    pub name: String,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteAllergenCommand {
+    pub code: String,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AllergenSearchQuery {
+    pub codes: Option<Vec<String>>,
+}
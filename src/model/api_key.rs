@@ -0,0 +1,98 @@
+use chrono::NaiveDateTime;
+
+use super::user::{Actor, Role};
+
+/// What an API key may be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    ReadRecipes,
+    WriteRecipes,
+    ManageCategories,
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 3] = [
+        ApiKeyScope::ReadRecipes,
+        ApiKeyScope::WriteRecipes,
+        ApiKeyScope::ManageCategories,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadRecipes => "recipes:read",
+            ApiKeyScope::WriteRecipes => "recipes:write",
+            ApiKeyScope::ManageCategories => "categories:manage",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ApiKeyScope> {
+        ApiKeyScope::ALL
+            .into_iter()
+            .find(|scope| scope.code() == code)
+    }
+
+    /// The role the owner needs to grant the scope.
+    pub fn required_role(&self) -> Role {
+        match self {
+            ApiKeyScope::ReadRecipes => Role::Viewer,
+            ApiKeyScope::WriteRecipes => Role::Author,
+            ApiKeyScope::ManageCategories => Role::Editor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    /// First characters of the key, enough to tell keys apart.
+    pub prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    /// Requests per minute.
+    pub rate_limit: i32,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A key together with its secret, which is shown only once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuedApiKey {
+    pub key: ApiKey,
+    pub secret: String,
+}
+
+/// The owner and grants of an authenticated API key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKeyIdentity {
+    pub key_id: String,
+    pub user_id: String,
+    pub role: Role,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ApiKeysQuery {
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateApiKeyCommand {
+    pub actor: Actor,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub rate_limit: Option<i32>,
+}
+
+/// Replaces the secret of a key, the old one stops working.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RotateApiKeyCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevokeApiKeyCommand {
+    pub id: String,
+    pub owner_id: String,
+}
@@ -1,31 +1,66 @@
+use super::{user::Actor, Pagination, SortDirection};
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Category {
     pub id: String,
+    pub slug: String,
     pub name: String,
+    pub description: String,
+    pub image: String,
+    pub sort_order: i32,
+    pub recipe_count: i64,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CreateCategoryCommand {
+    pub actor: Actor,
+    pub slug: String,
     pub name: String,
+    pub description: String,
+    pub image: String,
+    pub sort_order: i32,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct UpdateCategoryCommand {
     pub id: String,
-    pub name: String,
+    pub actor: Actor,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub sort_order: Option<i32>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct DeleteCategoryCommand {
     pub id: String,
+    pub actor: Actor,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CategorySearchQuery {
     pub ids: Option<Vec<String>>,
+    pub name: Option<String>,
+    pub sort: CategorySort,
+    pub direction: SortDirection,
+    pub pagination: Pagination,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum CategorySort {
+    #[default]
+    Order,
+    Name,
+    RecipeCount,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CategoryQuery {
     pub id: String,
 }
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CategorySlugQuery {
+    pub slug: String,
+}
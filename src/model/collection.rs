@@ -0,0 +1,70 @@
+use chrono::NaiveDateTime;
+
+use super::{recipe::Recipe, Pagination};
+
+/// A named, ordered list of recipes of a user.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Collection {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub description: String,
+    /// Set while the collection is shared, anyone knowing it can view the
+    /// collection.
+    pub share_token: Option<String>,
+    pub items: Vec<CollectionItem>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CollectionItem {
+    pub recipe_id: String,
+    pub note: String,
+    /// Filled in on the way out, unset when the recipe no longer exists.
+    pub recipe: Option<Recipe>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateCollectionCommand {
+    pub owner_id: String,
+    pub name: String,
+    pub description: String,
+    pub shared: bool,
+    pub items: Vec<CollectionItem>,
+}
+
+/// Changes a collection. `items`, when set, replaces all items in the given
+/// order.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateCollectionCommand {
+    pub id: String,
+    pub owner_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Sharing again after unsharing issues a new token.
+    pub shared: Option<bool>,
+    pub items: Option<Vec<CollectionItem>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteCollectionCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CollectionQuery {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SharedCollectionQuery {
+    pub share_token: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CollectionSearchQuery {
+    pub owner_id: String,
+    pub pagination: Pagination,
+}
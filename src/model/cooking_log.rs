@@ -0,0 +1,53 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::{meal_plan::Meal, recipe::Nutrients, Pagination};
+
+/// A recipe a user cooked. `nutrients` is the eaten amount, the per serving
+/// values of the recipe times `servings` when the entry was logged.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CookingLogEntry {
+    pub id: String,
+    pub owner_id: String,
+    pub recipe_id: String,
+    /// Title of the recipe when it was cooked.
+    pub recipe_title: String,
+    pub cooked_on: NaiveDate,
+    /// Meal of the day the recipe was eaten at, if told.
+    pub meal: Option<Meal>,
+    /// May be fractional, e.g. half a serving.
+    pub servings: f64,
+    pub notes: String,
+    pub photo: Vec<u8>,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+/// Logs that the owner cooked a recipe. `nutrients` and `recipe_title` are
+/// filled in by the service.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LogCookingCommand {
+    pub owner_id: String,
+    pub recipe_id: String,
+    pub recipe_title: String,
+    pub cooked_on: NaiveDate,
+    pub meal: Option<Meal>,
+    pub servings: f64,
+    pub notes: String,
+    pub photo: Vec<u8>,
+    pub nutrients: Nutrients,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteCookingLogCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+/// Entries of an owner cooked within `from`..=`to`, latest first.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CookingLogSearchQuery {
+    pub owner_id: String,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub pagination: Pagination,
+}
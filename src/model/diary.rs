@@ -0,0 +1,148 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::{
+    meal_plan::{Meal, NutritionTargets, PeriodSummary},
+    recipe::Nutrients,
+};
+
+/// Food eaten that is not a recipe. `nutrients` is the eaten amount.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FoodEntry {
+    pub id: String,
+    pub owner_id: String,
+    pub eaten_on: NaiveDate,
+    pub meal: Option<Meal>,
+    pub name: String,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LogFoodCommand {
+    pub owner_id: String,
+    pub eaten_on: NaiveDate,
+    pub meal: Option<Meal>,
+    pub name: String,
+    pub nutrients: Nutrients,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteFoodEntryCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum DiaryEntryKind {
+    /// A cooking log entry, `id` is the one of the entry.
+    #[default]
+    Recipe,
+    Food,
+}
+
+/// A cooked recipe or an ad-hoc food as shown in the diary.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiaryEntry {
+    pub id: String,
+    pub kind: DiaryEntryKind,
+    pub recipe_id: Option<String>,
+    pub name: String,
+    pub date: NaiveDate,
+    pub meal: Option<Meal>,
+    /// Servings of the recipe, unset for foods.
+    pub servings: Option<f64>,
+    pub nutrients: Nutrients,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiaryDayQuery {
+    pub owner_id: String,
+    pub date: NaiveDate,
+}
+
+/// Everything eaten on a day. `remaining` is the goal minus the intake,
+/// negative where the day exceeds the goal.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiaryDay {
+    pub date: NaiveDate,
+    pub entries: Vec<DiaryEntry>,
+    pub nutrients: Nutrients,
+    pub goals: Option<NutritionTargets>,
+    pub remaining: Option<NutritionTargets>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SetGoalsCommand {
+    pub owner_id: String,
+    pub goals: NutritionTargets,
+}
+
+/// Daily intake of an owner over `from`..=`to`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct IntakeQuery {
+    pub owner_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+/// Nutrients eaten per day of the range, days without entries included.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct IntakeSummary {
+    pub days: Vec<PeriodSummary>,
+    pub total: Nutrients,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum ReportPeriod {
+    #[default]
+    Day,
+    /// Weeks starting on Monday.
+    Week,
+    /// Calendar months.
+    Month,
+}
+
+/// Report of the periods overlapping `from`..=`to`. Unset bounds default to
+/// the last seven days, four weeks or six months up to today.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReportQuery {
+    pub owner_id: String,
+    pub period: ReportPeriod,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Report {
+    pub period: ReportPeriod,
+    /// Current daily goals, unset when the user has none.
+    pub goals: Option<NutritionTargets>,
+    pub periods: Vec<ReportPeriodSummary>,
+    /// Days with at least one entry.
+    pub logging_streak: Streak,
+    /// Days within the goals, see [`ReportPeriodSummary::days_on_target`].
+    pub goal_streak: Streak,
+}
+
+/// Intake of a period. `average` is per logged day and `deviation` is the
+/// average minus the daily goals, positive when the average exceeds them.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReportPeriodSummary {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub days_logged: i64,
+    /// Logged days within 10 % of every goal.
+    pub days_on_target: i64,
+    pub total: Nutrients,
+    pub average: Nutrients,
+    pub deviation: Option<NutritionTargets>,
+}
+
+/// Consecutive days meeting a condition. `current` ends today, or yesterday
+/// while today has no entries yet. Only the last year is looked at.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Streak {
+    pub current: i64,
+    pub longest: i64,
+}
@@ -0,0 +1,25 @@
+/// Dietary label derived from a recipe's ingredients and nutrients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diet {
+    Vegan,
+    Vegetarian,
+    Keto,
+    GlutenFree,
+}
+
+impl Diet {
+    pub const ALL: [Diet; 4] = [Diet::Vegan, Diet::Vegetarian, Diet::Keto, Diet::GlutenFree];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Diet::Vegan => "vegan",
+            Diet::Vegetarian => "vegetarian",
+            Diet::Keto => "keto",
+            Diet::GlutenFree => "gluten-free",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Diet> {
+        Diet::ALL.into_iter().find(|diet| diet.code() == code)
+    }
+}
@@ -0,0 +1,27 @@
+use std::{error::Error, fmt};
+
+/// Failures caused by the caller rather than the service. Other errors are
+/// reported as internal ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceError {
+    /// Missing, wrong or expired credentials.
+    Unauthorized(String),
+    /// The caller is known but not allowed to do this.
+    Forbidden(String),
+    Invalid(String),
+    /// The caller exceeded its request quota.
+    RateLimited(String),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Unauthorized(msg)
+            | ServiceError::Forbidden(msg)
+            | ServiceError::Invalid(msg)
+            | ServiceError::RateLimited(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl Error for ServiceError {}
@@ -0,0 +1,14 @@
+use super::Pagination;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FavoriteCommand {
+    pub owner_id: String,
+    pub recipe_id: String,
+}
+
+/// Favourite recipes of an owner, most recently added first.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FavoritesQuery {
+    pub owner_id: String,
+    pub pagination: Pagination,
+}
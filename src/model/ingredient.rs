@@ -0,0 +1,191 @@
+use super::{user::Actor, Pagination};
+
+/// Dictionary entry used to recognise free-form recipe ingredient lines.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Ingredient {
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub animal: bool,
+    pub meat: bool,
+    pub gluten: bool,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    /// Store section the ingredient is found in, used by shopping lists.
+    pub aisle: String,
+}
+
+/// Nutrition values per 100 g of an ingredient, sodium and cholesterol in
+/// milligrams.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct IngredientNutrients {
+    pub proteins: f64,
+    pub fats: f64,
+    pub saturated_fat: f64,
+    pub carbohydrates: f64,
+    pub sugars: f64,
+    pub fiber: f64,
+    pub sodium: f64,
+    pub cholesterol: f64,
+    pub kcal: f64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateIngredientCommand {
+    pub actor: Actor,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub animal: bool,
+    pub meat: bool,
+    pub gluten: bool,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub aisle: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateIngredientCommand {
+    pub id: String,
+    pub actor: Actor,
+    pub name: Option<String>,
+    pub aliases: Option<Vec<String>>,
+    pub animal: Option<bool>,
+    pub meat: Option<bool>,
+    pub gluten: Option<bool>,
+    pub nutrients: Option<IngredientNutrients>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub aisle: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteIngredientCommand {
+    pub id: String,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct IngredientQuery {
+    pub id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct IngredientSearchQuery {
+    pub name: Option<String>,
+    pub pagination: Pagination,
+}
+
+/// CSV dump of nutrition data, see [`ImportIngredientCommand`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ImportIngredientsCommand {
+    pub data: String,
+    pub actor: Actor,
+}
+
+/// Nutrition data of a single ingredient imported from a CSV dump. Existing
+/// dictionary entries with the same name only get their nutrition updated.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ImportIngredientCommand {
+    pub name: String,
+    pub nutrients: IngredientNutrients,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Mass,
+    Volume,
+    Count,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Milligram,
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+    Milliliter,
+    Liter,
+    Teaspoon,
+    Tablespoon,
+    Cup,
+    Piece,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 11] = [
+        Unit::Milligram,
+        Unit::Gram,
+        Unit::Kilogram,
+        Unit::Ounce,
+        Unit::Pound,
+        Unit::Milliliter,
+        Unit::Liter,
+        Unit::Teaspoon,
+        Unit::Tablespoon,
+        Unit::Cup,
+        Unit::Piece,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Unit::Milligram => "mg",
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Ounce => "oz",
+            Unit::Pound => "lb",
+            Unit::Milliliter => "ml",
+            Unit::Liter => "l",
+            Unit::Teaspoon => "tsp",
+            Unit::Tablespoon => "tbsp",
+            Unit::Cup => "cup",
+            Unit::Piece => "pcs",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Unit> {
+        Unit::ALL.into_iter().find(|unit| unit.code() == code)
+    }
+
+    pub fn kind(&self) -> UnitKind {
+        match self {
+            Unit::Milligram | Unit::Gram | Unit::Kilogram | Unit::Ounce | Unit::Pound => {
+                UnitKind::Mass
+            }
+            Unit::Milliliter | Unit::Liter | Unit::Teaspoon | Unit::Tablespoon | Unit::Cup => {
+                UnitKind::Volume
+            }
+            Unit::Piece => UnitKind::Count,
+        }
+    }
+
+    /// Size of the unit in the base unit of its kind: grams, milliliters or
+    /// pieces.
+    pub fn factor(&self) -> f64 {
+        match self {
+            Unit::Milligram => 0.001,
+            Unit::Gram => 1.0,
+            Unit::Kilogram => 1000.0,
+            Unit::Ounce => 28.349_523,
+            Unit::Pound => 453.592_37,
+            Unit::Milliliter => 1.0,
+            Unit::Liter => 1000.0,
+            Unit::Teaspoon => 4.928_922,
+            Unit::Tablespoon => 14.786_765,
+            Unit::Cup => 236.588_236,
+            Unit::Piece => 1.0,
+        }
+    }
+}
+
+/// Free-form ingredient line such as "1 1/2 cups milk" split into its parts.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct IngredientLine {
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub name: String,
+}
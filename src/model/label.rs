@@ -0,0 +1,106 @@
+/// Regulatory layout of a nutrition label.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelFormat {
+    /// EU "Nutrition declaration": per 100 g values, salt, % reference intake.
+    #[default]
+    Eu,
+    /// US "Nutrition Facts": per serving values, sodium, % daily value.
+    Us,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct NutritionLabelQuery {
+    pub recipe_id: String,
+    pub format: LabelFormat,
+    /// Daily energy the percentages refer to, the service default when unset.
+    pub reference_kcal: Option<f64>,
+}
+
+/// Daily amounts used for percent daily values. Macronutrients are in grams,
+/// sodium and cholesterol in milligrams.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceIntake {
+    pub kcal: f64,
+    pub fats: f64,
+    pub saturated_fat: f64,
+    pub carbohydrates: f64,
+    pub sugars: Option<f64>,
+    pub fiber: Option<f64>,
+    pub proteins: f64,
+    pub sodium: f64,
+    pub cholesterol: Option<f64>,
+}
+
+impl ReferenceIntake {
+    /// Reference intakes of Regulation (EU) No 1169/2011 for an average adult.
+    pub fn eu() -> Self {
+        Self {
+            kcal: 2000.0,
+            fats: 70.0,
+            saturated_fat: 20.0,
+            carbohydrates: 260.0,
+            sugars: Some(90.0),
+            fiber: None,
+            proteins: 50.0,
+            sodium: 2400.0,
+            cholesterol: None,
+        }
+    }
+
+    /// FDA daily values for adults and children of 4 years and older.
+    pub fn us() -> Self {
+        Self {
+            kcal: 2000.0,
+            fats: 78.0,
+            saturated_fat: 20.0,
+            carbohydrates: 275.0,
+            sugars: None,
+            fiber: Some(28.0),
+            proteins: 50.0,
+            sodium: 2300.0,
+            cholesterol: Some(300.0),
+        }
+    }
+
+    /// Scales the energy bound amounts to another daily energy intake.
+    /// Sodium and cholesterol limits do not depend on energy.
+    pub fn with_kcal(self, kcal: f64) -> Self {
+        let factor = kcal / self.kcal;
+
+        Self {
+            kcal,
+            fats: self.fats * factor,
+            saturated_fat: self.saturated_fat * factor,
+            carbohydrates: self.carbohydrates * factor,
+            sugars: self.sugars.map(|v| v * factor),
+            fiber: self.fiber.map(|v| v * factor),
+            proteins: self.proteins * factor,
+            ..self
+        }
+    }
+}
+
+/// Nutrition facts panel of a recipe ready to be rendered.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct NutritionLabel {
+    pub format: LabelFormat,
+    pub title: String,
+    pub servings: i64,
+    /// Grams per serving, if known.
+    pub serving_weight: Option<f64>,
+    pub reference_kcal: f64,
+    pub rows: Vec<LabelRow>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LabelRow {
+    pub name: String,
+    pub unit: String,
+    /// Sub-rows such as "of which saturates" are indented.
+    pub indent: bool,
+    pub per_serving: f64,
+    /// Missing when the serving weight is unknown.
+    pub per_100g: Option<f64>,
+    /// Percent of the reference intake per serving.
+    pub daily_value: Option<f64>,
+}
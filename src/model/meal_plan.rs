@@ -0,0 +1,158 @@
+use chrono::NaiveDate;
+
+use super::{diet::Diet, recipe::Nutrients, Pagination};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Meal {
+    #[default]
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+impl Meal {
+    pub const ALL: [Meal; 4] = [Meal::Breakfast, Meal::Lunch, Meal::Dinner, Meal::Snack];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Meal::Breakfast => "breakfast",
+            Meal::Lunch => "lunch",
+            Meal::Dinner => "dinner",
+            Meal::Snack => "snack",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Meal> {
+        Meal::ALL.into_iter().find(|meal| meal.code() == code)
+    }
+}
+
+/// A user's plan of meals over a date range, both ends inclusive.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MealPlan {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub slots: Vec<MealSlot>,
+}
+
+/// A recipe planned for a meal of a day. `servings` is the number of
+/// servings eaten, one when unset.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MealSlot {
+    pub date: NaiveDate,
+    pub meal: Meal,
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
+
+impl MealSlot {
+    pub fn planned_servings(&self) -> i64 {
+        self.servings.unwrap_or(1)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateMealPlanCommand {
+    pub owner_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub slots: Vec<MealSlot>,
+}
+
+/// Changes a meal plan. `slots`, when set, replaces all slots of the plan.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateMealPlanCommand {
+    pub id: String,
+    pub owner_id: String,
+    pub name: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub slots: Option<Vec<MealSlot>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteMealPlanCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MealPlanQuery {
+    pub id: String,
+    pub owner_id: String,
+}
+
+/// Plans of an owner overlapping the `from`..=`to` range.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MealPlanSearchQuery {
+    pub owner_id: String,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub pagination: Pagination,
+}
+
+/// Nutrient totals of a meal plan. Weeks are consecutive seven day periods
+/// starting on the first day of the plan, the last one may be shorter.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MealPlanSummary {
+    pub id: String,
+    pub days: Vec<PeriodSummary>,
+    pub weeks: Vec<PeriodSummary>,
+    pub total: Nutrients,
+    /// Slots left out because their recipe no longer exists.
+    pub missing_recipes: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PeriodSummary {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub nutrients: Nutrients,
+}
+
+/// Daily nutrition targets. Macronutrients are in grams, unset ones are not
+/// optimised for.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct NutritionTargets {
+    pub kcal: f64,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+}
+
+/// Asks for a plan of `days` days starting at `start_date` filled with
+/// catalogue recipes, one serving per slot.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GenerateMealPlanCommand {
+    pub owner_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub days: i64,
+    pub meals: Vec<Meal>,
+    pub targets: NutritionTargets,
+    pub diets: Vec<Diet>,
+    pub exclude_allergens: Vec<String>,
+    /// Recipes with an ingredient line containing any of these are skipped.
+    pub exclude_ingredients: Vec<String>,
+    pub max_time_to_cook: Option<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GeneratedMealPlan {
+    pub plan: MealPlan,
+    pub days: Vec<DayDeviation>,
+}
+
+/// Planned nutrients of a day and their difference to the targets, positive
+/// when the day exceeds a target.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DayDeviation {
+    pub date: NaiveDate,
+    pub nutrients: Nutrients,
+    pub deviation: NutritionTargets,
+}
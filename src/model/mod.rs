@@ -1,11 +1,60 @@
+pub(crate) mod allergen;
+pub(crate) mod api_key;
 pub(crate) mod category;
+pub(crate) mod collection;
+pub(crate) mod cooking_log;
+pub(crate) mod diary;
+pub(crate) mod diet;
+pub(crate) mod error;
+pub(crate) mod favorite;
+pub(crate) mod ingredient;
+pub(crate) mod label;
+pub(crate) mod meal_plan;
+pub(crate) mod pantry;
+pub(crate) mod preference;
 pub(crate) mod recipe;
+pub(crate) mod review;
+pub(crate) mod revision;
+pub(crate) mod shopping_list;
+pub(crate) mod substitution;
+pub(crate) mod user;
 
+pub use self::allergen::*;
+pub use self::api_key::*;
 pub use self::category::*;
+pub use self::collection::*;
+pub use self::cooking_log::*;
+pub use self::diary::*;
+pub use self::diet::*;
+pub use self::error::*;
+pub use self::favorite::*;
+pub use self::ingredient::*;
+pub use self::label::*;
+pub use self::meal_plan::*;
+pub use self::pantry::*;
+pub use self::preference::*;
 pub use self::recipe::*;
+pub use self::review::*;
+pub use self::revision::*;
+pub use self::shopping_list::*;
+pub use self::substitution::*;
+pub use self::user::*;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct SearchResult<T> {
     pub count: i64,
     pub items: Vec<T>,
 }
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Pagination {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
@@ -0,0 +1,97 @@
+use chrono::NaiveDate;
+
+use super::{
+    ingredient::{Unit, UnitKind},
+    Pagination,
+};
+
+/// An ingredient a user has at home. `name` is the dictionary name when the
+/// ingredient is known so it can be matched against recipe lines. Items
+/// without a quantity are in stock in an unknown amount.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PantryItem {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl PantryItem {
+    /// Takes up to `amount`, in the base unit of `kind`, out of the item and
+    /// returns how much it took. Items without a quantity or of another unit
+    /// kind give nothing.
+    pub fn take(&mut self, kind: UnitKind, amount: f64) -> f64 {
+        let (Some(quantity), Some(unit)) = (self.quantity, self.unit) else {
+            return 0.0;
+        };
+        if unit.kind() != kind || amount <= 0.0 {
+            return 0.0;
+        }
+
+        let have = quantity * unit.factor();
+        let used = have.min(amount);
+        // two decimals, so repeated takes don't leave float noise
+        self.quantity = Some(((have - used) / unit.factor() * 100.0).round() / 100.0);
+
+        used
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreatePantryItemCommand {
+    pub owner_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdatePantryItemCommand {
+    pub id: String,
+    pub owner_id: String,
+    pub name: Option<String>,
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeletePantryItemCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PantryItemQuery {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PantrySearchQuery {
+    pub owner_id: String,
+    /// Only items expiring on or before this date.
+    pub expires_before: Option<NaiveDate>,
+    pub pagination: Pagination,
+}
+
+/// Amount of an ingredient a cooked recipe used, in grams, milliliters or
+/// pieces depending on `kind`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PantryUse {
+    pub name: String,
+    pub kind: UnitKind,
+    pub amount: f64,
+}
+
+/// Marks `servings` of a recipe as cooked, the recipe's own servings when
+/// unset, taking its ingredients out of the pantry.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CookRecipeCommand {
+    pub owner_id: String,
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
@@ -0,0 +1,35 @@
+use super::{diet::Diet, recipe::Recipe};
+
+/// What a user wants to eat. Recipes without all `diets` or with any of the
+/// `exclude_allergens` are never recommended, `categories` are preferred and
+/// recipes using `disliked_ingredients` are left out.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UserPreferences {
+    pub owner_id: String,
+    pub diets: Vec<Diet>,
+    pub exclude_allergens: Vec<String>,
+    pub categories: Vec<String>,
+    pub disliked_ingredients: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecommendationQuery {
+    pub owner_id: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum RecommendationReason {
+    /// Close to recipes the user picked before or in a preferred category.
+    #[default]
+    Profile,
+    /// Nothing is known about the user yet, picked by popularity.
+    Popular,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Recommendation {
+    pub recipe: Recipe,
+    pub score: f64,
+    pub reason: RecommendationReason,
+}
@@ -1,4 +1,8 @@
-use super::category::Category;
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+
+use super::{allergen::Allergen, category::Category, diet::Diet, user::Actor, SortDirection};
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Recipe {
@@ -11,12 +15,32 @@ pub struct Recipe {
     pub servings: i64,
     pub category: Category,
     pub ingredients: Vec<String>,
+    pub allergens: Vec<Allergen>,
+    pub diets: Vec<Diet>,
     pub nutrients: Nutrients,
+    pub nutrients_computed: bool,
     pub guideline: String,
+    /// Unset for recipes created before user accounts existed.
+    pub author_id: Option<String>,
+    /// Where the recipe was taken from.
+    pub source_url: Option<String>,
+    /// Credit line for the original author or publisher.
+    pub attribution: String,
+    /// Terms the recipe may be used under, e.g. `CC-BY-4.0`.
+    pub license: String,
+    /// Whether the user the recipe was fetched for saved it.
+    pub is_favorite: bool,
+    /// Mean of the visible review ratings, unset without reviews.
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
+    pub status: RecipeStatus,
+    /// Set while the recipe is deleted but not yet purged.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CreateRecipeCommand {
+    pub actor: Actor,
     pub cover: Vec<u8>,
     pub title: String,
     pub description: String,
@@ -25,13 +49,22 @@ pub struct CreateRecipeCommand {
     pub servings: i64,
     pub category: String,
     pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
+    pub diets: Vec<Diet>,
     pub nutrients: Nutrients,
+    /// Derive `nutrients` from the ingredients instead of trusting the
+    /// submitted values.
+    pub compute_nutrients: bool,
     pub guideline: String,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct UpdateRecipeCommand {
     pub id: String,
+    pub actor: Actor,
     pub cover: Option<Vec<u8>>,
     pub title: Option<String>,
     pub description: Option<String>,
@@ -40,39 +73,289 @@ pub struct UpdateRecipeCommand {
     pub servings: Option<i64>,
     pub category: Option<String>,
     pub ingredients: Option<Vec<String>>,
+    pub allergens: Option<Vec<String>>,
+    pub diets: Option<Vec<Diet>>,
     pub nutrients: UpdateNutrients,
+    /// Switches between computed and manual nutrients. When unset, submitting
+    /// any nutrient value switches the recipe to manual nutrients.
+    pub compute_nutrients: Option<bool>,
     pub guideline: Option<String>,
+    /// An empty value removes the source.
+    pub source_url: Option<String>,
+    pub attribution: Option<String>,
+    pub license: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct DeleteRecipeCommand {
     pub id: String,
+    pub actor: Actor,
+}
+
+/// Takes a deleted recipe back out of the trash.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RestoreRecipeCommand {
+    pub id: String,
+    pub actor: Actor,
+}
+
+/// Hard-deletes recipes and categories deleted longer ago than the retention
+/// period.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PurgeDeletedCommand {
+    pub actor: Actor,
+}
+
+/// Re-derives dietary labels of every recipe.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReclassifyRecipesCommand {
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PurgeResult {
+    pub recipes: i64,
+    pub categories: i64,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct RecipeQuery {
     pub id: String,
+    pub basis: NutrientBasis,
+    /// User whose favourites are flagged.
+    pub viewer_id: Option<String>,
+    /// Recipes outside of it are reported as not found.
+    pub visibility: RecipeVisibility,
+    /// Deleted recipes are reported as not found unless set.
+    pub include_deleted: bool,
+}
+
+/// Where a recipe is in the publication workflow.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeStatus {
+    #[default]
+    Draft,
+    InReview,
+    Published,
+    Archived,
+}
+
+impl RecipeStatus {
+    pub const ALL: [RecipeStatus; 4] = [
+        RecipeStatus::Draft,
+        RecipeStatus::InReview,
+        RecipeStatus::Published,
+        RecipeStatus::Archived,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecipeStatus::Draft => "draft",
+            RecipeStatus::InReview => "in_review",
+            RecipeStatus::Published => "published",
+            RecipeStatus::Archived => "archived",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<RecipeStatus> {
+        RecipeStatus::ALL
+            .into_iter()
+            .find(|status| status.code() == code)
+    }
+}
+
+/// A step of the publication workflow.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeTransition {
+    /// Asks editors to review a draft.
+    #[default]
+    Submit,
+    /// Takes a submitted recipe back to draft.
+    Withdraw,
+    /// Sends a submitted recipe back to its author.
+    Reject,
+    /// Makes a recipe public, also used to stage drafts and bring back
+    /// archived recipes.
+    Publish,
+    /// Hides a published recipe.
+    Archive,
+}
+
+impl RecipeTransition {
+    /// Statuses the transition may start from.
+    pub fn sources(&self) -> &'static [RecipeStatus] {
+        match self {
+            RecipeTransition::Submit => &[RecipeStatus::Draft],
+            RecipeTransition::Withdraw | RecipeTransition::Reject => &[RecipeStatus::InReview],
+            RecipeTransition::Publish => &[
+                RecipeStatus::Draft,
+                RecipeStatus::InReview,
+                RecipeStatus::Archived,
+            ],
+            RecipeTransition::Archive => &[RecipeStatus::Published],
+        }
+    }
+
+    pub fn target(&self) -> RecipeStatus {
+        match self {
+            RecipeTransition::Submit => RecipeStatus::InReview,
+            RecipeTransition::Withdraw | RecipeTransition::Reject => RecipeStatus::Draft,
+            RecipeTransition::Publish => RecipeStatus::Published,
+            RecipeTransition::Archive => RecipeStatus::Archived,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TransitionRecipeCommand {
+    pub id: String,
+    pub actor: Actor,
+    pub transition: RecipeTransition,
+}
+
+/// Which recipes a query may return.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum RecipeVisibility {
+    #[default]
+    Published,
+    /// Published recipes and any recipe of the user.
+    PublishedOrAuthoredBy(String),
+    /// Recipes of any status.
+    All,
+}
+
+impl RecipeVisibility {
+    pub fn allows(&self, recipe: &Recipe) -> bool {
+        match self {
+            RecipeVisibility::Published => recipe.status == RecipeStatus::Published,
+            RecipeVisibility::PublishedOrAuthoredBy(user_id) => {
+                recipe.status == RecipeStatus::Published
+                    || recipe.author_id.as_ref() == Some(user_id)
+            }
+            RecipeVisibility::All => true,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct RecipeSearchQuery {
+    pub ids: Option<Vec<String>>,
     pub category_id: Option<String>,
+    pub exclude_allergens: Option<Vec<String>>,
+    pub diets: Option<Vec<Diet>>,
+    /// Upper bound of `time_to_cook`.
+    pub max_time_to_cook: Option<i64>,
+    /// Dictionary names of which a recipe must use at least one. Recipes
+    /// using more of them come first.
+    pub ingredients: Option<Vec<String>>,
+    pub author_id: Option<String>,
+    /// Lower bound of the average rating, unrated recipes are left out.
+    pub min_rating: Option<f64>,
+    pub status: Option<RecipeStatus>,
+    pub visibility: RecipeVisibility,
+    pub sort: RecipeSort,
+    pub direction: SortDirection,
+    pub basis: NutrientBasis,
+    /// User whose favourites are flagged.
+    pub viewer_id: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum RecipeSort {
+    /// Order of creation.
+    #[default]
+    Created,
+    Title,
+    /// Average rating, then number of ratings. Unrated recipes come last.
+    Rating,
+}
+
+/// What the nutrient values of a recipe refer to. Recipes are stored per
+/// serving and converted on the way in and out.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NutrientBasis {
+    #[default]
+    PerServing,
+    PerRecipe,
+    Per100g,
 }
 
+/// Nutrient profile. Macronutrients are in grams, energy in kcal, sodium and
+/// cholesterol in milligrams. Micronutrients are keyed by name including
+/// their unit, e.g. `vitamin_c_mg`.
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Nutrients {
-    pub proteins: i64,
+    pub basis: NutrientBasis,
+    /// Weight of one serving in grams, required for per 100 g values.
+    pub serving_weight: Option<f64>,
+    pub kcal: f64,
+    pub proteins: f64,
     pub fats: f64,
+    pub saturated_fat: f64,
     pub carbohydrates: f64,
-    pub fiber: i64,
-    pub kcal: i64,
+    pub sugars: f64,
+    pub fiber: f64,
+    pub sodium: f64,
+    pub cholesterol: f64,
+    pub micronutrients: BTreeMap<String, f64>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct UpdateNutrients {
-    pub proteins: Option<i64>,
+    /// Basis of the submitted values.
+    pub basis: NutrientBasis,
+    pub serving_weight: Option<f64>,
+    pub kcal: Option<f64>,
+    pub proteins: Option<f64>,
     pub fats: Option<f64>,
+    pub saturated_fat: Option<f64>,
     pub carbohydrates: Option<f64>,
-    pub fiber: Option<i64>,
-    pub kcal: Option<i64>,
+    pub sugars: Option<f64>,
+    pub fiber: Option<f64>,
+    pub sodium: Option<f64>,
+    pub cholesterol: Option<f64>,
+    pub micronutrients: Option<BTreeMap<String, f64>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CalculateNutrientsQuery {
+    pub ingredients: Vec<String>,
+    pub servings: i64,
+}
+
+/// Per serving nutrients computed from ingredient lines. `unmatched` lists
+/// the lines that were left out because their ingredient, nutrition data or
+/// quantity is unknown.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct NutrientsCalculation {
+    pub nutrients: Nutrients,
+    pub unmatched: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SimilarRecipesQuery {
+    pub id: String,
+    pub limit: Option<i64>,
+}
+
+/// How close two recipes are, every part between 0 and 1. `score` is the
+/// weighted sum of the parts.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct RecipeSimilarity {
+    /// Jaccard index of the ingredients.
+    pub ingredients: f64,
+    pub category: f64,
+    /// Jaccard index of the dietary labels.
+    pub tags: f64,
+    /// Closeness of the shares of energy from the macronutrients.
+    pub nutrients: f64,
+    pub time: f64,
+    pub difficulty: f64,
+    pub score: f64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SimilarRecipe {
+    pub recipe: Recipe,
+    pub similarity: RecipeSimilarity,
 }
@@ -0,0 +1,87 @@
+use chrono::NaiveDateTime;
+
+use super::{user::Actor, Pagination};
+
+pub const MIN_RATING: i32 = 1;
+pub const MAX_RATING: i32 = 5;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Review {
+    pub id: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    /// Stars from 1 to 5.
+    pub rating: i32,
+    pub body: String,
+    /// Reported by a user and waiting for a moderator.
+    pub flagged: bool,
+    pub flag_reason: String,
+    /// Hidden by a moderator, not listed and not part of the recipe rating.
+    pub hidden: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateReviewCommand {
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub rating: i32,
+    pub body: String,
+}
+
+/// Changes a review, only its author may.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateReviewCommand {
+    pub id: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub rating: Option<i32>,
+    pub body: Option<String>,
+}
+
+/// Removes a review, its author or an editor may.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteReviewCommand {
+    pub id: String,
+    pub recipe_id: String,
+    pub actor: Actor,
+}
+
+/// Reports a review to the moderators.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FlagReviewCommand {
+    pub id: String,
+    pub recipe_id: String,
+    pub reason: String,
+}
+
+/// Settles a flagged review by hiding or keeping it, editors only.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ModerateReviewCommand {
+    pub id: String,
+    pub recipe_id: String,
+    pub actor: Actor,
+    pub hidden: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReviewQuery {
+    pub id: String,
+    pub recipe_id: String,
+}
+
+/// Newest reviews first. Hidden ones only show up in the moderation queue.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReviewSearchQuery {
+    pub recipe_id: Option<String>,
+    /// Only reviews waiting for moderation.
+    pub flagged: bool,
+    pub pagination: Pagination,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FlaggedReviewsQuery {
+    pub actor: Actor,
+    pub pagination: Pagination,
+}
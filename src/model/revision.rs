@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+
+use super::{
+    diet::Diet,
+    recipe::{Nutrients, RecipeStatus},
+    user::Actor,
+    Pagination,
+};
+
+/// Immutable copy of a recipe's content after a change, a status change or
+/// a restore. Numbers count up from 1 per recipe.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecipeRevision {
+    pub id: String,
+    pub recipe_id: String,
+    pub number: i32,
+    /// User who made the change, unset for changes made by the service.
+    pub author_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub snapshot: RecipeSnapshot,
+}
+
+/// The editable content of a recipe. Nutrients are per serving.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecipeSnapshot {
+    pub cover: Vec<u8>,
+    pub title: String,
+    pub description: String,
+    pub time_to_cook: i64,
+    pub difficulty: String,
+    pub servings: i64,
+    pub category_id: String,
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
+    pub diets: Vec<Diet>,
+    pub nutrients: Nutrients,
+    pub nutrients_computed: bool,
+    pub guideline: String,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    /// Unset in revisions recorded before status changes were versioned.
+    /// Rollback leaves the status alone, it only changes by transitions.
+    pub status: Option<RecipeStatus>,
+}
+
+/// Revisions of a recipe, latest first.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevisionSearchQuery {
+    pub recipe_id: String,
+    pub actor: Actor,
+    pub pagination: Pagination,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevisionQuery {
+    pub recipe_id: String,
+    pub number: i32,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevisionDiffQuery {
+    pub recipe_id: String,
+    pub from: i32,
+    pub to: i32,
+    pub actor: Actor,
+}
+
+/// Changes from revision `from` to revision `to`, either may be the older
+/// one.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevisionDiff {
+    pub recipe_id: String,
+    pub from: i32,
+    pub to: i32,
+    pub fields: Vec<FieldChange>,
+    pub lists: Vec<ListChange>,
+}
+
+/// A changed single value, e.g. `title` or `nutrients.kcal`, rendered as
+/// text. Unset values are empty.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Entries added to or removed from a list such as `ingredients`. `reordered`
+/// is set when the entries are the same but their order changed.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ListChange {
+    pub field: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub reordered: bool,
+}
+
+/// Restores the content of a revision, which is recorded as a new revision.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RollbackRecipeCommand {
+    pub recipe_id: String,
+    pub number: i32,
+    pub actor: Actor,
+}
@@ -0,0 +1,74 @@
+use super::{ingredient::Unit, Pagination};
+
+/// Consolidated list of ingredients to buy for a set of recipes.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShoppingList {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    /// The meal plan the list was generated from, if any.
+    pub meal_plan_id: Option<String>,
+    /// Ordered by aisle and name.
+    pub items: Vec<ShoppingListItem>,
+    /// Recipes left out because they were missing, deleted or unpublished
+    /// when the list was built.
+    pub missing_recipes: Vec<String>,
+}
+
+/// An ingredient to buy. Quantities are summed in grams, milliliters or
+/// pieces and scaled up to kilograms or liters; `quantity` is unset for
+/// lines without an amount such as "salt to taste".
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShoppingListItem {
+    pub id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<Unit>,
+    pub aisle: String,
+    pub checked: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecipeServings {
+    pub recipe_id: String,
+    /// The recipe's own servings when unset.
+    pub servings: Option<i64>,
+}
+
+/// Builds a list from `recipes`, or from the slots of `meal_plan_id` when
+/// set.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateShoppingListCommand {
+    pub owner_id: String,
+    pub name: String,
+    pub recipes: Vec<RecipeServings>,
+    pub meal_plan_id: Option<String>,
+    /// Keeps what the owner already has in the pantry on the list.
+    pub ignore_pantry: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CheckShoppingListItemCommand {
+    pub shopping_list_id: String,
+    pub owner_id: String,
+    pub item_id: String,
+    pub checked: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteShoppingListCommand {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShoppingListQuery {
+    pub id: String,
+    pub owner_id: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShoppingListSearchQuery {
+    pub owner_id: String,
+    pub pagination: Pagination,
+}
@@ -0,0 +1,93 @@
+use super::{
+    ingredient::Unit,
+    recipe::{Nutrients, Recipe},
+    user::Actor,
+    Pagination,
+};
+
+/// Knowledge base entry: `substitute` replaces `ratio` times the amount of
+/// `ingredient`, measured in `unit` when set and in the unit of the recipe
+/// line otherwise. `avoids` lists the restrictions the swap satisfies, e.g.
+/// `dairy`, `eggs`, `gluten`, `meat` or `animal`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Substitution {
+    pub id: String,
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<Unit>,
+    pub avoids: Vec<String>,
+    pub notes: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateSubstitutionCommand {
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<Unit>,
+    pub avoids: Vec<String>,
+    pub notes: String,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateSubstitutionCommand {
+    pub id: String,
+    pub ingredient: Option<String>,
+    pub substitute: Option<String>,
+    pub ratio: Option<f64>,
+    pub unit: Option<Unit>,
+    pub avoids: Option<Vec<String>>,
+    pub notes: Option<String>,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteSubstitutionCommand {
+    pub id: String,
+    pub actor: Actor,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SubstitutionQuery {
+    pub id: String,
+}
+
+/// Entries for `ingredient` satisfying any of `avoids`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SubstitutionSearchQuery {
+    pub ingredient: Option<String>,
+    pub avoids: Option<Vec<String>>,
+    pub pagination: Pagination,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecipeSubstitutionsQuery {
+    pub recipe_id: String,
+    pub avoid: Vec<String>,
+}
+
+/// Swaps for the lines of a recipe. `recipe` has the first suggestion of
+/// every line applied and `nutrients_delta` is the per serving change this
+/// makes, estimated from the ingredient dictionary.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecipeSubstitutions {
+    pub recipe: Recipe,
+    pub lines: Vec<LineSubstitutions>,
+    pub nutrients_delta: Nutrients,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LineSubstitutions {
+    pub line: String,
+    pub ingredient: String,
+    pub suggestions: Vec<SuggestedSwap>,
+}
+
+/// A knowledge base entry applied to a recipe line.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SuggestedSwap {
+    pub substitution: Substitution,
+    pub line: String,
+}
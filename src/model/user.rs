@@ -0,0 +1,99 @@
+use chrono::NaiveDateTime;
+
+/// What a user may do, every role includes the ones before it. Viewers only
+/// read, authors also manage their own recipes, editors manage every recipe
+/// and the categories, admins also assign roles.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    #[default]
+    Viewer,
+    Author,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Viewer, Role::Author, Role::Editor, Role::Admin];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Author => "author",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Role> {
+        Role::ALL.into_iter().find(|role| role.code() == code)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct User {
+    pub id: String,
+    pub email: String,
+    pub name: String,
+    pub role: Role,
+    pub created_at: NaiveDateTime,
+}
+
+/// The authenticated user a change is made by, checked against the access
+/// policies of the services.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Actor {
+    pub user_id: String,
+    pub role: Role,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SetRoleCommand {
+    pub actor: Actor,
+    pub user_id: String,
+    pub role: Role,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RegisterCommand {
+    pub email: String,
+    pub password: String,
+    pub name: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LoginCommand {
+    pub email: String,
+    pub password: String,
+}
+
+/// Trades a refresh token for a new pair, the old one stops working.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RefreshTokenCommand {
+    pub refresh_token: String,
+}
+
+/// Revokes the session, or every session of the user when `all` is set.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LogoutCommand {
+    pub user_id: String,
+    pub session_id: String,
+    pub all: bool,
+}
+
+/// Issued on login. `expires_in` is the lifetime of the access token in
+/// seconds.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+    pub user: User,
+}
+
+/// The user and session an access token was issued for.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Identity {
+    pub user_id: String,
+    pub session_id: String,
+    pub role: Role,
+}
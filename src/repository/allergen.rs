@@ -0,0 +1,100 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+
+use crate::model::{allergen as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct AllergenRepository {
+    pool: Arc<Pool>,
+}
+
+impl AllergenRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        AllergenRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateAllergenCommand,
+    ) -> Result<app_model::Allergen, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let allergen_resp = conn
+            .interact(move |conn| {
+                let new_allergen: db_model::CreateAllergen = item.into();
+
+                diesel::insert_into(scheme::allergens::table)
+                    .values(new_allergen)
+                    .returning(db_model::Allergen::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(allergen_resp.into())
+    }
+
+    /// Deletes a custom allergen. The regulatory ones are not removable.
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteAllergenCommand,
+    ) -> Result<app_model::Allergen, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let allergen_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::allergens::table)
+                    .filter(scheme::allergens::code.eq(q.code))
+                    .filter(scheme::allergens::custom.eq(true))
+                    .returning(db_model::Allergen::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(allergen_resp.into())
+    }
+
+    /// Number of recipes declaring the allergen, deleted ones included as
+    /// they may be restored.
+    pub async fn recipe_count(&self, code: String) -> Result<i64, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let count = conn
+            .interact(move |conn| {
+                scheme::recipes::table
+                    .filter(scheme::recipes::allergens.contains(vec![Some(code)]))
+                    .count()
+                    .get_result::<i64>(conn)
+            })
+            .await??;
+
+        Ok(count)
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::AllergenSearchQuery,
+    ) -> Result<SearchResult<app_model::Allergen>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let allergen_resp = conn
+            .interact(|conn| {
+                let mut myq = scheme::allergens::table.into_boxed();
+
+                if let Some(codes) = q.codes {
+                    myq = myq.filter(scheme::allergens::code.eq_any(codes));
+                }
+
+                myq.order((scheme::allergens::custom, scheme::allergens::id))
+                    .select(db_model::Allergen::as_select())
+                    .get_results(conn)
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count: allergen_resp.len() as i64,
+            items: allergen_resp.into_iter().map(|item| item.into()).collect(),
+        })
+    }
+}
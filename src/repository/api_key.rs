@@ -0,0 +1,169 @@
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct ApiKeyRepository {
+    pool: Arc<Pool>,
+}
+
+impl ApiKeyRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        ApiKeyRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateApiKeyCommand,
+        prefix: String,
+        key_hash: String,
+        rate_limit: i32,
+    ) -> Result<app_model::ApiKey, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let key_resp = conn
+            .interact(move |conn| {
+                diesel::insert_into(scheme::api_keys::table)
+                    .values(db_model::CreateApiKey {
+                        uuid: Uuid::new_v4().to_string(),
+                        owner_id: item.actor.user_id,
+                        name: item.name,
+                        prefix,
+                        key_hash,
+                        scopes: item
+                            .scopes
+                            .iter()
+                            .map(|scope| Some(scope.code().to_string()))
+                            .collect(),
+                        rate_limit,
+                    })
+                    .returning(db_model::ApiKey::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(key_resp.into())
+    }
+
+    /// Active keys of the owner, newest first.
+    pub async fn list(
+        &self,
+        q: app_model::ApiKeysQuery,
+    ) -> Result<Vec<app_model::ApiKey>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let keys_resp = conn
+            .interact(move |conn| {
+                scheme::api_keys::table
+                    .filter(scheme::api_keys::owner_id.eq(q.owner_id))
+                    .filter(scheme::api_keys::revoked_at.is_null())
+                    .order(scheme::api_keys::id.desc())
+                    .select(db_model::ApiKey::as_select())
+                    .load(conn)
+            })
+            .await??;
+
+        Ok(keys_resp.into_iter().map(|key| key.into()).collect())
+    }
+
+    pub async fn rotate(
+        &self,
+        q: app_model::RotateApiKeyCommand,
+        prefix: String,
+        key_hash: String,
+    ) -> Result<app_model::ApiKey, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let key_resp = conn
+            .interact(move |conn| {
+                diesel::update(scheme::api_keys::table)
+                    .filter(scheme::api_keys::uuid.eq(q.id))
+                    .filter(scheme::api_keys::owner_id.eq(q.owner_id))
+                    .filter(scheme::api_keys::revoked_at.is_null())
+                    .set((
+                        scheme::api_keys::prefix.eq(prefix),
+                        scheme::api_keys::key_hash.eq(key_hash),
+                        scheme::api_keys::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .returning(db_model::ApiKey::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(key_resp.into())
+    }
+
+    pub async fn revoke(
+        &self,
+        q: app_model::RevokeApiKeyCommand,
+    ) -> Result<app_model::ApiKey, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let key_resp = conn
+            .interact(move |conn| {
+                let now = Utc::now().naive_utc();
+
+                diesel::update(scheme::api_keys::table)
+                    .filter(scheme::api_keys::uuid.eq(q.id))
+                    .filter(scheme::api_keys::owner_id.eq(q.owner_id))
+                    .filter(scheme::api_keys::revoked_at.is_null())
+                    .set((
+                        scheme::api_keys::revoked_at.eq(now),
+                        scheme::api_keys::updated_at.eq(now),
+                    ))
+                    .returning(db_model::ApiKey::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(key_resp.into())
+    }
+
+    /// The active key with `key_hash` and the current role of its owner.
+    pub async fn find_active(
+        &self,
+        key_hash: String,
+    ) -> Result<Option<(app_model::ApiKey, app_model::Role)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let key_resp = conn
+            .interact(move |conn| {
+                scheme::api_keys::table
+                    .inner_join(
+                        scheme::users::table.on(scheme::users::uuid.eq(scheme::api_keys::owner_id)),
+                    )
+                    .filter(scheme::api_keys::key_hash.eq(key_hash))
+                    .filter(scheme::api_keys::revoked_at.is_null())
+                    .select((db_model::ApiKey::as_select(), scheme::users::role))
+                    .first::<(db_model::ApiKey, String)>(conn)
+                    .optional()
+            })
+            .await??;
+
+        Ok(key_resp.map(|(key, role)| {
+            (
+                key.into(),
+                app_model::Role::from_code(&role).unwrap_or_default(),
+            )
+        }))
+    }
+
+    pub async fn touch(&self, id: String, used_at: NaiveDateTime) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        conn.interact(move |conn| {
+            diesel::update(scheme::api_keys::table)
+                .filter(scheme::api_keys::uuid.eq(id))
+                .set(scheme::api_keys::last_used_at.eq(used_at))
+                .execute(conn)
+        })
+        .await??;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,59 @@
+use chrono::{NaiveDateTime, Utc};
 use deadpool_diesel::postgres::Pool;
-use diesel::prelude::*;
+use diesel::{
+    dsl::sql,
+    expression::SqlLiteral,
+    pg::Pg,
+    prelude::*,
+    sql_types::{BigInt, Bool},
+};
 use std::{error::Error, sync::Arc};
 use uuid::Uuid;
 
-use crate::model::{category as app_model, SearchResult};
+use crate::model::{category as app_model, SearchResult, SortDirection};
 
-use super::{model as db_model, scheme};
+use super::{like_pattern, model as db_model, scheme};
 
 pub struct CategoryRepository {
     pool: Arc<Pool>,
 }
 
+/// Number of recipes referencing the category, evaluated per selected row.
+/// Deleted recipes are not counted.
+fn recipe_count() -> SqlLiteral<BigInt> {
+    sql::<BigInt>(
+        "(SELECT COUNT(*) FROM recipes \
+         WHERE recipes.category_id = categories.uuid AND recipes.deleted_at IS NULL)",
+    )
+}
+
+/// Whether no recipe, deleted or not, references the category.
+fn unreferenced() -> SqlLiteral<Bool> {
+    sql::<Bool>("NOT EXISTS (SELECT 1 FROM recipes WHERE recipes.category_id = categories.uuid)")
+}
+
+fn with_recipe_count((category, count): (db_model::Category, i64)) -> app_model::Category {
+    let mut res: app_model::Category = category.into();
+    res.recipe_count = count;
+    res
+}
+
+fn filtered(q: &app_model::CategorySearchQuery) -> scheme::categories::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::categories::table
+        .filter(scheme::categories::deleted_at.is_null())
+        .into_boxed();
+
+    if let Some(category_ids) = q.ids.clone() {
+        myq = myq.filter(scheme::categories::uuid.eq_any(category_ids));
+    }
+
+    if let Some(name) = q.name.as_deref() {
+        myq = myq.filter(scheme::categories::name.ilike(like_pattern(name)));
+    }
+
+    myq
+}
+
 impl CategoryRepository {
     pub async fn new(pool: Arc<Pool>) -> Self {
         CategoryRepository { pool }
@@ -47,13 +90,34 @@ impl CategoryRepository {
             .interact(|conn| {
                 scheme::categories::table
                     .filter(scheme::categories::uuid.eq(q.id))
+                    .filter(scheme::categories::deleted_at.is_null())
                     .limit(1)
-                    .select(db_model::Category::as_select())
+                    .select((db_model::Category::as_select(), recipe_count()))
                     .get_result(conn)
             })
             .await??;
 
-        Ok(category_resp.into())
+        Ok(with_recipe_count(category_resp))
+    }
+
+    pub async fn fetch_by_slug(
+        &self,
+        q: app_model::CategorySlugQuery,
+    ) -> Result<app_model::Category, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let category_resp = conn
+            .interact(|conn| {
+                scheme::categories::table
+                    .filter(scheme::categories::slug.eq(q.slug))
+                    .filter(scheme::categories::deleted_at.is_null())
+                    .limit(1)
+                    .select((db_model::Category::as_select(), recipe_count()))
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(with_recipe_count(category_resp))
     }
 
     pub async fn update(
@@ -69,18 +133,37 @@ impl CategoryRepository {
 
                 diesel::update(scheme::categories::table)
                     .filter(scheme::categories::uuid.eq(&category_id))
+                    .filter(scheme::categories::deleted_at.is_null())
                     .set(category_update)
                     .execute(conn)?;
 
                 scheme::categories::table
                     .filter(scheme::categories::uuid.eq(&category_id))
+                    .filter(scheme::categories::deleted_at.is_null())
                     .limit(1)
-                    .select(db_model::Category::as_select())
+                    .select((db_model::Category::as_select(), recipe_count()))
                     .get_result(conn)
             })
             .await??;
 
-        Ok(category_resp.into())
+        Ok(with_recipe_count(category_resp))
+    }
+
+    /// Number of recipes in the category, deleted ones included as they may
+    /// be restored.
+    pub async fn recipe_count(&self, id: String) -> Result<i64, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let count = conn
+            .interact(move |conn| {
+                scheme::recipes::table
+                    .filter(scheme::recipes::category_id.eq(id))
+                    .count()
+                    .get_result::<i64>(conn)
+            })
+            .await??;
+
+        Ok(count)
     }
 
     pub async fn delete(
@@ -94,8 +177,9 @@ impl CategoryRepository {
             .await?;
 
         conn.interact(|conn| {
-            diesel::delete(scheme::categories::table)
+            diesel::update(scheme::categories::table)
                 .filter(scheme::categories::uuid.eq(q.id))
+                .set(scheme::categories::deleted_at.eq(Utc::now().naive_utc()))
                 .execute(conn)
         })
         .await??;
@@ -103,28 +187,78 @@ impl CategoryRepository {
         Ok(category_resp)
     }
 
+    /// Hard-deletes categories deleted before `before`, returns their number.
+    /// Categories still referenced by a recipe in the trash are kept until
+    /// that recipe is purged.
+    pub async fn purge(&self, before: NaiveDateTime) -> Result<i64, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let purged = conn
+            .interact(move |conn| {
+                diesel::delete(scheme::categories::table)
+                    .filter(scheme::categories::deleted_at.lt(before))
+                    .filter(unreferenced())
+                    .execute(conn)
+            })
+            .await??;
+
+        Ok(purged as i64)
+    }
+
     pub async fn search(
         &self,
         q: app_model::CategorySearchQuery,
     ) -> Result<SearchResult<app_model::Category>, Box<dyn Error>> {
         let conn = self.pool.get().await?;
 
-        let category_resp = conn
-            .interact(|conn| {
-                let mut myq = scheme::categories::table.into_boxed();
+        let (count, category_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q);
+
+                myq = match (q.sort, q.direction) {
+                    (app_model::CategorySort::Order, SortDirection::Asc) => {
+                        myq.order(scheme::categories::sort_order.asc())
+                    }
+                    (app_model::CategorySort::Order, SortDirection::Desc) => {
+                        myq.order(scheme::categories::sort_order.desc())
+                    }
+                    (app_model::CategorySort::Name, SortDirection::Asc) => {
+                        myq.order(scheme::categories::name.asc())
+                    }
+                    (app_model::CategorySort::Name, SortDirection::Desc) => {
+                        myq.order(scheme::categories::name.desc())
+                    }
+                    (app_model::CategorySort::RecipeCount, SortDirection::Asc) => {
+                        myq.order(recipe_count().asc())
+                    }
+                    (app_model::CategorySort::RecipeCount, SortDirection::Desc) => {
+                        myq.order(recipe_count().desc())
+                    }
+                };
+                myq = myq.then_order_by(scheme::categories::name.asc());
+                myq = myq.then_order_by(scheme::categories::id.asc());
 
-                if let Some(category_ids) = q.ids {
-                    myq = myq.filter(scheme::categories::uuid.eq_any(category_ids));
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
                 }
 
-                myq.select(db_model::Category::as_select())
-                    .get_results(conn)
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let items = myq
+                    .select((db_model::Category::as_select(), recipe_count()))
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, items))
             })
             .await??;
 
         Ok(SearchResult {
-            count: category_resp.len() as i64,
-            items: category_resp.into_iter().map(|item| item.into()).collect(),
+            count,
+            items: category_resp.into_iter().map(with_recipe_count).collect(),
         })
     }
 }
@@ -0,0 +1,256 @@
+use chrono::Utc;
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{collection as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct CollectionRepository {
+    pool: Arc<Pool>,
+}
+
+fn insert_items(
+    conn: &mut PgConnection,
+    collection_id: i32,
+    items: Vec<app_model::CollectionItem>,
+) -> QueryResult<usize> {
+    let rows: Vec<db_model::CreateCollectionItem> = items
+        .into_iter()
+        .enumerate()
+        .map(|(position, item)| db_model::CreateCollectionItem {
+            collection_id,
+            recipe_id: item.recipe_id,
+            position: position as i32,
+            note: item.note,
+        })
+        .collect();
+
+    diesel::insert_into(scheme::collection_items::table)
+        .values(rows)
+        .execute(conn)
+}
+
+fn load_items(
+    conn: &mut PgConnection,
+    collections: Vec<db_model::Collection>,
+) -> QueryResult<Vec<app_model::Collection>> {
+    let items = db_model::CollectionItem::belonging_to(&collections)
+        .order(scheme::collection_items::position)
+        .select(db_model::CollectionItem::as_select())
+        .load(conn)?;
+
+    Ok(items
+        .grouped_by(&collections)
+        .into_iter()
+        .zip(collections)
+        .map(|(items, collection)| (collection, items).into())
+        .collect())
+}
+
+impl CollectionRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        CollectionRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateCollectionCommand,
+        share_token: Option<String>,
+    ) -> Result<app_model::Collection, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let collection_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let collection = diesel::insert_into(scheme::collections::table)
+                        .values(db_model::CreateCollection {
+                            uuid: Uuid::new_v4().to_string(),
+                            owner_id: item.owner_id,
+                            name: item.name,
+                            description: item.description,
+                            share_token,
+                        })
+                        .returning(db_model::Collection::as_returning())
+                        .get_result(conn)?;
+
+                    insert_items(conn, collection.id, item.items)?;
+
+                    load_items(conn, vec![collection])
+                })
+            })
+            .await??;
+
+        collection_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "collection was not created".into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::CollectionQuery,
+    ) -> Result<app_model::Collection, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let collection_resp = conn
+            .interact(|conn| {
+                let collection = scheme::collections::table
+                    .filter(scheme::collections::uuid.eq(q.id))
+                    .filter(scheme::collections::owner_id.eq(q.owner_id))
+                    .limit(1)
+                    .select(db_model::Collection::as_select())
+                    .get_result(conn)?;
+
+                load_items(conn, vec![collection])
+            })
+            .await??;
+
+        collection_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "collection not found".into())
+    }
+
+    pub async fn fetch_shared(
+        &self,
+        q: app_model::SharedCollectionQuery,
+    ) -> Result<app_model::Collection, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let collection_resp = conn
+            .interact(|conn| {
+                let collection = scheme::collections::table
+                    .filter(scheme::collections::share_token.eq(q.share_token))
+                    .limit(1)
+                    .select(db_model::Collection::as_select())
+                    .get_result(conn)?;
+
+                load_items(conn, vec![collection])
+            })
+            .await??;
+
+        collection_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "collection not found".into())
+    }
+
+    /// `share_token` is left alone when unset and cleared when `Some(None)`.
+    pub async fn update(
+        &self,
+        q: app_model::UpdateCollectionCommand,
+        share_token: Option<Option<String>>,
+    ) -> Result<app_model::Collection, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let collection_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let collection = diesel::update(scheme::collections::table)
+                        .filter(scheme::collections::uuid.eq(&q.id))
+                        .filter(scheme::collections::owner_id.eq(&q.owner_id))
+                        .set(db_model::UpdateCollection {
+                            name: q.name,
+                            description: q.description,
+                            share_token,
+                            updated_at: Utc::now().naive_utc(),
+                        })
+                        .returning(db_model::Collection::as_returning())
+                        .get_result(conn)?;
+
+                    if let Some(items) = q.items {
+                        diesel::delete(scheme::collection_items::table)
+                            .filter(scheme::collection_items::collection_id.eq(collection.id))
+                            .execute(conn)?;
+
+                        insert_items(conn, collection.id, items)?;
+                    }
+
+                    load_items(conn, vec![collection])
+                })
+            })
+            .await??;
+
+        collection_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "collection not found".into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteCollectionCommand,
+    ) -> Result<app_model::Collection, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let collection_resp = conn
+            .interact(|conn| {
+                conn.transaction(|conn| {
+                    let collection = scheme::collections::table
+                        .filter(scheme::collections::uuid.eq(q.id))
+                        .filter(scheme::collections::owner_id.eq(q.owner_id))
+                        .limit(1)
+                        .select(db_model::Collection::as_select())
+                        .get_result(conn)?;
+                    let collection_id = collection.id;
+
+                    let res = load_items(conn, vec![collection])?;
+
+                    diesel::delete(scheme::collections::table)
+                        .filter(scheme::collections::id.eq(collection_id))
+                        .execute(conn)?;
+
+                    QueryResult::Ok(res)
+                })
+            })
+            .await??;
+
+        collection_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "collection not found".into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::CollectionSearchQuery,
+    ) -> Result<SearchResult<app_model::Collection>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, collection_resp) = conn
+            .interact(move |conn| {
+                let count = scheme::collections::table
+                    .filter(scheme::collections::owner_id.eq(&q.owner_id))
+                    .count()
+                    .get_result::<i64>(conn)?;
+
+                let mut myq = scheme::collections::table
+                    .filter(scheme::collections::owner_id.eq(&q.owner_id))
+                    .order((scheme::collections::name, scheme::collections::id))
+                    .into_boxed();
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let collections = myq
+                    .select(db_model::Collection::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, load_items(conn, collections)?))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: collection_resp,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{cooking_log as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct CookingLogRepository {
+    pool: Arc<Pool>,
+}
+
+fn filtered(q: &app_model::CookingLogSearchQuery) -> scheme::cooking_logs::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::cooking_logs::table
+        .filter(scheme::cooking_logs::owner_id.eq(q.owner_id.clone()))
+        .into_boxed();
+
+    if let Some(from) = q.from {
+        myq = myq.filter(scheme::cooking_logs::cooked_on.ge(from));
+    }
+
+    if let Some(to) = q.to {
+        myq = myq.filter(scheme::cooking_logs::cooked_on.le(to));
+    }
+
+    myq
+}
+
+impl CookingLogRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        CookingLogRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::LogCookingCommand,
+    ) -> Result<app_model::CookingLogEntry, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let entry_resp = conn
+            .interact(move |conn| {
+                diesel::insert_into(scheme::cooking_logs::table)
+                    .values(db_model::CreateCookingLogEntry::new(
+                        Uuid::new_v4().to_string(),
+                        item,
+                    ))
+                    .returning(db_model::CookingLogEntry::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        entry_resp.try_into()
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteCookingLogCommand,
+    ) -> Result<app_model::CookingLogEntry, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let entry_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::cooking_logs::table)
+                    .filter(scheme::cooking_logs::uuid.eq(q.id))
+                    .filter(scheme::cooking_logs::owner_id.eq(q.owner_id))
+                    .returning(db_model::CookingLogEntry::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        entry_resp.try_into()
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::CookingLogSearchQuery,
+    ) -> Result<SearchResult<app_model::CookingLogEntry>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, entries_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q).order((
+                    scheme::cooking_logs::cooked_on.desc(),
+                    scheme::cooking_logs::id.desc(),
+                ));
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let entries = myq
+                    .select(db_model::CookingLogEntry::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, entries))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: entries_resp
+                .into_iter()
+                .map(app_model::CookingLogEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// How often each recipe is logged as cooked, by `owner_id` or by anyone when unset.
+    /// Most cooked first.
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let counts_resp = conn
+            .interact(move |conn| {
+                let mut myq = scheme::cooking_logs::table
+                    .group_by(scheme::cooking_logs::recipe_id)
+                    .select((scheme::cooking_logs::recipe_id, diesel::dsl::count_star()))
+                    .into_boxed();
+
+                if let Some(owner_id) = owner_id {
+                    myq = myq.filter(scheme::cooking_logs::owner_id.eq(owner_id));
+                }
+
+                myq.order((
+                    diesel::dsl::count_star().desc(),
+                    scheme::cooking_logs::recipe_id,
+                ))
+                .load::<(String, i64)>(conn)
+            })
+            .await??;
+
+        Ok(counts_resp)
+    }
+}
@@ -0,0 +1,139 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+
+use crate::model::{self as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct FavoriteRepository {
+    pool: Arc<Pool>,
+}
+
+impl FavoriteRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        FavoriteRepository { pool }
+    }
+
+    /// Saves the recipe, saving it twice changes nothing.
+    pub async fn add(&self, item: app_model::FavoriteCommand) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        conn.interact(move |conn| {
+            diesel::insert_into(scheme::favorites::table)
+                .values(db_model::CreateFavorite {
+                    owner_id: item.owner_id,
+                    recipe_id: item.recipe_id,
+                })
+                .on_conflict((scheme::favorites::owner_id, scheme::favorites::recipe_id))
+                .do_nothing()
+                .execute(conn)
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    pub async fn remove(&self, item: app_model::FavoriteCommand) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        conn.interact(move |conn| {
+            diesel::delete(scheme::favorites::table)
+                .filter(scheme::favorites::owner_id.eq(item.owner_id))
+                .filter(scheme::favorites::recipe_id.eq(item.recipe_id))
+                .execute(conn)
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Ids of the favourite recipes.
+    pub async fn list(
+        &self,
+        q: app_model::FavoritesQuery,
+    ) -> Result<SearchResult<String>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, favorites_resp) = conn
+            .interact(move |conn| {
+                let count = scheme::favorites::table
+                    .filter(scheme::favorites::owner_id.eq(&q.owner_id))
+                    .count()
+                    .get_result::<i64>(conn)?;
+
+                let mut myq = scheme::favorites::table
+                    .filter(scheme::favorites::owner_id.eq(&q.owner_id))
+                    .order(scheme::favorites::id.desc())
+                    .select(scheme::favorites::recipe_id)
+                    .into_boxed();
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                QueryResult::Ok((count, myq.load::<String>(conn)?))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: favorites_resp,
+        })
+    }
+
+    /// Which of `recipe_ids` the owner saved.
+    pub async fn saved(
+        &self,
+        owner_id: String,
+        recipe_ids: Vec<String>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let favorites_resp = conn
+            .interact(move |conn| {
+                scheme::favorites::table
+                    .filter(scheme::favorites::owner_id.eq(owner_id))
+                    .filter(scheme::favorites::recipe_id.eq_any(recipe_ids))
+                    .select(scheme::favorites::recipe_id)
+                    .load::<String>(conn)
+            })
+            .await??;
+
+        Ok(favorites_resp)
+    }
+
+    /// How often each recipe is saved, by `owner_id` or by anyone when unset.
+    /// Most saved first.
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let counts_resp = conn
+            .interact(move |conn| {
+                let mut myq = scheme::favorites::table
+                    .group_by(scheme::favorites::recipe_id)
+                    .select((scheme::favorites::recipe_id, diesel::dsl::count_star()))
+                    .into_boxed();
+
+                if let Some(owner_id) = owner_id {
+                    myq = myq.filter(scheme::favorites::owner_id.eq(owner_id));
+                }
+
+                myq.order((
+                    diesel::dsl::count_star().desc(),
+                    scheme::favorites::recipe_id,
+                ))
+                .load::<(String, i64)>(conn)
+            })
+            .await??;
+
+        Ok(counts_resp)
+    }
+}
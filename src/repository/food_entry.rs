@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::diary as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct FoodEntryRepository {
+    pool: Arc<Pool>,
+}
+
+impl FoodEntryRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        FoodEntryRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::LogFoodCommand,
+    ) -> Result<app_model::FoodEntry, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let entry_resp = conn
+            .interact(move |conn| {
+                diesel::insert_into(scheme::food_entries::table)
+                    .values(db_model::CreateFoodEntry::new(
+                        Uuid::new_v4().to_string(),
+                        item,
+                    ))
+                    .returning(db_model::FoodEntry::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        entry_resp.try_into()
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteFoodEntryCommand,
+    ) -> Result<app_model::FoodEntry, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let entry_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::food_entries::table)
+                    .filter(scheme::food_entries::uuid.eq(q.id))
+                    .filter(scheme::food_entries::owner_id.eq(q.owner_id))
+                    .returning(db_model::FoodEntry::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        entry_resp.try_into()
+    }
+
+    /// Entries of the owner eaten within `from`..=`to`, in logging order.
+    pub async fn list(
+        &self,
+        owner_id: String,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<app_model::FoodEntry>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let entries_resp = conn
+            .interact(move |conn| {
+                scheme::food_entries::table
+                    .filter(scheme::food_entries::owner_id.eq(owner_id))
+                    .filter(scheme::food_entries::eaten_on.between(from, to))
+                    .order(scheme::food_entries::id.asc())
+                    .select(db_model::FoodEntry::as_select())
+                    .load(conn)
+            })
+            .await??;
+
+        entries_resp
+            .into_iter()
+            .map(app_model::FoodEntry::try_from)
+            .collect()
+    }
+}
@@ -0,0 +1,207 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::upsert::excluded, pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{ingredient as app_model, SearchResult};
+
+use super::{like_pattern, model as db_model, scheme};
+
+pub struct IngredientRepository {
+    pool: Arc<Pool>,
+}
+
+/// Rows per INSERT statement, keeps imports below the bind parameter limit.
+const IMPORT_CHUNK_SIZE: usize = 1000;
+
+fn filtered(q: &app_model::IngredientSearchQuery) -> scheme::ingredients::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::ingredients::table.into_boxed();
+
+    if let Some(name) = q.name.as_deref() {
+        myq = myq.filter(scheme::ingredients::name.ilike(like_pattern(name)));
+    }
+
+    myq
+}
+
+impl IngredientRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        IngredientRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateIngredientCommand,
+    ) -> Result<app_model::Ingredient, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let ingredient_resp = conn
+            .interact(move |conn| {
+                let mut new_ingredient: db_model::CreateIngredient = item.into();
+                new_ingredient.uuid = Uuid::new_v4().to_string();
+
+                diesel::insert_into(scheme::ingredients::table)
+                    .values(new_ingredient)
+                    .returning(db_model::Ingredient::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(ingredient_resp.into())
+    }
+
+    /// Inserts or updates the nutrition data of the given ingredients, keyed
+    /// by name. Returns the number of affected rows.
+    pub async fn import(
+        &self,
+        items: Vec<app_model::ImportIngredientCommand>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let affected = conn
+            .interact(move |conn| {
+                let rows: Vec<db_model::CreateIngredient> = items
+                    .into_iter()
+                    .map(|item| {
+                        let mut row: db_model::CreateIngredient = item.into();
+                        row.uuid = Uuid::new_v4().to_string();
+                        row
+                    })
+                    .collect();
+
+                conn.transaction(|conn| {
+                    let mut affected = 0;
+
+                    for chunk in rows.chunks(IMPORT_CHUNK_SIZE) {
+                        affected += diesel::insert_into(scheme::ingredients::table)
+                            .values(chunk)
+                            .on_conflict(scheme::ingredients::name)
+                            .do_update()
+                            .set((
+                                scheme::ingredients::proteins
+                                    .eq(excluded(scheme::ingredients::proteins)),
+                                scheme::ingredients::fats.eq(excluded(scheme::ingredients::fats)),
+                                scheme::ingredients::saturated_fat
+                                    .eq(excluded(scheme::ingredients::saturated_fat)),
+                                scheme::ingredients::sugars
+                                    .eq(excluded(scheme::ingredients::sugars)),
+                                scheme::ingredients::sodium
+                                    .eq(excluded(scheme::ingredients::sodium)),
+                                scheme::ingredients::cholesterol
+                                    .eq(excluded(scheme::ingredients::cholesterol)),
+                                scheme::ingredients::carbohydrates
+                                    .eq(excluded(scheme::ingredients::carbohydrates)),
+                                scheme::ingredients::fiber.eq(excluded(scheme::ingredients::fiber)),
+                                scheme::ingredients::kcal.eq(excluded(scheme::ingredients::kcal)),
+                                scheme::ingredients::grams_per_piece
+                                    .eq(excluded(scheme::ingredients::grams_per_piece)),
+                                scheme::ingredients::density
+                                    .eq(excluded(scheme::ingredients::density)),
+                            ))
+                            .execute(conn)?;
+                    }
+
+                    QueryResult::Ok(affected)
+                })
+            })
+            .await??;
+
+        Ok(affected)
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::IngredientQuery,
+    ) -> Result<app_model::Ingredient, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let ingredient_resp = conn
+            .interact(|conn| {
+                scheme::ingredients::table
+                    .filter(scheme::ingredients::uuid.eq(q.id))
+                    .limit(1)
+                    .select(db_model::Ingredient::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(ingredient_resp.into())
+    }
+
+    pub async fn update(
+        &self,
+        q: app_model::UpdateIngredientCommand,
+    ) -> Result<app_model::Ingredient, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let ingredient_resp = conn
+            .interact(|conn| {
+                let ingredient_id = q.id.clone();
+                let ingredient_update: db_model::UpdateIngredient = q.into();
+
+                diesel::update(scheme::ingredients::table)
+                    .filter(scheme::ingredients::uuid.eq(&ingredient_id))
+                    .set(ingredient_update)
+                    .returning(db_model::Ingredient::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(ingredient_resp.into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteIngredientCommand,
+    ) -> Result<app_model::Ingredient, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let ingredient_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::ingredients::table)
+                    .filter(scheme::ingredients::uuid.eq(q.id))
+                    .returning(db_model::Ingredient::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(ingredient_resp.into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::IngredientSearchQuery,
+    ) -> Result<SearchResult<app_model::Ingredient>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, ingredient_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q).order(scheme::ingredients::name.asc());
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let items = myq
+                    .select(db_model::Ingredient::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, items))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: ingredient_resp
+                .into_iter()
+                .map(|item| item.into())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,263 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{meal_plan as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct MealPlanRepository {
+    pool: Arc<Pool>,
+}
+
+fn filtered(q: &app_model::MealPlanSearchQuery) -> scheme::meal_plans::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::meal_plans::table
+        .filter(scheme::meal_plans::owner_id.eq(q.owner_id.clone()))
+        .into_boxed();
+
+    if let Some(from) = q.from {
+        myq = myq.filter(scheme::meal_plans::end_date.ge(from));
+    }
+
+    if let Some(to) = q.to {
+        myq = myq.filter(scheme::meal_plans::start_date.le(to));
+    }
+
+    myq
+}
+
+fn insert_slots(
+    conn: &mut PgConnection,
+    meal_plan_id: i32,
+    slots: Vec<app_model::MealSlot>,
+) -> QueryResult<usize> {
+    let rows: Vec<db_model::CreateMealSlot> = slots
+        .into_iter()
+        .map(|slot| db_model::CreateMealSlot::new(meal_plan_id, slot))
+        .collect();
+
+    diesel::insert_into(scheme::meal_plan_slots::table)
+        .values(rows)
+        .execute(conn)
+}
+
+fn load_slots(
+    conn: &mut PgConnection,
+    plans: Vec<db_model::MealPlan>,
+) -> QueryResult<Vec<app_model::MealPlan>> {
+    let slots = db_model::MealSlot::belonging_to(&plans)
+        .order((scheme::meal_plan_slots::date, scheme::meal_plan_slots::id))
+        .select(db_model::MealSlot::as_select())
+        .load(conn)?;
+
+    Ok(slots
+        .grouped_by(&plans)
+        .into_iter()
+        .zip(plans)
+        .map(|(slots, plan)| (plan, slots).into())
+        .collect())
+}
+
+impl MealPlanRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        MealPlanRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateMealPlanCommand,
+    ) -> Result<app_model::MealPlan, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let meal_plan_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let mut new_meal_plan: db_model::CreateMealPlan = (&item).into();
+                    new_meal_plan.uuid = Uuid::new_v4().to_string();
+
+                    let plan = diesel::insert_into(scheme::meal_plans::table)
+                        .values(new_meal_plan)
+                        .returning(db_model::MealPlan::as_returning())
+                        .get_result(conn)?;
+
+                    insert_slots(conn, plan.id, item.slots)?;
+
+                    load_slots(conn, vec![plan])
+                })
+            })
+            .await??;
+
+        meal_plan_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "meal plan was not created".into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::MealPlanQuery,
+    ) -> Result<app_model::MealPlan, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let meal_plan_resp = conn
+            .interact(|conn| {
+                let plan = scheme::meal_plans::table
+                    .filter(scheme::meal_plans::uuid.eq(q.id))
+                    .filter(scheme::meal_plans::owner_id.eq(q.owner_id))
+                    .limit(1)
+                    .select(db_model::MealPlan::as_select())
+                    .get_result(conn)?;
+
+                load_slots(conn, vec![plan])
+            })
+            .await??;
+
+        meal_plan_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "meal plan not found".into())
+    }
+
+    pub async fn update(
+        &self,
+        q: app_model::UpdateMealPlanCommand,
+    ) -> Result<app_model::MealPlan, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let meal_plan_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let meal_plan_update: db_model::UpdateMealPlan = (&q).into();
+
+                    let plan = diesel::update(scheme::meal_plans::table)
+                        .filter(scheme::meal_plans::uuid.eq(&q.id))
+                        .filter(scheme::meal_plans::owner_id.eq(&q.owner_id))
+                        .set(meal_plan_update)
+                        .returning(db_model::MealPlan::as_returning())
+                        .get_result(conn)?;
+
+                    if let Some(slots) = q.slots {
+                        diesel::delete(scheme::meal_plan_slots::table)
+                            .filter(scheme::meal_plan_slots::meal_plan_id.eq(plan.id))
+                            .execute(conn)?;
+
+                        insert_slots(conn, plan.id, slots)?;
+                    }
+
+                    load_slots(conn, vec![plan])
+                })
+            })
+            .await??;
+
+        meal_plan_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "meal plan not found".into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteMealPlanCommand,
+    ) -> Result<app_model::MealPlan, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let meal_plan_resp = conn
+            .interact(|conn| {
+                conn.transaction(|conn| {
+                    let plan = scheme::meal_plans::table
+                        .filter(scheme::meal_plans::uuid.eq(q.id))
+                        .filter(scheme::meal_plans::owner_id.eq(q.owner_id))
+                        .limit(1)
+                        .select(db_model::MealPlan::as_select())
+                        .get_result(conn)?;
+                    let plan_id = plan.id;
+
+                    let res = load_slots(conn, vec![plan])?;
+
+                    diesel::delete(scheme::meal_plans::table)
+                        .filter(scheme::meal_plans::id.eq(plan_id))
+                        .execute(conn)?;
+
+                    QueryResult::Ok(res)
+                })
+            })
+            .await??;
+
+        meal_plan_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "meal plan not found".into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::MealPlanSearchQuery,
+    ) -> Result<SearchResult<app_model::MealPlan>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, meal_plan_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q).order((
+                    scheme::meal_plans::start_date.desc(),
+                    scheme::meal_plans::id,
+                ));
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let plans = myq
+                    .select(db_model::MealPlan::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, load_slots(conn, plans)?))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: meal_plan_resp,
+        })
+    }
+
+    /// How often each recipe is planned, in the plans of `owner_id` or in
+    /// every plan when unset. Most planned first.
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let counts_resp = conn
+            .interact(move |conn| {
+                let mut myq = scheme::meal_plan_slots::table
+                    .inner_join(scheme::meal_plans::table)
+                    .group_by(scheme::meal_plan_slots::recipe_id)
+                    .select((
+                        scheme::meal_plan_slots::recipe_id,
+                        diesel::dsl::count_star(),
+                    ))
+                    .into_boxed();
+
+                if let Some(owner_id) = owner_id {
+                    myq = myq.filter(scheme::meal_plans::owner_id.eq(owner_id));
+                }
+
+                myq.order((
+                    diesel::dsl::count_star().desc(),
+                    scheme::meal_plan_slots::recipe_id,
+                ))
+                .load::<(String, i64)>(conn)
+            })
+            .await??;
+
+        Ok(counts_resp)
+    }
+}
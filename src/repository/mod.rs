@@ -1,10 +1,44 @@
+pub(crate) mod allergen;
+pub(crate) mod api_key;
 pub(crate) mod category;
+pub(crate) mod collection;
+pub(crate) mod cooking_log;
+pub(crate) mod favorite;
+pub(crate) mod food_entry;
+pub(crate) mod ingredient;
+pub(crate) mod meal_plan;
 mod model;
+pub(crate) mod nutrition_goal;
+pub(crate) mod pantry;
+pub(crate) mod preference;
 pub(crate) mod recipe;
+pub(crate) mod review;
+pub(crate) mod revision;
 mod scheme;
+pub(crate) mod session;
+pub(crate) mod shopping_list;
+pub(crate) mod substitution;
+pub(crate) mod user;
 
+pub use allergen::*;
+pub use api_key::*;
 pub use category::*;
+pub use collection::*;
+pub use cooking_log::*;
+pub use favorite::*;
+pub use food_entry::*;
+pub use ingredient::*;
+pub use meal_plan::*;
+pub use nutrition_goal::*;
+pub use pantry::*;
+pub use preference::*;
 pub use recipe::*;
+pub use review::*;
+pub use revision::*;
+pub use session::*;
+pub use shopping_list::*;
+pub use substitution::*;
+pub use user::*;
 
 use deadpool_diesel::postgres::Pool;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
@@ -27,3 +61,34 @@ pub async fn connect(connection_url: String) -> Pool {
 
     pool
 }
+
+/// Whether a storage error means the requested row doesn't exist.
+pub fn is_not_found(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<diesel::result::Error>(),
+        Some(diesel::result::Error::NotFound)
+    )
+}
+
+/// Whether a storage error is a violated unique constraint, e.g. a slug
+/// taken by another row.
+pub fn is_unique_violation(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<diesel::result::Error>(),
+        Some(diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            _
+        ))
+    )
+}
+
+/// Builds a case-insensitive substring pattern, escaping LIKE wildcards so
+/// user input is matched literally.
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    format!("%{}%", escaped)
+}
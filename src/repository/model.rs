@@ -1,4 +1,7 @@
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, error::Error};
 
 use crate::model;
 
@@ -9,13 +12,22 @@ pub struct Category {
     pub id: i32,
     pub uuid: String,
     pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub image: String,
+    pub sort_order: i32,
 }
 
-impl Into<model::Category> for Category {
-    fn into(self) -> model::Category {
+impl From<Category> for model::Category {
+    fn from(value: Category) -> Self {
         model::Category {
-            id: self.uuid,
-            name: self.name,
+            id: value.uuid,
+            slug: value.slug,
+            name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
+            recipe_count: 0,
         }
     }
 }
@@ -25,14 +37,22 @@ impl Into<model::Category> for Category {
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct CreateCategory {
     pub uuid: String,
+    pub slug: String,
     pub name: String,
+    pub description: String,
+    pub image: String,
+    pub sort_order: i32,
 }
 
 impl From<model::CreateCategoryCommand> for CreateCategory {
     fn from(value: model::CreateCategoryCommand) -> Self {
         Self {
             uuid: String::default(),
+            slug: value.slug,
             name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
         }
     }
 }
@@ -41,13 +61,21 @@ impl From<model::CreateCategoryCommand> for CreateCategory {
 #[diesel(table_name = super::scheme::categories)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct UpdateCategory {
+    pub slug: Option<String>,
     pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub sort_order: Option<i32>,
 }
 
 impl From<model::UpdateCategoryCommand> for UpdateCategory {
     fn from(value: model::UpdateCategoryCommand) -> Self {
         Self {
-            name: Some(value.name),
+            slug: value.slug,
+            name: value.name,
+            description: value.description,
+            image: value.image,
+            sort_order: value.sort_order,
         }
     }
 }
@@ -68,44 +96,71 @@ pub struct Recipe {
     pub category_id: String,
     pub ingredients: Vec<Option<String>>,
     pub guideline: String,
-    pub nutrients_proteins: i64,
-    pub nutrients_fats: f64,
-    pub nutrients_carbohydrates: f64,
-    pub nutrients_fiber: i64,
-    pub nutrients_kcal: i64,
-}
-
-impl Into<model::Recipe> for Recipe {
-    fn into(self) -> model::Recipe {
-        model::Recipe {
-            id: self.uuid,
-            cover: self.cover,
-            title: self.title,
-            description: self.description,
-            time_to_cook: self.time_to_cook,
-            difficulty: self.difficulty,
-            servings: self.servings,
+    pub allergens: Vec<Option<String>>,
+    pub diets: Vec<Option<String>>,
+    pub nutrients_computed: bool,
+    pub nutrients: serde_json::Value,
+    pub author_id: Option<String>,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
+    pub status: String,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<Recipe> for model::Recipe {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Recipe) -> Result<Self, Self::Error> {
+        let nutrients = parse_nutrients(value.nutrients, &format!("recipe {}", value.uuid))?;
+
+        Ok(model::Recipe {
+            id: value.uuid,
+            cover: value.cover,
+            title: value.title,
+            description: value.description,
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty,
+            servings: value.servings,
             category: model::Category {
-                id: self.category_id,
+                id: value.category_id,
                 ..model::Category::default()
             },
-            ingredients: self
+            ingredients: value
                 .ingredients
-                .iter()
-                .map(|v| match v.to_owned() {
-                    Some(v) => v,
-                    _ => String::from(""),
+                .into_iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+            allergens: value
+                .allergens
+                .into_iter()
+                .flatten()
+                .map(|code| model::Allergen {
+                    code,
+                    ..model::Allergen::default()
                 })
                 .collect(),
-            nutrients: model::Nutrients {
-                proteins: self.nutrients_proteins,
-                fats: self.nutrients_fats,
-                carbohydrates: self.nutrients_carbohydrates,
-                fiber: self.nutrients_fiber,
-                kcal: self.nutrients_kcal,
-            },
-            guideline: self.guideline,
-        }
+            diets: value
+                .diets
+                .into_iter()
+                .flatten()
+                .filter_map(|code| model::Diet::from_code(&code))
+                .collect(),
+            nutrients,
+            nutrients_computed: value.nutrients_computed,
+            guideline: value.guideline,
+            author_id: value.author_id,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            is_favorite: false,
+            rating_average: value.rating_average,
+            rating_count: value.rating_count,
+            status: model::RecipeStatus::from_code(&value.status).unwrap_or_default(),
+            deleted_at: value.deleted_at,
+        })
     }
 }
 
@@ -123,11 +178,15 @@ pub struct CreateRecipe {
     pub category_id: String,
     pub ingredients: Vec<Option<String>>,
     pub guideline: String,
-    pub nutrients_proteins: i64,
-    pub nutrients_fats: f64,
-    pub nutrients_carbohydrates: f64,
-    pub nutrients_fiber: i64,
-    pub nutrients_kcal: i64,
+    pub allergens: Vec<Option<String>>,
+    pub diets: Vec<Option<String>>,
+    pub nutrients_computed: bool,
+    pub nutrients: serde_json::Value,
+    pub author_id: Option<String>,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    pub status: String,
 }
 
 impl From<model::CreateRecipeCommand> for CreateRecipe {
@@ -141,17 +200,17 @@ impl From<model::CreateRecipeCommand> for CreateRecipe {
             difficulty: value.difficulty,
             servings: value.servings,
             category_id: value.category,
-            ingredients: value
-                .ingredients
-                .iter()
-                .map(|v| Some(v.to_owned()))
-                .collect(),
+            ingredients: value.ingredients.into_iter().map(Some).collect(),
             guideline: value.guideline,
-            nutrients_proteins: value.nutrients.proteins,
-            nutrients_fats: value.nutrients.fats,
-            nutrients_carbohydrates: value.nutrients.carbohydrates,
-            nutrients_fiber: value.nutrients.fiber,
-            nutrients_kcal: value.nutrients.kcal,
+            nutrients: NutrientsDocument::from(value.nutrients).into(),
+            allergens: value.allergens.into_iter().map(Some).collect(),
+            diets: diet_codes(&value.diets),
+            nutrients_computed: value.compute_nutrients,
+            author_id: Some(value.actor.user_id),
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            status: model::RecipeStatus::Draft.code().to_string(),
         }
     }
 }
@@ -169,11 +228,13 @@ pub struct UpdateRecipe {
     pub category_id: Option<String>,
     pub ingredients: Option<Vec<Option<String>>>,
     pub guideline: Option<String>,
-    pub nutrients_proteins: Option<i64>,
-    pub nutrients_fats: Option<f64>,
-    pub nutrients_carbohydrates: Option<f64>,
-    pub nutrients_fiber: Option<i64>,
-    pub nutrients_kcal: Option<i64>,
+    pub allergens: Option<Vec<Option<String>>>,
+    pub diets: Option<Vec<Option<String>>>,
+    pub nutrients_computed: Option<bool>,
+    pub nutrients: Option<serde_json::Value>,
+    pub source_url: Option<Option<String>>,
+    pub attribution: Option<String>,
+    pub license: Option<String>,
 }
 
 impl From<model::UpdateRecipeCommand> for UpdateRecipe {
@@ -186,16 +247,1308 @@ impl From<model::UpdateRecipeCommand> for UpdateRecipe {
             difficulty: value.difficulty,
             servings: value.servings,
             category_id: value.category,
-            ingredients: match value.ingredients {
-                Some(items) => Some(items.iter().map(|v| Some(v.to_owned())).collect()),
-                None => None,
-            },
+            ingredients: value
+                .ingredients
+                .map(|items| items.into_iter().map(Some).collect()),
             guideline: value.guideline,
-            nutrients_proteins: value.nutrients.proteins,
-            nutrients_fats: value.nutrients.fats,
-            nutrients_carbohydrates: value.nutrients.carbohydrates,
-            nutrients_fiber: value.nutrients.fiber,
-            nutrients_kcal: value.nutrients.kcal,
+            nutrients: complete(&value.nutrients).map(|n| NutrientsDocument::from(n).into()),
+            allergens: value
+                .allergens
+                .map(|items| items.into_iter().map(Some).collect()),
+            diets: value.diets.as_deref().map(diet_codes),
+            nutrients_computed: value.compute_nutrients,
+            source_url: value
+                .source_url
+                .map(|url| Some(url).filter(|url| !url.is_empty())),
+            attribution: value.attribution,
+            license: value.license,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::allergens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Allergen {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub custom: bool,
+}
+
+impl From<Allergen> for model::Allergen {
+    fn from(value: Allergen) -> Self {
+        model::Allergen {
+            code: value.code,
+            name: value.name,
+            custom: value.custom,
+        }
+    }
+}
+
+#[derive(Default, Insertable)]
+#[diesel(table_name = super::scheme::allergens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateAllergen {
+    pub code: String,
+    pub name: String,
+    pub custom: bool,
+}
+
+impl From<model::CreateAllergenCommand> for CreateAllergen {
+    fn from(value: model::CreateAllergenCommand) -> Self {
+        Self {
+            code: value.code,
+            name: value.name,
+            custom: true,
+        }
+    }
+}
+
+pub(crate) fn diet_codes(diets: &[model::Diet]) -> Vec<Option<String>> {
+    diets
+        .iter()
+        .map(|diet| Some(diet.code().to_string()))
+        .collect()
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::ingredients)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Ingredient {
+    pub id: i32,
+    pub uuid: String,
+    pub name: String,
+    pub aliases: Vec<Option<String>>,
+    pub animal: bool,
+    pub meat: bool,
+    pub gluten: bool,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+    pub fiber: Option<f64>,
+    pub kcal: Option<f64>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub saturated_fat: Option<f64>,
+    pub sugars: Option<f64>,
+    pub sodium: Option<f64>,
+    pub cholesterol: Option<f64>,
+    pub aisle: String,
+}
+
+impl From<Ingredient> for model::Ingredient {
+    fn from(value: Ingredient) -> Self {
+        model::Ingredient {
+            id: value.uuid,
+            name: value.name,
+            aliases: value.aliases.into_iter().flatten().collect(),
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            nutrients: value.kcal.map(|kcal| model::IngredientNutrients {
+                proteins: value.proteins.unwrap_or_default(),
+                fats: value.fats.unwrap_or_default(),
+                saturated_fat: value.saturated_fat.unwrap_or_default(),
+                carbohydrates: value.carbohydrates.unwrap_or_default(),
+                sugars: value.sugars.unwrap_or_default(),
+                fiber: value.fiber.unwrap_or_default(),
+                sodium: value.sodium.unwrap_or_default(),
+                cholesterol: value.cholesterol.unwrap_or_default(),
+                kcal,
+            }),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            aisle: value.aisle,
         }
     }
 }
+
+#[derive(Default, Insertable)]
+#[diesel(table_name = super::scheme::ingredients)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateIngredient {
+    pub uuid: String,
+    pub name: String,
+    pub aliases: Vec<Option<String>>,
+    pub animal: bool,
+    pub meat: bool,
+    pub gluten: bool,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+    pub fiber: Option<f64>,
+    pub kcal: Option<f64>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub saturated_fat: Option<f64>,
+    pub sugars: Option<f64>,
+    pub sodium: Option<f64>,
+    pub cholesterol: Option<f64>,
+    pub aisle: Option<String>,
+}
+
+impl From<model::CreateIngredientCommand> for CreateIngredient {
+    fn from(value: model::CreateIngredientCommand) -> Self {
+        Self {
+            uuid: String::default(),
+            name: value.name,
+            aliases: value.aliases.into_iter().map(Some).collect(),
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            proteins: value.nutrients.map(|n| n.proteins),
+            fats: value.nutrients.map(|n| n.fats),
+            carbohydrates: value.nutrients.map(|n| n.carbohydrates),
+            fiber: value.nutrients.map(|n| n.fiber),
+            kcal: value.nutrients.map(|n| n.kcal),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            saturated_fat: value.nutrients.map(|n| n.saturated_fat),
+            sugars: value.nutrients.map(|n| n.sugars),
+            sodium: value.nutrients.map(|n| n.sodium),
+            cholesterol: value.nutrients.map(|n| n.cholesterol),
+            aisle: value.aisle,
+        }
+    }
+}
+
+impl From<model::ImportIngredientCommand> for CreateIngredient {
+    fn from(value: model::ImportIngredientCommand) -> Self {
+        Self {
+            uuid: String::default(),
+            name: value.name,
+            proteins: Some(value.nutrients.proteins),
+            fats: Some(value.nutrients.fats),
+            carbohydrates: Some(value.nutrients.carbohydrates),
+            fiber: Some(value.nutrients.fiber),
+            kcal: Some(value.nutrients.kcal),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            saturated_fat: Some(value.nutrients.saturated_fat),
+            sugars: Some(value.nutrients.sugars),
+            sodium: Some(value.nutrients.sodium),
+            cholesterol: Some(value.nutrients.cholesterol),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::ingredients)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdateIngredient {
+    pub name: Option<String>,
+    pub aliases: Option<Vec<Option<String>>>,
+    pub animal: Option<bool>,
+    pub meat: Option<bool>,
+    pub gluten: Option<bool>,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+    pub fiber: Option<f64>,
+    pub kcal: Option<f64>,
+    pub grams_per_piece: Option<f64>,
+    pub density: Option<f64>,
+    pub saturated_fat: Option<f64>,
+    pub sugars: Option<f64>,
+    pub sodium: Option<f64>,
+    pub cholesterol: Option<f64>,
+    pub aisle: Option<String>,
+}
+
+impl From<model::UpdateIngredientCommand> for UpdateIngredient {
+    fn from(value: model::UpdateIngredientCommand) -> Self {
+        Self {
+            name: value.name,
+            aliases: value
+                .aliases
+                .map(|items| items.into_iter().map(Some).collect()),
+            animal: value.animal,
+            meat: value.meat,
+            gluten: value.gluten,
+            proteins: value.nutrients.map(|n| n.proteins),
+            fats: value.nutrients.map(|n| n.fats),
+            carbohydrates: value.nutrients.map(|n| n.carbohydrates),
+            fiber: value.nutrients.map(|n| n.fiber),
+            kcal: value.nutrients.map(|n| n.kcal),
+            grams_per_piece: value.grams_per_piece,
+            density: value.density,
+            saturated_fat: value.nutrients.map(|n| n.saturated_fat),
+            sugars: value.nutrients.map(|n| n.sugars),
+            sodium: value.nutrients.map(|n| n.sodium),
+            cholesterol: value.nutrients.map(|n| n.cholesterol),
+            aisle: value.aisle,
+        }
+    }
+}
+
+/// Stored form of recipe nutrients, always per serving.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NutrientsDocument {
+    pub serving_weight: Option<f64>,
+    pub kcal: f64,
+    pub proteins: f64,
+    pub fats: f64,
+    pub saturated_fat: f64,
+    pub carbohydrates: f64,
+    pub sugars: f64,
+    pub fiber: f64,
+    pub sodium: f64,
+    pub cholesterol: f64,
+    pub micronutrients: BTreeMap<String, f64>,
+}
+
+impl From<model::Nutrients> for NutrientsDocument {
+    fn from(value: model::Nutrients) -> Self {
+        Self {
+            serving_weight: value.serving_weight,
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value.sodium,
+            cholesterol: value.cholesterol,
+            micronutrients: value.micronutrients,
+        }
+    }
+}
+
+impl From<NutrientsDocument> for model::Nutrients {
+    fn from(value: NutrientsDocument) -> Self {
+        model::Nutrients {
+            basis: model::NutrientBasis::PerServing,
+            serving_weight: value.serving_weight,
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            saturated_fat: value.saturated_fat,
+            carbohydrates: value.carbohydrates,
+            sugars: value.sugars,
+            fiber: value.fiber,
+            sodium: value.sodium,
+            cholesterol: value.cholesterol,
+            micronutrients: value.micronutrients,
+        }
+    }
+}
+
+/// Reads a stored nutrients document. Fields missing from older documents
+/// are zero, a document of the wrong shape is an error naming `owner`.
+fn parse_nutrients(
+    value: serde_json::Value,
+    owner: &str,
+) -> Result<model::Nutrients, Box<dyn Error>> {
+    let document: NutrientsDocument = serde_json::from_value(value)
+        .map_err(|err| format!("malformed nutrients of {}: {}", owner, err))?;
+
+    Ok(document.into())
+}
+
+impl From<NutrientsDocument> for serde_json::Value {
+    fn from(value: NutrientsDocument) -> Self {
+        serde_json::to_value(value).unwrap_or_default()
+    }
+}
+
+/// The nutrients document is replaced as a whole. The service merges partial
+/// updates with the stored profile, so a change always carries every value;
+/// anything less leaves the stored document untouched.
+fn complete(value: &model::UpdateNutrients) -> Option<model::Nutrients> {
+    Some(model::Nutrients {
+        basis: value.basis,
+        serving_weight: value.serving_weight,
+        kcal: value.kcal?,
+        proteins: value.proteins?,
+        fats: value.fats?,
+        saturated_fat: value.saturated_fat?,
+        carbohydrates: value.carbohydrates?,
+        sugars: value.sugars?,
+        fiber: value.fiber?,
+        sodium: value.sodium?,
+        cholesterol: value.cholesterol?,
+        micronutrients: value.micronutrients.clone()?,
+    })
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::meal_plans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MealPlan {
+    pub id: i32,
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl From<(MealPlan, Vec<MealSlot>)> for model::MealPlan {
+    fn from((plan, slots): (MealPlan, Vec<MealSlot>)) -> Self {
+        model::MealPlan {
+            id: plan.uuid,
+            owner_id: plan.owner_id,
+            name: plan.name,
+            start_date: plan.start_date,
+            end_date: plan.end_date,
+            slots: slots.into_iter().map(model::MealSlot::from).collect(),
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::meal_plans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateMealPlan {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl From<&model::CreateMealPlanCommand> for CreateMealPlan {
+    fn from(value: &model::CreateMealPlanCommand) -> Self {
+        Self {
+            uuid: String::default(),
+            owner_id: value.owner_id.clone(),
+            name: value.name.clone(),
+            start_date: value.start_date,
+            end_date: value.end_date,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::meal_plans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdateMealPlan {
+    pub name: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<&model::UpdateMealPlanCommand> for UpdateMealPlan {
+    fn from(value: &model::UpdateMealPlanCommand) -> Self {
+        Self {
+            name: value.name.clone(),
+            start_date: value.start_date,
+            end_date: value.end_date,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = super::scheme::meal_plan_slots)]
+#[diesel(belongs_to(MealPlan))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MealSlot {
+    pub id: i32,
+    pub meal_plan_id: i32,
+    pub date: NaiveDate,
+    pub meal: String,
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
+
+impl From<MealSlot> for model::MealSlot {
+    fn from(value: MealSlot) -> Self {
+        model::MealSlot {
+            date: value.date,
+            meal: model::Meal::from_code(&value.meal).unwrap_or_default(),
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::meal_plan_slots)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateMealSlot {
+    pub meal_plan_id: i32,
+    pub date: NaiveDate,
+    pub meal: String,
+    pub recipe_id: String,
+    pub servings: Option<i64>,
+}
+
+impl CreateMealSlot {
+    pub fn new(meal_plan_id: i32, value: model::MealSlot) -> Self {
+        Self {
+            meal_plan_id,
+            date: value.date,
+            meal: value.meal.code().to_string(),
+            recipe_id: value.recipe_id,
+            servings: value.servings,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::shopping_lists)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ShoppingList {
+    pub id: i32,
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub meal_plan_id: Option<String>,
+    pub missing_recipes: Vec<Option<String>>,
+}
+
+impl From<(ShoppingList, Vec<ShoppingListItem>)> for model::ShoppingList {
+    fn from((list, items): (ShoppingList, Vec<ShoppingListItem>)) -> Self {
+        model::ShoppingList {
+            id: list.uuid,
+            owner_id: list.owner_id,
+            name: list.name,
+            meal_plan_id: list.meal_plan_id,
+            items: items
+                .into_iter()
+                .map(model::ShoppingListItem::from)
+                .collect(),
+            missing_recipes: list.missing_recipes.into_iter().flatten().collect(),
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::shopping_lists)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateShoppingList {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub meal_plan_id: Option<String>,
+    pub missing_recipes: Vec<Option<String>>,
+}
+
+impl From<&model::ShoppingList> for CreateShoppingList {
+    fn from(value: &model::ShoppingList) -> Self {
+        Self {
+            uuid: String::default(),
+            owner_id: value.owner_id.clone(),
+            name: value.name.clone(),
+            meal_plan_id: value.meal_plan_id.clone(),
+            missing_recipes: value.missing_recipes.iter().cloned().map(Some).collect(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = super::scheme::shopping_list_items)]
+#[diesel(belongs_to(ShoppingList))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ShoppingListItem {
+    pub id: i32,
+    pub uuid: String,
+    pub shopping_list_id: i32,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub aisle: String,
+    pub checked: bool,
+}
+
+impl From<ShoppingListItem> for model::ShoppingListItem {
+    fn from(value: ShoppingListItem) -> Self {
+        model::ShoppingListItem {
+            id: value.uuid,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.as_deref().and_then(model::Unit::from_code),
+            aisle: value.aisle,
+            checked: value.checked,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::shopping_list_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateShoppingListItem {
+    pub uuid: String,
+    pub shopping_list_id: i32,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub aisle: String,
+    pub checked: bool,
+}
+
+impl CreateShoppingListItem {
+    pub fn new(shopping_list_id: i32, value: model::ShoppingListItem) -> Self {
+        Self {
+            uuid: String::default(),
+            shopping_list_id,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            aisle: value.aisle,
+            checked: value.checked,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::pantry_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PantryItem {
+    pub id: i32,
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl From<PantryItem> for model::PantryItem {
+    fn from(value: PantryItem) -> Self {
+        model::PantryItem {
+            id: value.uuid,
+            owner_id: value.owner_id,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.as_deref().and_then(model::Unit::from_code),
+            expires_on: value.expires_on,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::pantry_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreatePantryItem {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl From<model::CreatePantryItemCommand> for CreatePantryItem {
+    fn from(value: model::CreatePantryItemCommand) -> Self {
+        Self {
+            uuid: String::default(),
+            owner_id: value.owner_id,
+            name: value.name,
+            quantity: value.quantity,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            expires_on: value.expires_on,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::pantry_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdatePantryItem {
+    pub name: Option<String>,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<&model::UpdatePantryItemCommand> for UpdatePantryItem {
+    fn from(value: &model::UpdatePantryItemCommand) -> Self {
+        Self {
+            name: value.name.clone(),
+            quantity: value.quantity,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            expires_on: value.expires_on,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::user_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserPreferences {
+    pub owner_id: String,
+    pub diets: Vec<Option<String>>,
+    pub exclude_allergens: Vec<Option<String>>,
+    pub categories: Vec<Option<String>>,
+    pub disliked_ingredients: Vec<Option<String>>,
+}
+
+impl From<UserPreferences> for model::UserPreferences {
+    fn from(value: UserPreferences) -> Self {
+        model::UserPreferences {
+            owner_id: value.owner_id,
+            diets: value
+                .diets
+                .into_iter()
+                .flatten()
+                .filter_map(|code| model::Diet::from_code(&code))
+                .collect(),
+            exclude_allergens: value.exclude_allergens.into_iter().flatten().collect(),
+            categories: value.categories.into_iter().flatten().collect(),
+            disliked_ingredients: value.disliked_ingredients.into_iter().flatten().collect(),
+        }
+    }
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = super::scheme::user_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SaveUserPreferences {
+    pub owner_id: String,
+    pub diets: Vec<Option<String>>,
+    pub exclude_allergens: Vec<Option<String>>,
+    pub categories: Vec<Option<String>>,
+    pub disliked_ingredients: Vec<Option<String>>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<model::UserPreferences> for SaveUserPreferences {
+    fn from(value: model::UserPreferences) -> Self {
+        Self {
+            owner_id: value.owner_id,
+            diets: diet_codes(&value.diets),
+            exclude_allergens: value.exclude_allergens.into_iter().map(Some).collect(),
+            categories: value.categories.into_iter().map(Some).collect(),
+            disliked_ingredients: value.disliked_ingredients.into_iter().map(Some).collect(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::substitutions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Substitution {
+    pub uuid: String,
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<String>,
+    pub avoids: Vec<Option<String>>,
+    pub notes: String,
+}
+
+impl From<Substitution> for model::Substitution {
+    fn from(value: Substitution) -> Self {
+        model::Substitution {
+            id: value.uuid,
+            ingredient: value.ingredient,
+            substitute: value.substitute,
+            ratio: value.ratio,
+            unit: value.unit.as_deref().and_then(model::Unit::from_code),
+            avoids: value.avoids.into_iter().flatten().collect(),
+            notes: value.notes,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::substitutions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateSubstitution {
+    pub uuid: String,
+    pub ingredient: String,
+    pub substitute: String,
+    pub ratio: f64,
+    pub unit: Option<String>,
+    pub avoids: Vec<Option<String>>,
+    pub notes: String,
+}
+
+impl From<model::CreateSubstitutionCommand> for CreateSubstitution {
+    fn from(value: model::CreateSubstitutionCommand) -> Self {
+        Self {
+            uuid: String::default(),
+            ingredient: value.ingredient,
+            substitute: value.substitute,
+            ratio: value.ratio,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            avoids: value.avoids.into_iter().map(Some).collect(),
+            notes: value.notes,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::substitutions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdateSubstitution {
+    pub ingredient: Option<String>,
+    pub substitute: Option<String>,
+    pub ratio: Option<f64>,
+    pub unit: Option<String>,
+    pub avoids: Option<Vec<Option<String>>>,
+    pub notes: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<&model::UpdateSubstitutionCommand> for UpdateSubstitution {
+    fn from(value: &model::UpdateSubstitutionCommand) -> Self {
+        Self {
+            ingredient: value.ingredient.clone(),
+            substitute: value.substitute.clone(),
+            ratio: value.ratio,
+            unit: value.unit.map(|unit| unit.code().to_string()),
+            avoids: value
+                .avoids
+                .as_ref()
+                .map(|avoids| avoids.iter().cloned().map(Some).collect()),
+            notes: value.notes.clone(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::users)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct User {
+    pub uuid: String,
+    pub email: String,
+    pub name: String,
+    pub password_hash: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<User> for model::User {
+    fn from(value: User) -> Self {
+        model::User {
+            id: value.uuid,
+            email: value.email,
+            name: value.name,
+            role: model::Role::from_code(&value.role).unwrap_or_default(),
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::users)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateUser {
+    pub uuid: String,
+    pub email: String,
+    pub name: String,
+    pub password_hash: String,
+    pub role: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateSession {
+    pub uuid: String,
+    pub user_id: String,
+    pub refresh_token_hash: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiKey {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<Option<String>>,
+    pub rate_limit: i32,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ApiKey> for model::ApiKey {
+    fn from(value: ApiKey) -> Self {
+        model::ApiKey {
+            id: value.uuid,
+            owner_id: value.owner_id,
+            name: value.name,
+            prefix: value.prefix,
+            scopes: value
+                .scopes
+                .iter()
+                .flatten()
+                .filter_map(|code| model::ApiKeyScope::from_code(code))
+                .collect(),
+            rate_limit: value.rate_limit,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateApiKey {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<Option<String>>,
+    pub rate_limit: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::favorites)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateFavorite {
+    pub owner_id: String,
+    pub recipe_id: String,
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = super::scheme::collections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Collection {
+    pub id: i32,
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub description: String,
+    pub share_token: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<(Collection, Vec<CollectionItem>)> for model::Collection {
+    fn from((collection, items): (Collection, Vec<CollectionItem>)) -> Self {
+        model::Collection {
+            id: collection.uuid,
+            owner_id: collection.owner_id,
+            name: collection.name,
+            description: collection.description,
+            share_token: collection.share_token,
+            items: items.into_iter().map(model::CollectionItem::from).collect(),
+            updated_at: collection.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::collections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateCollection {
+    pub uuid: String,
+    pub owner_id: String,
+    pub name: String,
+    pub description: String,
+    pub share_token: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::collections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdateCollection {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub share_token: Option<Option<String>>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = super::scheme::collection_items)]
+#[diesel(belongs_to(Collection))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CollectionItem {
+    pub id: i32,
+    pub collection_id: i32,
+    pub recipe_id: String,
+    pub position: i32,
+    pub note: String,
+}
+
+impl From<CollectionItem> for model::CollectionItem {
+    fn from(value: CollectionItem) -> Self {
+        model::CollectionItem {
+            recipe_id: value.recipe_id,
+            note: value.note,
+            recipe: None,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::collection_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateCollectionItem {
+    pub collection_id: i32,
+    pub recipe_id: String,
+    pub position: i32,
+    pub note: String,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::reviews)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Review {
+    pub uuid: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub rating: i32,
+    pub body: String,
+    pub flagged: bool,
+    pub flag_reason: String,
+    pub hidden: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Review> for model::Review {
+    fn from(value: Review) -> Self {
+        model::Review {
+            id: value.uuid,
+            recipe_id: value.recipe_id,
+            owner_id: value.owner_id,
+            rating: value.rating,
+            body: value.body,
+            flagged: value.flagged,
+            flag_reason: value.flag_reason,
+            hidden: value.hidden,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::reviews)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateReview {
+    pub uuid: String,
+    pub recipe_id: String,
+    pub owner_id: String,
+    pub rating: i32,
+    pub body: String,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = super::scheme::reviews)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UpdateReview {
+    pub rating: Option<i32>,
+    pub body: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<&model::UpdateReviewCommand> for UpdateReview {
+    fn from(value: &model::UpdateReviewCommand) -> Self {
+        Self {
+            rating: value.rating,
+            body: value.body.clone(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::cooking_logs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CookingLogEntry {
+    pub uuid: String,
+    pub owner_id: String,
+    pub recipe_id: String,
+    pub recipe_title: String,
+    pub cooked_on: NaiveDate,
+    pub servings: f64,
+    pub notes: String,
+    pub photo: Vec<u8>,
+    pub nutrients: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub meal: Option<String>,
+}
+
+impl TryFrom<CookingLogEntry> for model::CookingLogEntry {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: CookingLogEntry) -> Result<Self, Self::Error> {
+        let nutrients = parse_nutrients(value.nutrients, &format!("log entry {}", value.uuid))?;
+
+        Ok(model::CookingLogEntry {
+            id: value.uuid,
+            owner_id: value.owner_id,
+            recipe_id: value.recipe_id,
+            recipe_title: value.recipe_title,
+            cooked_on: value.cooked_on,
+            meal: value.meal.as_deref().and_then(model::Meal::from_code),
+            servings: value.servings,
+            notes: value.notes,
+            photo: value.photo,
+            nutrients,
+            created_at: value.created_at,
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::cooking_logs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateCookingLogEntry {
+    pub uuid: String,
+    pub owner_id: String,
+    pub recipe_id: String,
+    pub recipe_title: String,
+    pub cooked_on: NaiveDate,
+    pub servings: f64,
+    pub notes: String,
+    pub photo: Vec<u8>,
+    pub nutrients: serde_json::Value,
+    pub meal: Option<String>,
+}
+
+impl CreateCookingLogEntry {
+    pub fn new(uuid: String, value: model::LogCookingCommand) -> Self {
+        Self {
+            uuid,
+            owner_id: value.owner_id,
+            recipe_id: value.recipe_id,
+            recipe_title: value.recipe_title,
+            cooked_on: value.cooked_on,
+            servings: value.servings,
+            notes: value.notes,
+            photo: value.photo,
+            nutrients: NutrientsDocument::from(value.nutrients).into(),
+            meal: value.meal.map(|meal| meal.code().to_string()),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::food_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FoodEntry {
+    pub uuid: String,
+    pub owner_id: String,
+    pub eaten_on: NaiveDate,
+    pub meal: Option<String>,
+    pub name: String,
+    pub nutrients: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<FoodEntry> for model::FoodEntry {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: FoodEntry) -> Result<Self, Self::Error> {
+        let nutrients = parse_nutrients(value.nutrients, &format!("food entry {}", value.uuid))?;
+
+        Ok(model::FoodEntry {
+            id: value.uuid,
+            owner_id: value.owner_id,
+            eaten_on: value.eaten_on,
+            meal: value.meal.as_deref().and_then(model::Meal::from_code),
+            name: value.name,
+            nutrients,
+            created_at: value.created_at,
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::food_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateFoodEntry {
+    pub uuid: String,
+    pub owner_id: String,
+    pub eaten_on: NaiveDate,
+    pub meal: Option<String>,
+    pub name: String,
+    pub nutrients: serde_json::Value,
+}
+
+impl CreateFoodEntry {
+    pub fn new(uuid: String, value: model::LogFoodCommand) -> Self {
+        Self {
+            uuid,
+            owner_id: value.owner_id,
+            eaten_on: value.eaten_on,
+            meal: value.meal.map(|meal| meal.code().to_string()),
+            name: value.name,
+            nutrients: NutrientsDocument::from(value.nutrients).into(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::nutrition_goals)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NutritionGoals {
+    pub kcal: f64,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+}
+
+impl From<NutritionGoals> for model::NutritionTargets {
+    fn from(value: NutritionGoals) -> Self {
+        model::NutritionTargets {
+            kcal: value.kcal,
+            proteins: value.proteins,
+            fats: value.fats,
+            carbohydrates: value.carbohydrates,
+        }
+    }
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = super::scheme::nutrition_goals)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+pub struct SetNutritionGoals {
+    pub owner_id: String,
+    pub kcal: f64,
+    pub proteins: Option<f64>,
+    pub fats: Option<f64>,
+    pub carbohydrates: Option<f64>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<model::SetGoalsCommand> for SetNutritionGoals {
+    fn from(value: model::SetGoalsCommand) -> Self {
+        Self {
+            owner_id: value.owner_id,
+            kcal: value.goals.kcal,
+            proteins: value.goals.proteins,
+            fats: value.goals.fats,
+            carbohydrates: value.goals.carbohydrates,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// Stored form of a recipe revision's content.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecipeSnapshotDocument {
+    pub cover: String,
+    pub title: String,
+    pub description: String,
+    pub time_to_cook: i64,
+    pub difficulty: String,
+    pub servings: i64,
+    pub category_id: String,
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
+    pub diets: Vec<String>,
+    pub nutrients: NutrientsDocument,
+    pub nutrients_computed: bool,
+    pub guideline: String,
+    pub source_url: Option<String>,
+    pub attribution: String,
+    pub license: String,
+    pub status: Option<String>,
+}
+
+impl TryFrom<&Recipe> for RecipeSnapshotDocument {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &Recipe) -> Result<Self, Self::Error> {
+        Ok(Self {
+            cover: String::from_utf8_lossy(&value.cover).into_owned(),
+            title: value.title.clone(),
+            description: value.description.clone(),
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty.clone(),
+            servings: value.servings,
+            category_id: value.category_id.clone(),
+            ingredients: value.ingredients.iter().flatten().cloned().collect(),
+            allergens: value.allergens.iter().flatten().cloned().collect(),
+            diets: value.diets.iter().flatten().cloned().collect(),
+            nutrients: serde_json::from_value(value.nutrients.clone())
+                .map_err(|err| format!("malformed nutrients of recipe {}: {}", value.uuid, err))?,
+            nutrients_computed: value.nutrients_computed,
+            guideline: value.guideline.clone(),
+            source_url: value.source_url.clone(),
+            attribution: value.attribution.clone(),
+            license: value.license.clone(),
+            status: Some(value.status.clone()),
+        })
+    }
+}
+
+impl From<RecipeSnapshotDocument> for model::RecipeSnapshot {
+    fn from(value: RecipeSnapshotDocument) -> Self {
+        model::RecipeSnapshot {
+            cover: value.cover.into_bytes(),
+            title: value.title,
+            description: value.description,
+            time_to_cook: value.time_to_cook,
+            difficulty: value.difficulty,
+            servings: value.servings,
+            category_id: value.category_id,
+            ingredients: value.ingredients,
+            allergens: value.allergens,
+            diets: value
+                .diets
+                .iter()
+                .filter_map(|code| model::Diet::from_code(code))
+                .collect(),
+            nutrients: value.nutrients.into(),
+            nutrients_computed: value.nutrients_computed,
+            guideline: value.guideline,
+            source_url: value.source_url,
+            attribution: value.attribution,
+            license: value.license,
+            status: value
+                .status
+                .as_deref()
+                .and_then(model::RecipeStatus::from_code),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = super::scheme::recipe_revisions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RecipeRevision {
+    pub uuid: String,
+    pub recipe_id: String,
+    pub number: i32,
+    pub author_id: Option<String>,
+    pub snapshot: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<RecipeRevision> for model::RecipeRevision {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: RecipeRevision) -> Result<Self, Self::Error> {
+        let snapshot: RecipeSnapshotDocument =
+            serde_json::from_value(value.snapshot).map_err(|err| {
+                format!(
+                    "malformed snapshot of revision {} of recipe {}: {}",
+                    value.number, value.recipe_id, err
+                )
+            })?;
+
+        Ok(model::RecipeRevision {
+            id: value.uuid,
+            recipe_id: value.recipe_id,
+            number: value.number,
+            author_id: value.author_id,
+            created_at: value.created_at,
+            snapshot: snapshot.into(),
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::scheme::recipe_revisions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreateRecipeRevision {
+    pub uuid: String,
+    pub recipe_id: String,
+    pub number: i32,
+    pub author_id: Option<String>,
+    pub snapshot: serde_json::Value,
+}
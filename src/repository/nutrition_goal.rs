@@ -0,0 +1,73 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+
+use crate::model as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct NutritionGoalRepository {
+    pool: Arc<Pool>,
+}
+
+impl NutritionGoalRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        NutritionGoalRepository { pool }
+    }
+
+    /// Daily goals of the owner, if ever set.
+    pub async fn fetch(
+        &self,
+        owner_id: String,
+    ) -> Result<Option<app_model::NutritionTargets>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let goals_resp = conn
+            .interact(move |conn| {
+                scheme::nutrition_goals::table
+                    .filter(scheme::nutrition_goals::owner_id.eq(owner_id))
+                    .select(db_model::NutritionGoals::as_select())
+                    .first(conn)
+                    .optional()
+            })
+            .await??;
+
+        Ok(goals_resp.map(|goals| goals.into()))
+    }
+
+    pub async fn save(
+        &self,
+        item: app_model::SetGoalsCommand,
+    ) -> Result<app_model::NutritionTargets, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let goals_resp = conn
+            .interact(move |conn| {
+                let row: db_model::SetNutritionGoals = item.into();
+
+                diesel::insert_into(scheme::nutrition_goals::table)
+                    .values(&row)
+                    .on_conflict(scheme::nutrition_goals::owner_id)
+                    .do_update()
+                    .set(&row)
+                    .returning(db_model::NutritionGoals::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(goals_resp.into())
+    }
+
+    pub async fn delete(&self, owner_id: String) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        conn.interact(move |conn| {
+            diesel::delete(scheme::nutrition_goals::table)
+                .filter(scheme::nutrition_goals::owner_id.eq(owner_id))
+                .execute(conn)
+        })
+        .await??;
+
+        Ok(())
+    }
+}
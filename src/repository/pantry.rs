@@ -0,0 +1,212 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{pantry as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct PantryRepository {
+    pool: Arc<Pool>,
+}
+
+fn filtered(q: &app_model::PantrySearchQuery) -> scheme::pantry_items::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::pantry_items::table
+        .filter(scheme::pantry_items::owner_id.eq(q.owner_id.clone()))
+        .into_boxed();
+
+    if let Some(expires_before) = q.expires_before {
+        myq = myq.filter(scheme::pantry_items::expires_on.le(expires_before));
+    }
+
+    myq
+}
+
+impl PantryRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        PantryRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreatePantryItemCommand,
+    ) -> Result<app_model::PantryItem, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let pantry_resp = conn
+            .interact(move |conn| {
+                let mut new_item: db_model::CreatePantryItem = item.into();
+                new_item.uuid = Uuid::new_v4().to_string();
+
+                diesel::insert_into(scheme::pantry_items::table)
+                    .values(new_item)
+                    .returning(db_model::PantryItem::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(pantry_resp.into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::PantryItemQuery,
+    ) -> Result<app_model::PantryItem, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let pantry_resp = conn
+            .interact(|conn| {
+                scheme::pantry_items::table
+                    .filter(scheme::pantry_items::uuid.eq(q.id))
+                    .filter(scheme::pantry_items::owner_id.eq(q.owner_id))
+                    .limit(1)
+                    .select(db_model::PantryItem::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(pantry_resp.into())
+    }
+
+    pub async fn update(
+        &self,
+        q: app_model::UpdatePantryItemCommand,
+    ) -> Result<app_model::PantryItem, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let pantry_resp = conn
+            .interact(move |conn| {
+                let pantry_update: db_model::UpdatePantryItem = (&q).into();
+
+                diesel::update(scheme::pantry_items::table)
+                    .filter(scheme::pantry_items::uuid.eq(&q.id))
+                    .filter(scheme::pantry_items::owner_id.eq(&q.owner_id))
+                    .set(pantry_update)
+                    .returning(db_model::PantryItem::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(pantry_resp.into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeletePantryItemCommand,
+    ) -> Result<app_model::PantryItem, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let pantry_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::pantry_items::table)
+                    .filter(scheme::pantry_items::uuid.eq(q.id))
+                    .filter(scheme::pantry_items::owner_id.eq(q.owner_id))
+                    .returning(db_model::PantryItem::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(pantry_resp.into())
+    }
+
+    /// Takes used amounts out of the owner's items of the same name, soonest
+    /// expiring first, and removes the used up ones. The items are locked
+    /// while they change so concurrent edits are not lost.
+    pub async fn use_up(
+        &self,
+        owner_id: String,
+        mut uses: Vec<app_model::PantryUse>,
+    ) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        // a fixed lock order keeps concurrent cooks from deadlocking
+        uses.sort_by(|a, b| a.name.cmp(&b.name));
+
+        conn.interact(move |conn| {
+            conn.transaction(|conn| {
+                for used in uses {
+                    let rows = scheme::pantry_items::table
+                        .filter(scheme::pantry_items::owner_id.eq(&owner_id))
+                        .filter(scheme::pantry_items::name.eq(&used.name))
+                        .order((
+                            scheme::pantry_items::expires_on.asc().nulls_last(),
+                            scheme::pantry_items::id,
+                        ))
+                        .select(db_model::PantryItem::as_select())
+                        .for_update()
+                        .load(conn)?;
+
+                    let mut need = used.amount;
+                    for row in rows {
+                        let id = row.id;
+                        let mut stock: app_model::PantryItem = row.into();
+
+                        let taken = stock.take(used.kind, need);
+                        if taken <= 0.0 {
+                            continue;
+                        }
+                        need -= taken;
+
+                        let item =
+                            scheme::pantry_items::table.filter(scheme::pantry_items::id.eq(id));
+                        match stock.quantity {
+                            Some(quantity) if quantity > 0.0 => {
+                                diesel::update(item)
+                                    .set(scheme::pantry_items::quantity.eq(quantity))
+                                    .execute(conn)?;
+                            }
+                            _ => {
+                                diesel::delete(item).execute(conn)?;
+                            }
+                        }
+                    }
+                }
+
+                QueryResult::Ok(())
+            })
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Items of the owner, soonest expiring first.
+    pub async fn search(
+        &self,
+        q: app_model::PantrySearchQuery,
+    ) -> Result<SearchResult<app_model::PantryItem>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, pantry_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q).order((
+                    scheme::pantry_items::expires_on.asc().nulls_last(),
+                    scheme::pantry_items::name,
+                    scheme::pantry_items::id,
+                ));
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let items = myq
+                    .select(db_model::PantryItem::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, items))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: pantry_resp.into_iter().map(|item| item.into()).collect(),
+        })
+    }
+}
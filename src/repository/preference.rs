@@ -0,0 +1,67 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+
+use crate::model::preference as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct PreferenceRepository {
+    pool: Arc<Pool>,
+}
+
+impl PreferenceRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        PreferenceRepository { pool }
+    }
+
+    /// Preferences of the owner, empty ones when never saved.
+    pub async fn fetch(
+        &self,
+        owner_id: String,
+    ) -> Result<app_model::UserPreferences, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let preference_resp = conn
+            .interact(move |conn| {
+                scheme::user_preferences::table
+                    .filter(scheme::user_preferences::owner_id.eq(&owner_id))
+                    .select(db_model::UserPreferences::as_select())
+                    .first(conn)
+                    .optional()
+                    .map(|found| (owner_id, found))
+            })
+            .await??;
+
+        Ok(match preference_resp {
+            (_, Some(found)) => found.into(),
+            (owner_id, None) => app_model::UserPreferences {
+                owner_id,
+                ..app_model::UserPreferences::default()
+            },
+        })
+    }
+
+    pub async fn save(
+        &self,
+        item: app_model::UserPreferences,
+    ) -> Result<app_model::UserPreferences, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let preference_resp = conn
+            .interact(move |conn| {
+                let row: db_model::SaveUserPreferences = item.into();
+
+                diesel::insert_into(scheme::user_preferences::table)
+                    .values(&row)
+                    .on_conflict(scheme::user_preferences::owner_id)
+                    .do_update()
+                    .set(&row)
+                    .returning(db_model::UserPreferences::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(preference_resp.into())
+    }
+}
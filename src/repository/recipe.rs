@@ -1,11 +1,12 @@
+use chrono::{NaiveDateTime, Utc};
 use deadpool_diesel::postgres::Pool;
-use diesel::prelude::*;
+use diesel::{dsl::not, prelude::*};
 use std::{error::Error, sync::Arc};
 use uuid::Uuid;
 
-use crate::model::{self as app_model, SearchResult};
+use crate::model::{self as app_model, SearchResult, SortDirection};
 
-use super::{model as db_model, scheme};
+use super::{model as db_model, revision, scheme};
 
 pub struct RecipeRepository {
     pool: Arc<Pool>,
@@ -22,20 +23,27 @@ impl RecipeRepository {
     ) -> Result<app_model::Recipe, Box<dyn Error>> {
         let conn = self.pool.get().await?;
 
+        let author_id = Some(item.actor.user_id.clone()).filter(|id| !id.is_empty());
         let mut new_recipe: db_model::CreateRecipe = item.into();
         let new_recipe_uuid = Uuid::new_v4().to_string();
         new_recipe.uuid = new_recipe_uuid.clone();
 
         let recipe_resp = conn
             .interact(|conn| {
-                diesel::insert_into(scheme::recipes::table)
-                    .values(new_recipe)
-                    .returning(db_model::Recipe::as_returning())
-                    .get_result(conn)
+                conn.transaction(|conn| {
+                    let recipe = diesel::insert_into(scheme::recipes::table)
+                        .values(new_recipe)
+                        .returning(db_model::Recipe::as_returning())
+                        .get_result(conn)?;
+
+                    revision::record(conn, &recipe, author_id)?;
+
+                    QueryResult::Ok(recipe)
+                })
             })
             .await??;
 
-        Ok(recipe_resp.into())
+        recipe_resp.try_into()
     }
 
     pub async fn fetch(
@@ -45,16 +53,22 @@ impl RecipeRepository {
         let conn = self.pool.get().await?;
 
         let recipe_resp = conn
-            .interact(|conn| {
-                scheme::recipes::table
+            .interact(move |conn| {
+                let mut myq = scheme::recipes::table
                     .filter(scheme::recipes::uuid.eq(q.id))
-                    .limit(1)
+                    .into_boxed();
+
+                if !q.include_deleted {
+                    myq = myq.filter(scheme::recipes::deleted_at.is_null());
+                }
+
+                myq.limit(1)
                     .select(db_model::Recipe::as_select())
                     .get_result(conn)
             })
             .await??;
 
-        Ok(recipe_resp.into())
+        recipe_resp.try_into()
     }
 
     pub async fn update(
@@ -66,22 +80,65 @@ impl RecipeRepository {
         let recipe_resp = conn
             .interact(|conn| {
                 let recipe_id = q.id.clone();
+                let author_id = Some(q.actor.user_id.clone()).filter(|id| !id.is_empty());
                 let recipe_update: db_model::UpdateRecipe = q.into();
 
-                diesel::update(scheme::recipes::table)
-                    .filter(scheme::recipes::uuid.eq(&recipe_id))
-                    .set(recipe_update)
-                    .execute(conn)?;
+                conn.transaction(|conn| {
+                    revision::lock(conn, &recipe_id)?;
+                    revision::record_baseline(conn, &recipe_id)?;
 
-                scheme::recipes::table
-                    .filter(scheme::recipes::uuid.eq(&recipe_id))
-                    .limit(1)
-                    .select(db_model::Recipe::as_select())
-                    .get_result(conn)
+                    diesel::update(scheme::recipes::table)
+                        .filter(scheme::recipes::uuid.eq(&recipe_id))
+                        .set(recipe_update)
+                        .execute(conn)?;
+
+                    let recipe = scheme::recipes::table
+                        .filter(scheme::recipes::uuid.eq(&recipe_id))
+                        .limit(1)
+                        .select(db_model::Recipe::as_select())
+                        .get_result(conn)?;
+
+                    revision::record(conn, &recipe, author_id)?;
+
+                    QueryResult::Ok(recipe)
+                })
             })
             .await??;
 
-        Ok(recipe_resp.into())
+        recipe_resp.try_into()
+    }
+
+    pub async fn set_status(
+        &self,
+        id: String,
+        status: app_model::RecipeStatus,
+        author_id: Option<String>,
+    ) -> Result<app_model::Recipe, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let recipe_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    revision::lock(conn, &id)?;
+                    revision::record_baseline(conn, &id)?;
+
+                    let recipe = diesel::update(scheme::recipes::table)
+                        .filter(scheme::recipes::uuid.eq(&id))
+                        .set((
+                            scheme::recipes::status.eq(status.code()),
+                            scheme::recipes::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .returning(db_model::Recipe::as_returning())
+                        .get_result(conn)?;
+
+                    revision::record(conn, &recipe, author_id)?;
+
+                    QueryResult::Ok(recipe)
+                })
+            })
+            .await??;
+
+        recipe_resp.try_into()
     }
 
     pub async fn delete(
@@ -91,12 +148,16 @@ impl RecipeRepository {
         let conn = self.pool.get().await?;
 
         let recipe_resp = self
-            .fetch(app_model::RecipeQuery { id: q.id.clone() })
+            .fetch(app_model::RecipeQuery {
+                id: q.id.clone(),
+                ..app_model::RecipeQuery::default()
+            })
             .await?;
 
         conn.interact(|conn| {
-            diesel::delete(scheme::recipes::table)
+            diesel::update(scheme::recipes::table)
                 .filter(scheme::recipes::uuid.eq(q.id))
+                .set(scheme::recipes::deleted_at.eq(Utc::now().naive_utc()))
                 .execute(conn)
         })
         .await??;
@@ -104,6 +165,73 @@ impl RecipeRepository {
         Ok(recipe_resp)
     }
 
+    pub async fn restore(
+        &self,
+        id: String,
+        author_id: Option<String>,
+    ) -> Result<app_model::Recipe, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let recipe_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    revision::lock(conn, &id)?;
+                    revision::record_baseline(conn, &id)?;
+
+                    let recipe = diesel::update(scheme::recipes::table)
+                        .filter(scheme::recipes::uuid.eq(&id))
+                        .set(scheme::recipes::deleted_at.eq(None::<NaiveDateTime>))
+                        .returning(db_model::Recipe::as_returning())
+                        .get_result(conn)?;
+
+                    revision::record(conn, &recipe, author_id)?;
+
+                    QueryResult::Ok(recipe)
+                })
+            })
+            .await??;
+
+        recipe_resp.try_into()
+    }
+
+    /// Hard-deletes recipes deleted before `before` together with their
+    /// reviews, favourites, collection items and revisions, returns their
+    /// number.
+    pub async fn purge(&self, before: NaiveDateTime) -> Result<i64, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let purged = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let ids = scheme::recipes::table
+                        .filter(scheme::recipes::deleted_at.lt(before))
+                        .select(scheme::recipes::uuid)
+                        .for_update()
+                        .load::<String>(conn)?;
+
+                    diesel::delete(scheme::reviews::table)
+                        .filter(scheme::reviews::recipe_id.eq_any(&ids))
+                        .execute(conn)?;
+                    diesel::delete(scheme::favorites::table)
+                        .filter(scheme::favorites::recipe_id.eq_any(&ids))
+                        .execute(conn)?;
+                    diesel::delete(scheme::collection_items::table)
+                        .filter(scheme::collection_items::recipe_id.eq_any(&ids))
+                        .execute(conn)?;
+                    diesel::delete(scheme::recipe_revisions::table)
+                        .filter(scheme::recipe_revisions::recipe_id.eq_any(&ids))
+                        .execute(conn)?;
+
+                    diesel::delete(scheme::recipes::table)
+                        .filter(scheme::recipes::uuid.eq_any(&ids))
+                        .execute(conn)
+                })
+            })
+            .await??;
+
+        Ok(purged as i64)
+    }
+
     pub async fn search(
         &self,
         q: app_model::RecipeSearchQuery,
@@ -111,20 +239,92 @@ impl RecipeRepository {
         let conn = self.pool.get().await?;
 
         let recipe_resp = conn
-            .interact(|conn| {
-                let mut myq = scheme::recipes::table.into_boxed();
+            .interact(move |conn| {
+                let mut myq = scheme::recipes::table
+                    .filter(scheme::recipes::deleted_at.is_null())
+                    .into_boxed();
+
+                if let Some(ids) = q.ids {
+                    myq = myq.filter(scheme::recipes::uuid.eq_any(ids));
+                }
 
                 if let Some(category_id) = q.category_id {
                     myq = myq.filter(scheme::recipes::category_id.eq(category_id));
                 }
 
+                if let Some(allergens) = q.exclude_allergens {
+                    let allergens: Vec<Option<String>> = allergens.into_iter().map(Some).collect();
+                    myq = myq.filter(not(scheme::recipes::allergens.overlaps_with(allergens)));
+                }
+
+                if let Some(diets) = q.diets {
+                    myq = myq.filter(scheme::recipes::diets.contains(db_model::diet_codes(&diets)));
+                }
+
+                if let Some(author_id) = q.author_id {
+                    myq = myq.filter(scheme::recipes::author_id.eq(author_id));
+                }
+
+                if let Some(max_time_to_cook) = q.max_time_to_cook {
+                    myq = myq.filter(scheme::recipes::time_to_cook.le(max_time_to_cook));
+                }
+
+                if let Some(min_rating) = q.min_rating {
+                    myq = myq.filter(scheme::recipes::rating_average.ge(min_rating));
+                }
+
+                if let Some(status) = q.status {
+                    myq = myq.filter(scheme::recipes::status.eq(status.code()));
+                }
+
+                let published = app_model::RecipeStatus::Published.code();
+                myq = match q.visibility {
+                    app_model::RecipeVisibility::Published => {
+                        myq.filter(scheme::recipes::status.eq(published))
+                    }
+                    app_model::RecipeVisibility::PublishedOrAuthoredBy(user_id) => myq.filter(
+                        scheme::recipes::status
+                            .eq(published)
+                            .or(scheme::recipes::author_id.eq(user_id)),
+                    ),
+                    app_model::RecipeVisibility::All => myq,
+                };
+
+                myq = match (q.sort, q.direction) {
+                    (app_model::RecipeSort::Created, SortDirection::Asc) => {
+                        myq.order(scheme::recipes::id.asc())
+                    }
+                    (app_model::RecipeSort::Created, SortDirection::Desc) => {
+                        myq.order(scheme::recipes::id.desc())
+                    }
+                    (app_model::RecipeSort::Title, SortDirection::Asc) => {
+                        myq.order((scheme::recipes::title.asc(), scheme::recipes::id))
+                    }
+                    (app_model::RecipeSort::Title, SortDirection::Desc) => {
+                        myq.order((scheme::recipes::title.desc(), scheme::recipes::id))
+                    }
+                    (app_model::RecipeSort::Rating, SortDirection::Asc) => myq.order((
+                        scheme::recipes::rating_average.asc().nulls_last(),
+                        scheme::recipes::rating_count.asc(),
+                        scheme::recipes::id,
+                    )),
+                    (app_model::RecipeSort::Rating, SortDirection::Desc) => myq.order((
+                        scheme::recipes::rating_average.desc().nulls_last(),
+                        scheme::recipes::rating_count.desc(),
+                        scheme::recipes::id,
+                    )),
+                };
+
                 myq.select(db_model::Recipe::as_select()).get_results(conn)
             })
             .await??;
 
         Ok(SearchResult {
             count: recipe_resp.len() as i64,
-            items: recipe_resp.into_iter().map(|item| item.into()).collect(),
+            items: recipe_resp
+                .into_iter()
+                .map(app_model::Recipe::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
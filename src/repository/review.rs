@@ -0,0 +1,290 @@
+use chrono::Utc;
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{review as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct ReviewRepository {
+    pool: Arc<Pool>,
+}
+
+fn filtered(q: &app_model::ReviewSearchQuery) -> scheme::reviews::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::reviews::table.into_boxed();
+
+    if let Some(recipe_id) = q.recipe_id.clone() {
+        myq = myq.filter(scheme::reviews::recipe_id.eq(recipe_id));
+    }
+
+    if q.flagged {
+        myq = myq.filter(scheme::reviews::flagged.eq(true));
+    } else {
+        myq = myq.filter(scheme::reviews::hidden.eq(false));
+    }
+
+    myq
+}
+
+/// Recomputes the rating of a recipe from its visible reviews.
+fn refresh_rating(conn: &mut PgConnection, recipe_id: &str) -> QueryResult<usize> {
+    let (count, sum) = scheme::reviews::table
+        .filter(scheme::reviews::recipe_id.eq(recipe_id))
+        .filter(scheme::reviews::hidden.eq(false))
+        .select((
+            diesel::dsl::count_star(),
+            diesel::dsl::sum(scheme::reviews::rating),
+        ))
+        .get_result::<(i64, Option<i64>)>(conn)?;
+
+    let average = sum
+        .filter(|_| count > 0)
+        .map(|sum| sum as f64 / count as f64);
+
+    diesel::update(scheme::recipes::table)
+        .filter(scheme::recipes::uuid.eq(recipe_id))
+        .set((
+            scheme::recipes::rating_average.eq(average),
+            scheme::recipes::rating_count.eq(count),
+        ))
+        .execute(conn)
+}
+
+impl ReviewRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        ReviewRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateReviewCommand,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let review = diesel::insert_into(scheme::reviews::table)
+                        .values(db_model::CreateReview {
+                            uuid: Uuid::new_v4().to_string(),
+                            recipe_id: item.recipe_id,
+                            owner_id: item.owner_id,
+                            rating: item.rating,
+                            body: item.body,
+                        })
+                        .returning(db_model::Review::as_returning())
+                        .get_result(conn)?;
+
+                    refresh_rating(conn, &review.recipe_id)?;
+
+                    QueryResult::Ok(review)
+                })
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::ReviewQuery,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(|conn| {
+                scheme::reviews::table
+                    .filter(scheme::reviews::uuid.eq(q.id))
+                    .filter(scheme::reviews::recipe_id.eq(q.recipe_id))
+                    .limit(1)
+                    .select(db_model::Review::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    /// The review `owner_id` wrote for the recipe, if any.
+    pub async fn find(
+        &self,
+        recipe_id: String,
+        owner_id: String,
+    ) -> Result<Option<app_model::Review>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(|conn| {
+                scheme::reviews::table
+                    .filter(scheme::reviews::recipe_id.eq(recipe_id))
+                    .filter(scheme::reviews::owner_id.eq(owner_id))
+                    .select(db_model::Review::as_select())
+                    .first(conn)
+                    .optional()
+            })
+            .await??;
+
+        Ok(review_resp.map(|review| review.into()))
+    }
+
+    pub async fn update(
+        &self,
+        q: app_model::UpdateReviewCommand,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let review_update: db_model::UpdateReview = (&q).into();
+
+                    let review = diesel::update(scheme::reviews::table)
+                        .filter(scheme::reviews::uuid.eq(&q.id))
+                        .filter(scheme::reviews::recipe_id.eq(&q.recipe_id))
+                        .filter(scheme::reviews::owner_id.eq(&q.owner_id))
+                        .set(review_update)
+                        .returning(db_model::Review::as_returning())
+                        .get_result(conn)?;
+
+                    refresh_rating(conn, &review.recipe_id)?;
+
+                    QueryResult::Ok(review)
+                })
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteReviewCommand,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(|conn| {
+                conn.transaction(|conn| {
+                    let review = diesel::delete(scheme::reviews::table)
+                        .filter(scheme::reviews::uuid.eq(q.id))
+                        .filter(scheme::reviews::recipe_id.eq(q.recipe_id))
+                        .returning(db_model::Review::as_returning())
+                        .get_result(conn)?;
+
+                    refresh_rating(conn, &review.recipe_id)?;
+
+                    QueryResult::Ok(review)
+                })
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    pub async fn flag(
+        &self,
+        q: app_model::FlagReviewCommand,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(|conn| {
+                diesel::update(scheme::reviews::table)
+                    .filter(scheme::reviews::uuid.eq(q.id))
+                    .filter(scheme::reviews::recipe_id.eq(q.recipe_id))
+                    .set((
+                        scheme::reviews::flagged.eq(true),
+                        scheme::reviews::flag_reason.eq(q.reason),
+                    ))
+                    .returning(db_model::Review::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    /// Hides or keeps the review and clears its flag.
+    pub async fn moderate(
+        &self,
+        q: app_model::ModerateReviewCommand,
+    ) -> Result<app_model::Review, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let review_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let review = diesel::update(scheme::reviews::table)
+                        .filter(scheme::reviews::uuid.eq(q.id))
+                        .filter(scheme::reviews::recipe_id.eq(q.recipe_id))
+                        .set((
+                            scheme::reviews::hidden.eq(q.hidden),
+                            scheme::reviews::flagged.eq(false),
+                            scheme::reviews::flag_reason.eq(""),
+                            scheme::reviews::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .returning(db_model::Review::as_returning())
+                        .get_result(conn)?;
+
+                    refresh_rating(conn, &review.recipe_id)?;
+
+                    QueryResult::Ok(review)
+                })
+            })
+            .await??;
+
+        Ok(review_resp.into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::ReviewSearchQuery,
+    ) -> Result<SearchResult<app_model::Review>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, review_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q).order(scheme::reviews::id.desc());
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let reviews = myq
+                    .select(db_model::Review::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, reviews))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: review_resp.into_iter().map(|item| item.into()).collect(),
+        })
+    }
+
+    /// The rating the owner gave each recipe they reviewed.
+    pub async fn ratings(&self, owner_id: String) -> Result<Vec<(String, i32)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let ratings_resp = conn
+            .interact(move |conn| {
+                scheme::reviews::table
+                    .filter(scheme::reviews::owner_id.eq(owner_id))
+                    .select((scheme::reviews::recipe_id, scheme::reviews::rating))
+                    .load::<(String, i32)>(conn)
+            })
+            .await??;
+
+        Ok(ratings_resp)
+    }
+}
@@ -0,0 +1,159 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{self as app_model, Pagination, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct RevisionRepository {
+    pool: Arc<Pool>,
+}
+
+/// Stores the content of the recipe row as its next revision, unless it
+/// equals the latest one. Fails when the row can't be read back as a
+/// snapshot, so no revision is left unusable. Must run in a transaction,
+/// which holds the lock on the recipe row.
+pub(super) fn record(
+    conn: &mut PgConnection,
+    recipe: &db_model::Recipe,
+    author_id: Option<String>,
+) -> QueryResult<usize> {
+    let snapshot = db_model::RecipeSnapshotDocument::try_from(recipe)
+        .and_then(|document| Ok(serde_json::to_value(document)?))
+        .map_err(|err| diesel::result::Error::SerializationError(err.to_string().into()))?;
+
+    lock(conn, &recipe.uuid)?;
+
+    let last = scheme::recipe_revisions::table
+        .filter(scheme::recipe_revisions::recipe_id.eq(&recipe.uuid))
+        .order(scheme::recipe_revisions::number.desc())
+        .select((
+            scheme::recipe_revisions::number,
+            scheme::recipe_revisions::snapshot,
+        ))
+        .first::<(i32, serde_json::Value)>(conn)
+        .optional()?;
+
+    if let Some((_, last_snapshot)) = &last {
+        if *last_snapshot == snapshot {
+            return Ok(0);
+        }
+    }
+
+    diesel::insert_into(scheme::recipe_revisions::table)
+        .values(db_model::CreateRecipeRevision {
+            uuid: Uuid::new_v4().to_string(),
+            recipe_id: recipe.uuid.clone(),
+            number: last.map_or(0, |(number, _)| number) + 1,
+            author_id,
+            snapshot,
+        })
+        .execute(conn)
+}
+
+/// Locks the recipe row until the end of the transaction. Changes take it
+/// before reading the content they record, which serialises concurrent
+/// changes so revision numbers stay unique and baselines current.
+pub(super) fn lock(conn: &mut PgConnection, recipe_id: &str) -> QueryResult<()> {
+    scheme::recipes::table
+        .filter(scheme::recipes::uuid.eq(recipe_id))
+        .select(scheme::recipes::id)
+        .for_update()
+        .get_result::<i32>(conn)?;
+
+    Ok(())
+}
+
+/// Stores the current content of a recipe older than the revision history
+/// as its first revision, so its content from before the change is kept.
+pub(super) fn record_baseline(conn: &mut PgConnection, recipe_id: &str) -> QueryResult<()> {
+    if has_revisions(conn, recipe_id)? {
+        return Ok(());
+    }
+
+    let current = scheme::recipes::table
+        .filter(scheme::recipes::uuid.eq(recipe_id))
+        .select(db_model::Recipe::as_select())
+        .get_result(conn)?;
+    record(conn, &current, None)?;
+
+    Ok(())
+}
+
+/// Whether the recipe has any revision yet.
+fn has_revisions(conn: &mut PgConnection, recipe_id: &str) -> QueryResult<bool> {
+    diesel::select(diesel::dsl::exists(
+        scheme::recipe_revisions::table.filter(scheme::recipe_revisions::recipe_id.eq(recipe_id)),
+    ))
+    .get_result(conn)
+}
+
+impl RevisionRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        RevisionRepository { pool }
+    }
+
+    pub async fn fetch(
+        &self,
+        recipe_id: String,
+        number: i32,
+    ) -> Result<app_model::RecipeRevision, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let revision_resp = conn
+            .interact(move |conn| {
+                scheme::recipe_revisions::table
+                    .filter(scheme::recipe_revisions::recipe_id.eq(recipe_id))
+                    .filter(scheme::recipe_revisions::number.eq(number))
+                    .select(db_model::RecipeRevision::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        revision_resp.try_into()
+    }
+
+    /// Revisions of the recipe, latest first.
+    pub async fn search(
+        &self,
+        recipe_id: String,
+        pagination: Pagination,
+    ) -> Result<SearchResult<app_model::RecipeRevision>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, revisions_resp) = conn
+            .interact(move |conn| {
+                let count = scheme::recipe_revisions::table
+                    .filter(scheme::recipe_revisions::recipe_id.eq(&recipe_id))
+                    .count()
+                    .get_result::<i64>(conn)?;
+
+                let mut myq = scheme::recipe_revisions::table
+                    .filter(scheme::recipe_revisions::recipe_id.eq(&recipe_id))
+                    .order(scheme::recipe_revisions::number.desc())
+                    .select(db_model::RecipeRevision::as_select())
+                    .into_boxed();
+
+                if let Some(limit) = pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                QueryResult::Ok((count, myq.load(conn)?))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: revisions_resp
+                .into_iter()
+                .map(app_model::RecipeRevision::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
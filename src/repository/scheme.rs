@@ -1,11 +1,190 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    allergens (id) {
+        id -> Int4,
+        code -> Text,
+        name -> Text,
+        custom -> Bool,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        name -> Text,
+        prefix -> Text,
+        key_hash -> Text,
+        scopes -> Array<Nullable<Text>>,
+        rate_limit -> Int4,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     categories (id) {
         id -> Int4,
         uuid -> Text,
         name -> Text,
         updated_at -> Timestamp,
+        slug -> Text,
+        description -> Text,
+        image -> Text,
+        sort_order -> Int4,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    collection_items (id) {
+        id -> Int4,
+        collection_id -> Int4,
+        recipe_id -> Text,
+        position -> Int4,
+        note -> Text,
+    }
+}
+
+diesel::table! {
+    collections (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        name -> Text,
+        description -> Text,
+        share_token -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    cooking_logs (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        recipe_id -> Text,
+        recipe_title -> Text,
+        cooked_on -> Date,
+        servings -> Float8,
+        notes -> Text,
+        photo -> Bytea,
+        nutrients -> Jsonb,
+        created_at -> Timestamp,
+        meal -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    favorites (id) {
+        id -> Int4,
+        owner_id -> Text,
+        recipe_id -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    food_entries (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        eaten_on -> Date,
+        meal -> Nullable<Text>,
+        name -> Text,
+        nutrients -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ingredients (id) {
+        id -> Int4,
+        uuid -> Text,
+        name -> Text,
+        aliases -> Array<Nullable<Text>>,
+        animal -> Bool,
+        meat -> Bool,
+        gluten -> Bool,
+        updated_at -> Timestamp,
+        proteins -> Nullable<Float8>,
+        fats -> Nullable<Float8>,
+        carbohydrates -> Nullable<Float8>,
+        fiber -> Nullable<Float8>,
+        kcal -> Nullable<Float8>,
+        grams_per_piece -> Nullable<Float8>,
+        density -> Nullable<Float8>,
+        saturated_fat -> Nullable<Float8>,
+        sugars -> Nullable<Float8>,
+        sodium -> Nullable<Float8>,
+        cholesterol -> Nullable<Float8>,
+        aisle -> Text,
+    }
+}
+
+diesel::table! {
+    meal_plan_slots (id) {
+        id -> Int4,
+        meal_plan_id -> Int4,
+        date -> Date,
+        meal -> Text,
+        recipe_id -> Text,
+        servings -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    meal_plans (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        name -> Text,
+        start_date -> Date,
+        end_date -> Date,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    nutrition_goals (id) {
+        id -> Int4,
+        owner_id -> Text,
+        kcal -> Float8,
+        proteins -> Nullable<Float8>,
+        fats -> Nullable<Float8>,
+        carbohydrates -> Nullable<Float8>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    pantry_items (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        name -> Text,
+        quantity -> Nullable<Float8>,
+        unit -> Nullable<Text>,
+        expires_on -> Nullable<Date>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    recipe_revisions (id) {
+        id -> Int4,
+        uuid -> Text,
+        recipe_id -> Text,
+        number -> Int4,
+        author_id -> Nullable<Text>,
+        snapshot -> Jsonb,
+        created_at -> Timestamp,
     }
 }
 
@@ -23,17 +202,139 @@ diesel::table! {
         ingredients -> Array<Nullable<Text>>,
         guideline -> Text,
         updated_at -> Timestamp,
-        #[sql_name = "nutrients.proteins"]
-        nutrients_proteins -> Int8,
-        #[sql_name = "nutrients.fats"]
-        nutrients_fats -> Float8,
-        #[sql_name = "nutrients.carbohydrates"]
-        nutrients_carbohydrates -> Float8,
-        #[sql_name = "nutrients.fiber"]
-        nutrients_fiber -> Int8,
-        #[sql_name = "nutrients.kcal"]
-        nutrients_kcal -> Int8,
+        allergens -> Array<Nullable<Text>>,
+        diets -> Array<Nullable<Text>>,
+        nutrients_computed -> Bool,
+        nutrients -> Jsonb,
+        author_id -> Nullable<Text>,
+        source_url -> Nullable<Text>,
+        attribution -> Text,
+        license -> Text,
+        rating_average -> Nullable<Float8>,
+        rating_count -> Int8,
+        status -> Text,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reviews (id) {
+        id -> Int4,
+        uuid -> Text,
+        recipe_id -> Text,
+        owner_id -> Text,
+        rating -> Int4,
+        body -> Text,
+        flagged -> Bool,
+        flag_reason -> Text,
+        hidden -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Int4,
+        uuid -> Text,
+        user_id -> Text,
+        refresh_token_hash -> Text,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    shopping_list_items (id) {
+        id -> Int4,
+        uuid -> Text,
+        shopping_list_id -> Int4,
+        name -> Text,
+        quantity -> Nullable<Float8>,
+        unit -> Nullable<Text>,
+        aisle -> Text,
+        checked -> Bool,
+    }
+}
+
+diesel::table! {
+    shopping_lists (id) {
+        id -> Int4,
+        uuid -> Text,
+        owner_id -> Text,
+        name -> Text,
+        meal_plan_id -> Nullable<Text>,
+        updated_at -> Timestamp,
+        missing_recipes -> Array<Nullable<Text>>,
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(categories, recipes,);
+diesel::table! {
+    substitutions (id) {
+        id -> Int4,
+        uuid -> Text,
+        ingredient -> Text,
+        substitute -> Text,
+        ratio -> Float8,
+        unit -> Nullable<Text>,
+        avoids -> Array<Nullable<Text>>,
+        notes -> Text,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_preferences (id) {
+        id -> Int4,
+        owner_id -> Text,
+        diets -> Array<Nullable<Text>>,
+        exclude_allergens -> Array<Nullable<Text>>,
+        categories -> Array<Nullable<Text>>,
+        disliked_ingredients -> Array<Nullable<Text>>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        uuid -> Text,
+        email -> Text,
+        name -> Text,
+        password_hash -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        role -> Text,
+    }
+}
+
+diesel::joinable!(collection_items -> collections (collection_id));
+diesel::joinable!(meal_plan_slots -> meal_plans (meal_plan_id));
+diesel::joinable!(shopping_list_items -> shopping_lists (shopping_list_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    allergens,
+    api_keys,
+    categories,
+    collection_items,
+    collections,
+    cooking_logs,
+    favorites,
+    food_entries,
+    ingredients,
+    meal_plan_slots,
+    meal_plans,
+    nutrition_goals,
+    pantry_items,
+    recipe_revisions,
+    recipes,
+    reviews,
+    sessions,
+    shopping_list_items,
+    shopping_lists,
+    substitutions,
+    user_preferences,
+    users,
+);
@@ -0,0 +1,151 @@
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::user as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct SessionRepository {
+    pool: Arc<Pool>,
+}
+
+impl SessionRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        SessionRepository { pool }
+    }
+
+    /// Starts a session and returns its id.
+    pub async fn create(
+        &self,
+        user_id: String,
+        refresh_token_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<String, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let session_resp = conn
+            .interact(move |conn| {
+                diesel::insert_into(scheme::sessions::table)
+                    .values(db_model::CreateSession {
+                        uuid: Uuid::new_v4().to_string(),
+                        user_id,
+                        refresh_token_hash,
+                        expires_at,
+                    })
+                    .returning(scheme::sessions::uuid)
+                    .get_result::<String>(conn)
+            })
+            .await??;
+
+        Ok(session_resp)
+    }
+
+    /// Replaces the refresh token of the active session holding `old_hash`.
+    /// `None` when there is no such session.
+    pub async fn rotate(
+        &self,
+        old_hash: String,
+        new_hash: String,
+        expires_at: NaiveDateTime,
+    ) -> Result<Option<app_model::Identity>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let session_resp = conn
+            .interact(move |conn| {
+                let now = Utc::now().naive_utc();
+
+                conn.transaction(|conn| {
+                    let session = diesel::update(scheme::sessions::table)
+                        .filter(scheme::sessions::refresh_token_hash.eq(old_hash))
+                        .filter(scheme::sessions::revoked_at.is_null())
+                        .filter(scheme::sessions::expires_at.gt(now))
+                        .set((
+                            scheme::sessions::refresh_token_hash.eq(new_hash),
+                            scheme::sessions::expires_at.eq(expires_at),
+                            scheme::sessions::updated_at.eq(now),
+                        ))
+                        .returning((scheme::sessions::user_id, scheme::sessions::uuid))
+                        .get_result::<(String, String)>(conn)
+                        .optional()?;
+
+                    let Some((user_id, session_id)) = session else {
+                        return QueryResult::Ok(None);
+                    };
+
+                    let role = scheme::users::table
+                        .filter(scheme::users::uuid.eq(&user_id))
+                        .select(scheme::users::role)
+                        .get_result::<String>(conn)?;
+
+                    QueryResult::Ok(Some((user_id, session_id, role)))
+                })
+            })
+            .await??;
+
+        Ok(
+            session_resp.map(|(user_id, session_id, role)| app_model::Identity {
+                user_id,
+                session_id,
+                role: app_model::Role::from_code(&role).unwrap_or_default(),
+            }),
+        )
+    }
+
+    /// Current role of the user of an active session, `None` when the
+    /// session was revoked or expired.
+    pub async fn active_role(
+        &self,
+        user_id: String,
+        session_id: String,
+    ) -> Result<Option<app_model::Role>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let session_resp = conn
+            .interact(move |conn| {
+                scheme::sessions::table
+                    .inner_join(
+                        scheme::users::table.on(scheme::users::uuid.eq(scheme::sessions::user_id)),
+                    )
+                    .filter(scheme::sessions::uuid.eq(session_id))
+                    .filter(scheme::sessions::user_id.eq(user_id))
+                    .filter(scheme::sessions::revoked_at.is_null())
+                    .filter(scheme::sessions::expires_at.gt(Utc::now().naive_utc()))
+                    .select(scheme::users::role)
+                    .first::<String>(conn)
+                    .optional()
+            })
+            .await??;
+
+        Ok(session_resp.map(|role| app_model::Role::from_code(&role).unwrap_or_default()))
+    }
+
+    /// Revokes the session, or every session of the user when `all` is set.
+    pub async fn revoke(&self, q: app_model::LogoutCommand) -> Result<(), Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        conn.interact(move |conn| {
+            let now = Utc::now().naive_utc();
+
+            let mut myq = diesel::update(scheme::sessions::table)
+                .filter(scheme::sessions::user_id.eq(q.user_id))
+                .filter(scheme::sessions::revoked_at.is_null())
+                .into_boxed();
+
+            if !q.all {
+                myq = myq.filter(scheme::sessions::uuid.eq(q.session_id));
+            }
+
+            myq.set((
+                scheme::sessions::revoked_at.eq(now),
+                scheme::sessions::updated_at.eq(now),
+            ))
+            .execute(conn)
+        })
+        .await??;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{shopping_list as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct ShoppingListRepository {
+    pool: Arc<Pool>,
+}
+
+fn load_items(
+    conn: &mut PgConnection,
+    lists: Vec<db_model::ShoppingList>,
+) -> QueryResult<Vec<app_model::ShoppingList>> {
+    let items = db_model::ShoppingListItem::belonging_to(&lists)
+        .order(scheme::shopping_list_items::id)
+        .select(db_model::ShoppingListItem::as_select())
+        .load(conn)?;
+
+    Ok(items
+        .grouped_by(&lists)
+        .into_iter()
+        .zip(lists)
+        .map(|(items, list)| (list, items).into())
+        .collect())
+}
+
+fn fetch_list(
+    conn: &mut PgConnection,
+    id: &str,
+    owner_id: &str,
+) -> QueryResult<db_model::ShoppingList> {
+    scheme::shopping_lists::table
+        .filter(scheme::shopping_lists::uuid.eq(id))
+        .filter(scheme::shopping_lists::owner_id.eq(owner_id))
+        .limit(1)
+        .select(db_model::ShoppingList::as_select())
+        .get_result(conn)
+}
+
+impl ShoppingListRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        ShoppingListRepository { pool }
+    }
+
+    /// Stores a list with its items, in the given order.
+    pub async fn create(
+        &self,
+        item: app_model::ShoppingList,
+    ) -> Result<app_model::ShoppingList, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let shopping_list_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let mut new_list: db_model::CreateShoppingList = (&item).into();
+                    new_list.uuid = Uuid::new_v4().to_string();
+
+                    let list = diesel::insert_into(scheme::shopping_lists::table)
+                        .values(new_list)
+                        .returning(db_model::ShoppingList::as_returning())
+                        .get_result(conn)?;
+
+                    let rows: Vec<db_model::CreateShoppingListItem> = item
+                        .items
+                        .into_iter()
+                        .map(|entry| {
+                            let mut row = db_model::CreateShoppingListItem::new(list.id, entry);
+                            row.uuid = Uuid::new_v4().to_string();
+                            row
+                        })
+                        .collect();
+
+                    diesel::insert_into(scheme::shopping_list_items::table)
+                        .values(rows)
+                        .execute(conn)?;
+
+                    load_items(conn, vec![list])
+                })
+            })
+            .await??;
+
+        shopping_list_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "shopping list was not created".into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::ShoppingListQuery,
+    ) -> Result<app_model::ShoppingList, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let shopping_list_resp = conn
+            .interact(move |conn| {
+                let list = fetch_list(conn, &q.id, &q.owner_id)?;
+
+                load_items(conn, vec![list])
+            })
+            .await??;
+
+        shopping_list_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "shopping list not found".into())
+    }
+
+    pub async fn check_item(
+        &self,
+        q: app_model::CheckShoppingListItemCommand,
+    ) -> Result<app_model::ShoppingList, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let shopping_list_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let list = fetch_list(conn, &q.shopping_list_id, &q.owner_id)?;
+
+                    diesel::update(scheme::shopping_list_items::table)
+                        .filter(scheme::shopping_list_items::shopping_list_id.eq(list.id))
+                        .filter(scheme::shopping_list_items::uuid.eq(&q.item_id))
+                        .set(scheme::shopping_list_items::checked.eq(q.checked))
+                        .returning(scheme::shopping_list_items::id)
+                        .get_result::<i32>(conn)?;
+
+                    load_items(conn, vec![list])
+                })
+            })
+            .await??;
+
+        shopping_list_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "shopping list not found".into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteShoppingListCommand,
+    ) -> Result<app_model::ShoppingList, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let shopping_list_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let list = fetch_list(conn, &q.id, &q.owner_id)?;
+                    let list_id = list.id;
+
+                    let res = load_items(conn, vec![list])?;
+
+                    diesel::delete(scheme::shopping_lists::table)
+                        .filter(scheme::shopping_lists::id.eq(list_id))
+                        .execute(conn)?;
+
+                    QueryResult::Ok(res)
+                })
+            })
+            .await??;
+
+        shopping_list_resp
+            .into_iter()
+            .next()
+            .ok_or_else(|| "shopping list not found".into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::ShoppingListSearchQuery,
+    ) -> Result<SearchResult<app_model::ShoppingList>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, shopping_list_resp) = conn
+            .interact(move |conn| {
+                let count = scheme::shopping_lists::table
+                    .filter(scheme::shopping_lists::owner_id.eq(&q.owner_id))
+                    .count()
+                    .get_result::<i64>(conn)?;
+
+                let mut myq = scheme::shopping_lists::table
+                    .filter(scheme::shopping_lists::owner_id.eq(&q.owner_id))
+                    .order(scheme::shopping_lists::id.desc())
+                    .into_boxed();
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let lists = myq
+                    .select(db_model::ShoppingList::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, load_items(conn, lists)?))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: shopping_list_resp,
+        })
+    }
+}
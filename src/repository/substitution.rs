@@ -0,0 +1,152 @@
+use deadpool_diesel::postgres::Pool;
+use diesel::{pg::Pg, prelude::*};
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::{substitution as app_model, SearchResult};
+
+use super::{model as db_model, scheme};
+
+pub struct SubstitutionRepository {
+    pool: Arc<Pool>,
+}
+
+fn filtered(
+    q: &app_model::SubstitutionSearchQuery,
+) -> scheme::substitutions::BoxedQuery<'static, Pg> {
+    let mut myq = scheme::substitutions::table.into_boxed();
+
+    if let Some(ingredient) = q.ingredient.clone() {
+        myq = myq.filter(scheme::substitutions::ingredient.eq(ingredient));
+    }
+
+    if let Some(avoids) = q.avoids.as_ref() {
+        let avoids: Vec<Option<String>> = avoids.iter().cloned().map(Some).collect();
+        myq = myq.filter(scheme::substitutions::avoids.overlaps_with(avoids));
+    }
+
+    myq
+}
+
+impl SubstitutionRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        SubstitutionRepository { pool }
+    }
+
+    pub async fn create(
+        &self,
+        item: app_model::CreateSubstitutionCommand,
+    ) -> Result<app_model::Substitution, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let substitution_resp = conn
+            .interact(move |conn| {
+                let mut new_substitution: db_model::CreateSubstitution = item.into();
+                new_substitution.uuid = Uuid::new_v4().to_string();
+
+                diesel::insert_into(scheme::substitutions::table)
+                    .values(new_substitution)
+                    .returning(db_model::Substitution::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(substitution_resp.into())
+    }
+
+    pub async fn fetch(
+        &self,
+        q: app_model::SubstitutionQuery,
+    ) -> Result<app_model::Substitution, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let substitution_resp = conn
+            .interact(|conn| {
+                scheme::substitutions::table
+                    .filter(scheme::substitutions::uuid.eq(q.id))
+                    .limit(1)
+                    .select(db_model::Substitution::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(substitution_resp.into())
+    }
+
+    pub async fn update(
+        &self,
+        q: app_model::UpdateSubstitutionCommand,
+    ) -> Result<app_model::Substitution, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let substitution_resp = conn
+            .interact(move |conn| {
+                let substitution_update: db_model::UpdateSubstitution = (&q).into();
+
+                diesel::update(scheme::substitutions::table)
+                    .filter(scheme::substitutions::uuid.eq(&q.id))
+                    .set(substitution_update)
+                    .returning(db_model::Substitution::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(substitution_resp.into())
+    }
+
+    pub async fn delete(
+        &self,
+        q: app_model::DeleteSubstitutionCommand,
+    ) -> Result<app_model::Substitution, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let substitution_resp = conn
+            .interact(|conn| {
+                diesel::delete(scheme::substitutions::table)
+                    .filter(scheme::substitutions::uuid.eq(q.id))
+                    .returning(db_model::Substitution::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(substitution_resp.into())
+    }
+
+    pub async fn search(
+        &self,
+        q: app_model::SubstitutionSearchQuery,
+    ) -> Result<SearchResult<app_model::Substitution>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let (count, substitution_resp) = conn
+            .interact(move |conn| {
+                let count = filtered(&q).count().get_result::<i64>(conn)?;
+
+                let mut myq = filtered(&q)
+                    .order((scheme::substitutions::ingredient, scheme::substitutions::id));
+
+                if let Some(limit) = q.pagination.limit {
+                    myq = myq.limit(limit);
+                }
+
+                if let Some(offset) = q.pagination.offset {
+                    myq = myq.offset(offset);
+                }
+
+                let items = myq
+                    .select(db_model::Substitution::as_select())
+                    .get_results(conn)?;
+
+                QueryResult::Ok((count, items))
+            })
+            .await??;
+
+        Ok(SearchResult {
+            count,
+            items: substitution_resp
+                .into_iter()
+                .map(|item| item.into())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::Utc;
+use deadpool_diesel::postgres::Pool;
+use diesel::prelude::*;
+use std::{error::Error, sync::Arc};
+use uuid::Uuid;
+
+use crate::model::user as app_model;
+
+use super::{model as db_model, scheme};
+
+pub struct UserRepository {
+    pool: Arc<Pool>,
+}
+
+impl UserRepository {
+    pub async fn new(pool: Arc<Pool>) -> Self {
+        UserRepository { pool }
+    }
+
+    /// Creates a user with `role`, or an admin when there is no user yet so
+    /// a fresh installation can be set up.
+    pub async fn create(
+        &self,
+        email: String,
+        name: String,
+        password_hash: String,
+        role: app_model::Role,
+    ) -> Result<app_model::User, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let user_resp = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    diesel::sql_query("LOCK TABLE users IN SHARE ROW EXCLUSIVE MODE")
+                        .execute(conn)?;
+
+                    let count = scheme::users::table.count().get_result::<i64>(conn)?;
+                    let role = match count {
+                        0 => app_model::Role::Admin,
+                        _ => role,
+                    };
+
+                    diesel::insert_into(scheme::users::table)
+                        .values(db_model::CreateUser {
+                            uuid: Uuid::new_v4().to_string(),
+                            email,
+                            name,
+                            password_hash,
+                            role: role.code().to_string(),
+                        })
+                        .returning(db_model::User::as_returning())
+                        .get_result(conn)
+                })
+            })
+            .await??;
+
+        Ok(user_resp.into())
+    }
+
+    pub async fn fetch(&self, id: String) -> Result<app_model::User, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let user_resp = conn
+            .interact(|conn| {
+                scheme::users::table
+                    .filter(scheme::users::uuid.eq(id))
+                    .limit(1)
+                    .select(db_model::User::as_select())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(user_resp.into())
+    }
+
+    /// The user with the email and their password hash.
+    pub async fn find_by_email(
+        &self,
+        email: String,
+    ) -> Result<Option<(app_model::User, String)>, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let user_resp = conn
+            .interact(|conn| {
+                scheme::users::table
+                    .filter(scheme::users::email.eq(email))
+                    .select(db_model::User::as_select())
+                    .first(conn)
+                    .optional()
+            })
+            .await??;
+
+        Ok(user_resp.map(|user| {
+            let password_hash = user.password_hash.clone();
+            (user.into(), password_hash)
+        }))
+    }
+
+    pub async fn set_role(
+        &self,
+        id: String,
+        role: app_model::Role,
+    ) -> Result<app_model::User, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+
+        let user_resp = conn
+            .interact(move |conn| {
+                diesel::update(scheme::users::table)
+                    .filter(scheme::users::uuid.eq(id))
+                    .set((
+                        scheme::users::role.eq(role.code()),
+                        scheme::users::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .returning(db_model::User::as_returning())
+                    .get_result(conn)
+            })
+            .await??;
+
+        Ok(user_resp.into())
+    }
+}
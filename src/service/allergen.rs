@@ -0,0 +1,108 @@
+use crate::{
+    model::allergen::*,
+    model::{Role, SearchResult, ServiceError},
+    repository,
+};
+use std::{collections::HashSet, error::Error, sync::Arc};
+
+use super::policy;
+
+pub struct Config {
+    pub allergen_storage: Arc<repository::AllergenRepository>,
+}
+
+pub struct AllergenService {
+    pub allergen_storage: Arc<repository::AllergenRepository>,
+}
+
+impl AllergenService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            allergen_storage: cfg.allergen_storage,
+        }
+    }
+
+    pub async fn create(&self, mut q: CreateAllergenCommand) -> Result<Allergen, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        q.code = q.code.trim().to_lowercase();
+
+        if q.code.is_empty()
+            || !q
+                .code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(
+                ServiceError::Invalid(format!("invalid allergen code {:?}", q.code)).into(),
+            );
+        }
+
+        if q.name.trim().is_empty() {
+            return Err(ServiceError::Invalid("allergen name must not be empty".into()).into());
+        }
+
+        self.allergen_storage.create(q).await
+    }
+
+    /// Deletes a custom allergen no recipe declares, as recipes with
+    /// unknown allergens can't be read.
+    pub async fn delete(&self, q: DeleteAllergenCommand) -> Result<Allergen, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        let recipe_count = self.allergen_storage.recipe_count(q.code.clone()).await?;
+        if recipe_count > 0 {
+            return Err(ServiceError::Invalid(format!(
+                "allergen {} is declared by {} recipes",
+                q.code, recipe_count
+            ))
+            .into());
+        }
+
+        let code = q.code.clone();
+        self.allergen_storage.delete(q).await.map_err(|err| {
+            if repository::is_not_found(err.as_ref()) {
+                return ServiceError::Invalid(format!("custom allergen {} not found", code)).into();
+            }
+
+            err
+        })
+    }
+
+    pub async fn search(
+        &self,
+        q: AllergenSearchQuery,
+    ) -> Result<SearchResult<Allergen>, Box<dyn Error>> {
+        self.allergen_storage.search(q).await
+    }
+
+    /// Resolves allergen codes into catalogue entries, failing on unknown ones.
+    pub async fn resolve(&self, codes: &[String]) -> Result<Vec<Allergen>, Box<dyn Error>> {
+        if codes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let found = self
+            .search(AllergenSearchQuery {
+                codes: Some(codes.to_vec()),
+            })
+            .await?;
+
+        let known: HashSet<&str> = found.items.iter().map(|item| item.code.as_str()).collect();
+        let unknown: Vec<&str> = codes
+            .iter()
+            .map(String::as_str)
+            .filter(|code| !known.contains(code))
+            .collect();
+
+        if !unknown.is_empty() {
+            return Err(ServiceError::Invalid(format!(
+                "unknown allergens: {}",
+                unknown.join(", ")
+            ))
+            .into());
+        }
+
+        Ok(found.items)
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    model::{
+        ApiKey, ApiKeyIdentity, ApiKeysQuery, CreateApiKeyCommand, IssuedApiKey,
+        RevokeApiKeyCommand, RotateApiKeyCommand, ServiceError,
+    },
+    repository::ApiKeyRepository,
+};
+
+use super::{
+    auth::{hash_token, new_token},
+    dedup,
+};
+
+/// Marks API keys so they are recognisable, e.g. by secret scanners.
+const KEY_PREFIX: &str = "ck_";
+/// Characters of the secret kept to identify the key.
+const VISIBLE_CHARS: usize = 8;
+const DEFAULT_RATE_LIMIT: i32 = 60;
+const MAX_RATE_LIMIT: i32 = 6000;
+
+pub struct Config {
+    pub api_key_storage: Arc<ApiKeyRepository>,
+}
+
+pub struct ApiKeyService {
+    pub api_key_storage: Arc<ApiKeyRepository>,
+    /// Requests per key in the current minute, kept in memory so limits
+    /// apply per instance.
+    usage: Mutex<HashMap<String, (i64, i32)>>,
+}
+
+impl ApiKeyService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            api_key_storage: cfg.api_key_storage,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn list(&self, q: ApiKeysQuery) -> Result<Vec<ApiKey>, Box<dyn Error>> {
+        self.api_key_storage.list(q).await
+    }
+
+    /// Creates a key, scopes are limited to what the owner's role allows.
+    pub async fn create(
+        &self,
+        mut item: CreateApiKeyCommand,
+    ) -> Result<IssuedApiKey, Box<dyn Error>> {
+        item.name = item.name.trim().to_string();
+
+        item.scopes = dedup(item.scopes);
+        if item.scopes.is_empty() {
+            return Err(ServiceError::Invalid("at least one scope is required".into()).into());
+        }
+        for scope in &item.scopes {
+            let role = scope.required_role();
+            if item.actor.role < role {
+                return Err(ServiceError::Forbidden(format!(
+                    "{} scope requires the {} role",
+                    scope.code(),
+                    role.code()
+                ))
+                .into());
+            }
+        }
+
+        let rate_limit = item.rate_limit.unwrap_or(DEFAULT_RATE_LIMIT);
+        if !(1..=MAX_RATE_LIMIT).contains(&rate_limit) {
+            return Err(ServiceError::Invalid(format!(
+                "rateLimit must be between 1 and {}",
+                MAX_RATE_LIMIT
+            ))
+            .into());
+        }
+
+        let secret = new_secret();
+        let key = self
+            .api_key_storage
+            .create(
+                item,
+                visible_prefix(&secret),
+                hash_token(&secret),
+                rate_limit,
+            )
+            .await?;
+
+        Ok(IssuedApiKey { key, secret })
+    }
+
+    pub async fn rotate(&self, q: RotateApiKeyCommand) -> Result<IssuedApiKey, Box<dyn Error>> {
+        let secret = new_secret();
+        let key = self
+            .api_key_storage
+            .rotate(q, visible_prefix(&secret), hash_token(&secret))
+            .await?;
+
+        Ok(IssuedApiKey { key, secret })
+    }
+
+    pub async fn revoke(&self, q: RevokeApiKeyCommand) -> Result<ApiKey, Box<dyn Error>> {
+        let key = self.api_key_storage.revoke(q).await?;
+
+        self.usage.lock().unwrap().remove(&key.id);
+
+        Ok(key)
+    }
+
+    /// Resolves a secret to its key and counts the request against the
+    /// key's rate limit. Scopes the owner's role no longer allows are dropped.
+    pub async fn authenticate(&self, secret: &str) -> Result<ApiKeyIdentity, Box<dyn Error>> {
+        let (key, role) = self
+            .api_key_storage
+            .find_active(hash_token(secret))
+            .await?
+            .ok_or_else(|| ServiceError::Unauthorized("invalid API key".into()))?;
+
+        self.count_request(&key)?;
+
+        // Recording every request would turn reads into writes, a minute is
+        // precise enough.
+        let now = Utc::now().naive_utc();
+        if key
+            .last_used_at
+            .is_none_or(|used_at| now - used_at >= Duration::minutes(1))
+        {
+            self.api_key_storage.touch(key.id.clone(), now).await?;
+        }
+
+        Ok(ApiKeyIdentity {
+            key_id: key.id,
+            user_id: key.owner_id,
+            role,
+            scopes: key
+                .scopes
+                .into_iter()
+                .filter(|scope| role >= scope.required_role())
+                .collect(),
+        })
+    }
+
+    fn count_request(&self, key: &ApiKey) -> Result<(), Box<dyn Error>> {
+        let minute = Utc::now().timestamp() / 60;
+
+        let mut usage = self.usage.lock().unwrap();
+        let (window, count) = usage.entry(key.id.clone()).or_insert((minute, 0));
+        if *window != minute {
+            *window = minute;
+            *count = 0;
+        }
+        *count += 1;
+
+        if *count > key.rate_limit {
+            return Err(ServiceError::RateLimited(format!(
+                "rate limit of {} requests per minute exceeded",
+                key.rate_limit
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+fn new_secret() -> String {
+    format!("{}{}", KEY_PREFIX, new_token())
+}
+
+fn visible_prefix(secret: &str) -> String {
+    secret
+        .chars()
+        .take(KEY_PREFIX.len() + VISIBLE_CHARS)
+        .collect()
+}
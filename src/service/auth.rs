@@ -0,0 +1,268 @@
+use std::{error::Error, sync::Arc};
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    model::{
+        AuthTokens, Identity, LoginCommand, LogoutCommand, RefreshTokenCommand, RegisterCommand,
+        Role, ServiceError, SetRoleCommand, User,
+    },
+    repository::{SessionRepository, UserRepository},
+};
+
+use super::policy;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+pub struct Config {
+    pub user_storage: Arc<UserRepository>,
+    pub session_storage: Arc<SessionRepository>,
+    /// Secret the access tokens are signed with.
+    pub jwt_secret: String,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+}
+
+pub struct AuthService {
+    pub user_storage: Arc<UserRepository>,
+    pub session_storage: Arc<SessionRepository>,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+}
+
+/// Claims of an access token.
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    sid: String,
+    iat: i64,
+    exp: i64,
+}
+
+impl AuthService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            user_storage: cfg.user_storage,
+            session_storage: cfg.session_storage,
+            encoding_key: EncodingKey::from_secret(cfg.jwt_secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(cfg.jwt_secret.as_bytes()),
+            access_token_ttl: cfg.access_token_ttl,
+            refresh_token_ttl: cfg.refresh_token_ttl,
+        }
+    }
+
+    pub async fn register(&self, item: RegisterCommand) -> Result<AuthTokens, Box<dyn Error>> {
+        let email = normalize_email(&item.email)?;
+
+        if item.password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(ServiceError::Invalid(format!(
+                "password must have at least {} characters",
+                MIN_PASSWORD_LENGTH
+            ))
+            .into());
+        }
+
+        if self
+            .user_storage
+            .find_by_email(email.clone())
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::Invalid("email is already registered".into()).into());
+        }
+
+        let password_hash = hash_password(item.password).await?;
+        let user = self
+            .user_storage
+            .create(
+                email,
+                item.name.trim().to_string(),
+                password_hash,
+                Role::Viewer,
+            )
+            .await?;
+
+        self.start_session(user).await
+    }
+
+    pub async fn login(&self, item: LoginCommand) -> Result<AuthTokens, Box<dyn Error>> {
+        let invalid = || ServiceError::Unauthorized("invalid email or password".into()).into();
+
+        let email = normalize_email(&item.email).map_err(|_| invalid())?;
+        let (user, password_hash) = self
+            .user_storage
+            .find_by_email(email)
+            .await?
+            .ok_or_else(invalid)?;
+
+        if !verify_password(item.password, password_hash).await? {
+            return Err(invalid());
+        }
+
+        self.start_session(user).await
+    }
+
+    /// Rotates the refresh token, a token can be used only once.
+    pub async fn refresh(&self, item: RefreshTokenCommand) -> Result<AuthTokens, Box<dyn Error>> {
+        let refresh_token = new_token();
+
+        let identity = self
+            .session_storage
+            .rotate(
+                hash_token(&item.refresh_token),
+                hash_token(&refresh_token),
+                (Utc::now() + self.refresh_token_ttl).naive_utc(),
+            )
+            .await?
+            .ok_or_else(|| ServiceError::Unauthorized("invalid or expired refresh token".into()))?;
+
+        let user = self.user_storage.fetch(identity.user_id.clone()).await?;
+
+        Ok(AuthTokens {
+            access_token: self.access_token(&identity)?,
+            refresh_token,
+            expires_in: self.access_token_ttl.num_seconds(),
+            user,
+        })
+    }
+
+    pub async fn logout(&self, q: LogoutCommand) -> Result<(), Box<dyn Error>> {
+        self.session_storage.revoke(q).await
+    }
+
+    pub async fn me(&self, identity: &Identity) -> Result<User, Box<dyn Error>> {
+        self.user_storage.fetch(identity.user_id.clone()).await
+    }
+
+    /// Assigns a role, admins only. Admins can't demote themselves so there
+    /// is always one left.
+    pub async fn set_role(&self, q: SetRoleCommand) -> Result<User, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Admin)?;
+
+        if q.actor.user_id == q.user_id {
+            return Err(ServiceError::Forbidden("can't change your own role".into()).into());
+        }
+
+        self.user_storage.set_role(q.user_id, q.role).await
+    }
+
+    /// Checks the signature and expiry of an access token and that its
+    /// session was not revoked. The role is read fresh so changes apply
+    /// immediately.
+    pub async fn authenticate(&self, access_token: &str) -> Result<Identity, Box<dyn Error>> {
+        let invalid = || ServiceError::Unauthorized("invalid or expired access token".into());
+
+        let claims = decode::<Claims>(access_token, &self.decoding_key, &Validation::default())
+            .map_err(|_| invalid())?
+            .claims;
+        let role = self
+            .session_storage
+            .active_role(claims.sub.clone(), claims.sid.clone())
+            .await?
+            .ok_or_else(invalid)?;
+
+        Ok(Identity {
+            user_id: claims.sub,
+            session_id: claims.sid,
+            role,
+        })
+    }
+
+    async fn start_session(&self, user: User) -> Result<AuthTokens, Box<dyn Error>> {
+        let refresh_token = new_token();
+
+        let session_id = self
+            .session_storage
+            .create(
+                user.id.clone(),
+                hash_token(&refresh_token),
+                (Utc::now() + self.refresh_token_ttl).naive_utc(),
+            )
+            .await?;
+        let identity = Identity {
+            user_id: user.id.clone(),
+            session_id,
+            role: user.role,
+        };
+
+        Ok(AuthTokens {
+            access_token: self.access_token(&identity)?,
+            refresh_token,
+            expires_in: self.access_token_ttl.num_seconds(),
+            user,
+        })
+    }
+
+    fn access_token(&self, identity: &Identity) -> Result<String, Box<dyn Error>> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: identity.user_id.clone(),
+            sid: identity.session_id.clone(),
+            iat: now.timestamp(),
+            exp: (now + self.access_token_ttl).timestamp(),
+        };
+
+        Ok(encode(&Header::default(), &claims, &self.encoding_key)?)
+    }
+}
+
+fn normalize_email(email: &str) -> Result<String, Box<dyn Error>> {
+    let email = email.trim().to_lowercase();
+
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Ok(email),
+        _ => Err(ServiceError::Invalid("invalid email".into()).into()),
+    }
+}
+
+/// Hashes off the async runtime as Argon2 is slow on purpose.
+async fn hash_password(password: String) -> Result<String, Box<dyn Error>> {
+    let res = tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+    })
+    .await?;
+
+    res.map_err(|err| err.to_string().into())
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool, Box<dyn Error>> {
+    let res = tokio::task::spawn_blocking(move || {
+        let hash = PasswordHash::new(&password_hash)?;
+
+        Ok::<bool, argon2::password_hash::Error>(
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok(),
+        )
+    })
+    .await?;
+
+    res.map_err(|err| err.to_string().into())
+}
+
+/// A random secret, hex encoded.
+pub(super) fn new_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+
+    hex::encode(bytes)
+}
+
+/// Secrets are stored as their SHA-256 hash only.
+pub(super) fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,6 +1,11 @@
-use crate::{model::category::*, model::SearchResult, repository};
+use crate::{
+    model::category::*, model::Role, model::SearchResult, model::ServiceError, repository,
+};
+use chrono::NaiveDateTime;
 use std::{error::Error, sync::Arc};
 
+use super::{check_pagination, policy};
+
 pub struct Config {
     pub category_storage: Arc<repository::CategoryRepository>,
 }
@@ -20,22 +25,137 @@ impl CategoryService {
         self.category_storage.fetch(q).await
     }
 
-    pub async fn create(&self, q: CreateCategoryCommand) -> Result<Category, Box<dyn Error>> {
-        self.category_storage.create(q).await
+    pub async fn fetch_by_slug(&self, q: CategorySlugQuery) -> Result<Category, Box<dyn Error>> {
+        self.category_storage.fetch_by_slug(q).await
     }
 
-    pub async fn update(&self, q: UpdateCategoryCommand) -> Result<Category, Box<dyn Error>> {
-        self.category_storage.update(q).await
+    pub async fn create(&self, mut q: CreateCategoryCommand) -> Result<Category, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        q.slug = match q.slug.trim() {
+            "" => slugify(&q.name),
+            slug => slugify(slug),
+        };
+
+        if q.slug.is_empty() {
+            return Err(ServiceError::Invalid(format!(
+                "can't build a slug for category {:?}",
+                q.name
+            ))
+            .into());
+        }
+
+        let slug = q.slug.clone();
+        self.category_storage
+            .create(q)
+            .await
+            .map_err(|err| slug_taken(err, &slug))
+    }
+
+    pub async fn update(&self, mut q: UpdateCategoryCommand) -> Result<Category, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        if q.slug.is_none()
+            && q.name.is_none()
+            && q.description.is_none()
+            && q.image.is_none()
+            && q.sort_order.is_none()
+        {
+            return Err(ServiceError::Invalid("at least one field must be set".into()).into());
+        }
+
+        if let Some(slug) = q.slug.as_deref() {
+            let slug = slugify(slug);
+            if slug.is_empty() {
+                return Err(ServiceError::Invalid("category slug must not be empty".into()).into());
+            }
+            q.slug = Some(slug);
+        }
+
+        let slug = q.slug.clone().unwrap_or_default();
+        self.category_storage
+            .update(q)
+            .await
+            .map_err(|err| slug_taken(err, &slug))
     }
 
     pub async fn delete(&self, q: DeleteCategoryCommand) -> Result<Category, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        // recipes in the trash count too, restoring them needs the category
+        let recipe_count = self.category_storage.recipe_count(q.id.clone()).await?;
+        if recipe_count > 0 {
+            return Err(ServiceError::Invalid(format!(
+                "category with id {} is used by {} recipes",
+                q.id, recipe_count
+            ))
+            .into());
+        }
+
         self.category_storage.delete(q).await
     }
 
+    /// Hard-deletes categories deleted before `before`.
+    pub async fn purge(&self, before: NaiveDateTime) -> Result<i64, Box<dyn Error>> {
+        self.category_storage.purge(before).await
+    }
+
     pub async fn search(
         &self,
         q: CategorySearchQuery,
     ) -> Result<SearchResult<Category>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
         self.category_storage.search(q).await
     }
 }
+
+/// Reports a unique violation as a taken slug, the only unique column
+/// callers set. Other errors pass through.
+fn slug_taken(err: Box<dyn Error>, slug: &str) -> Box<dyn Error> {
+    if repository::is_unique_violation(err.as_ref()) {
+        return ServiceError::Invalid(format!("category slug {:?} is already taken", slug)).into();
+    }
+
+    err
+}
+
+/// Lowercases the value and collapses every run of non-alphanumeric
+/// characters into a single dash, e.g. "Soups & Stews" -> "soups-stews".
+fn slugify(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::slugify;
+
+    #[test]
+    fn slugify_collapses_separators() {
+        assert_eq!(slugify("Soups & Stews"), "soups-stews");
+        assert_eq!(slugify("  Quick -- Easy  "), "quick-easy");
+        assert_eq!(slugify("already-a-slug"), "already-a-slug");
+    }
+
+    #[test]
+    fn slugify_keeps_digits() {
+        assert_eq!(slugify("Top 10 Salads"), "top-10-salads");
+    }
+
+    #[test]
+    fn slugify_without_alphanumerics_is_empty() {
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify(" & -- !"), "");
+    }
+
+    #[test]
+    fn slugify_keeps_unicode_letters() {
+        assert_eq!(slugify("Crème Brûlée"), "crème-brûlée");
+        assert_eq!(slugify("Борщ и щи"), "борщ-и-щи");
+    }
+}
@@ -0,0 +1,252 @@
+use std::{collections::HashMap, error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        Collection, CollectionItem, CollectionQuery, CollectionSearchQuery,
+        CreateCollectionCommand, DeleteCollectionCommand, Recipe, RecipeSearchQuery,
+        RecipeVisibility, SearchResult, ServiceError, SharedCollectionQuery,
+        UpdateCollectionCommand,
+    },
+    repository::CollectionRepository,
+};
+
+use super::{auth::new_token, check_pagination, RecipeService};
+
+const MAX_COLLECTION_ITEMS: usize = 500;
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub collection_storage: Arc<CollectionRepository>,
+}
+
+pub struct CollectionService {
+    pub recipe_service: Arc<RecipeService>,
+    pub collection_storage: Arc<CollectionRepository>,
+}
+
+impl CollectionService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            collection_storage: cfg.collection_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: CollectionQuery) -> Result<Collection, Box<dyn Error>> {
+        let viewer_id = q.owner_id.clone();
+        let mut item = self.collection_storage.fetch(q).await?;
+
+        self.fill(std::slice::from_mut(&mut item), Some(viewer_id))
+            .await?;
+
+        Ok(item)
+    }
+
+    /// A shared collection, viewable without an account.
+    pub async fn fetch_shared(
+        &self,
+        q: SharedCollectionQuery,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let mut item = self.collection_storage.fetch_shared(q).await?;
+
+        self.fill(std::slice::from_mut(&mut item), None).await?;
+
+        Ok(item)
+    }
+
+    pub async fn create(
+        &self,
+        mut item: CreateCollectionCommand,
+    ) -> Result<Collection, Box<dyn Error>> {
+        item.name = check_name(&item.name)?;
+        self.check_items(&item.items, &item.owner_id).await?;
+
+        let viewer_id = item.owner_id.clone();
+        let share_token = item.shared.then(new_token);
+        let mut res = self.collection_storage.create(item, share_token).await?;
+
+        self.fill(std::slice::from_mut(&mut res), Some(viewer_id))
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn update(
+        &self,
+        mut q: UpdateCollectionCommand,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let current = self
+            .collection_storage
+            .fetch(CollectionQuery {
+                id: q.id.clone(),
+                owner_id: q.owner_id.clone(),
+            })
+            .await?;
+
+        if let Some(name) = q.name.as_deref() {
+            q.name = Some(check_name(name)?);
+        }
+        if let Some(items) = q.items.as_deref() {
+            self.check_items(items, &q.owner_id).await?;
+        }
+
+        let share_token = match q.shared {
+            Some(true) if current.share_token.is_none() => Some(Some(new_token())),
+            Some(false) => Some(None),
+            _ => None,
+        };
+
+        let viewer_id = q.owner_id.clone();
+        let mut res = self.collection_storage.update(q, share_token).await?;
+
+        self.fill(std::slice::from_mut(&mut res), Some(viewer_id))
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn delete(&self, q: DeleteCollectionCommand) -> Result<Collection, Box<dyn Error>> {
+        self.collection_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: CollectionSearchQuery,
+    ) -> Result<SearchResult<Collection>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        let viewer_id = q.owner_id.clone();
+        let mut res = self.collection_storage.search(q).await?;
+
+        self.fill(&mut res.items, Some(viewer_id)).await?;
+
+        Ok(res)
+    }
+
+    /// Checks the items of the owner's collection, who may list published
+    /// recipes and their own ones, like with favourites.
+    async fn check_items(
+        &self,
+        items: &[CollectionItem],
+        owner_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        if items.len() > MAX_COLLECTION_ITEMS {
+            return Err(ServiceError::Invalid(format!(
+                "a collection holds at most {} recipes",
+                MAX_COLLECTION_ITEMS
+            ))
+            .into());
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            if items[..i]
+                .iter()
+                .any(|other| other.recipe_id == item.recipe_id)
+            {
+                return Err(ServiceError::Invalid(format!(
+                    "recipe with id {} is listed twice",
+                    item.recipe_id
+                ))
+                .into());
+            }
+        }
+
+        let recipes = self.recipes(items, Some(owner_id.to_string())).await?;
+        if let Some(item) = items
+            .iter()
+            .find(|item| !recipes.contains_key(&item.recipe_id))
+        {
+            return Err(ServiceError::Invalid(format!(
+                "recipe with id {} not found",
+                item.recipe_id
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Attaches the recipes to the items of the collections.
+    async fn fill(
+        &self,
+        collections: &mut [Collection],
+        viewer_id: Option<String>,
+    ) -> Result<(), Box<dyn Error>> {
+        let items: Vec<CollectionItem> = collections
+            .iter()
+            .flat_map(|collection| collection.items.iter().cloned())
+            .collect();
+        let recipes = self.recipes(&items, viewer_id).await?;
+
+        for item in collections
+            .iter_mut()
+            .flat_map(|collection| collection.items.iter_mut())
+        {
+            item.recipe = recipes.get(&item.recipe_id).cloned();
+        }
+
+        Ok(())
+    }
+
+    async fn recipes(
+        &self,
+        items: &[CollectionItem],
+        viewer_id: Option<String>,
+    ) -> Result<HashMap<String, Recipe>, Box<dyn Error>> {
+        let mut ids: Vec<String> = items.iter().map(|item| item.recipe_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let res = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                ids: Some(ids),
+                visibility: visibility(viewer_id.clone()),
+                viewer_id,
+                ..RecipeSearchQuery::default()
+            })
+            .await?;
+
+        Ok(res
+            .items
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect())
+    }
+}
+
+/// Viewers see published recipes and their own ones, anonymous viewers of
+/// shared collections only published ones.
+fn visibility(viewer_id: Option<String>) -> RecipeVisibility {
+    viewer_id.map_or(
+        RecipeVisibility::Published,
+        RecipeVisibility::PublishedOrAuthoredBy,
+    )
+}
+
+fn check_name(name: &str) -> Result<String, Box<dyn Error>> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ServiceError::Invalid("name must not be empty".into()).into());
+    }
+
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visibility_includes_own_recipes() {
+        assert_eq!(visibility(None), RecipeVisibility::Published);
+        assert_eq!(
+            visibility(Some("u1".into())),
+            RecipeVisibility::PublishedOrAuthoredBy("u1".into())
+        );
+    }
+}
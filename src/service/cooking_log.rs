@@ -0,0 +1,114 @@
+use std::{error::Error, sync::Arc};
+
+use chrono::{Days, NaiveDate, Utc};
+
+use crate::{
+    model::{
+        CookingLogEntry, CookingLogSearchQuery, DeleteCookingLogCommand, LogCookingCommand,
+        RecipeQuery, SearchResult, ServiceError,
+    },
+    repository::CookingLogRepository,
+};
+
+use super::{check_pagination, nutrition::scale, RecipeService};
+
+/// Most servings a single entry may log.
+const MAX_SERVINGS: f64 = 100.0;
+
+const MAX_NOTES_LENGTH: usize = 2000;
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub cooking_log_storage: Arc<CookingLogRepository>,
+}
+
+pub struct CookingLogService {
+    pub recipe_service: Arc<RecipeService>,
+    pub cooking_log_storage: Arc<CookingLogRepository>,
+}
+
+impl CookingLogService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            cooking_log_storage: cfg.cooking_log_storage,
+        }
+    }
+
+    /// Logs the recipe as cooked, taking the eaten nutrients from its current
+    /// per serving values.
+    pub async fn log(
+        &self,
+        mut item: LogCookingCommand,
+    ) -> Result<CookingLogEntry, Box<dyn Error>> {
+        if !item.servings.is_finite() || item.servings <= 0.0 || item.servings > MAX_SERVINGS {
+            return Err(ServiceError::Invalid(format!(
+                "servings must be positive and at most {}",
+                MAX_SERVINGS
+            ))
+            .into());
+        }
+
+        check_date(item.cooked_on)?;
+
+        item.notes = item.notes.trim().to_string();
+        if item.notes.chars().count() > MAX_NOTES_LENGTH {
+            return Err(ServiceError::Invalid(format!(
+                "notes must not exceed {} characters",
+                MAX_NOTES_LENGTH
+            ))
+            .into());
+        }
+
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: item.recipe_id.clone(),
+                ..RecipeQuery::default()
+            })
+            .await
+            .map_err(|_| {
+                ServiceError::Invalid(format!("recipe with id {} not found", item.recipe_id))
+            })?;
+
+        item.recipe_title = recipe.title;
+        item.nutrients = scale(recipe.nutrients, item.servings);
+
+        self.cooking_log_storage.create(item).await
+    }
+
+    /// Recipe ids with the number of times they were cooked, see
+    /// [`CookingLogRepository::recipe_counts`].
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        self.cooking_log_storage.recipe_counts(owner_id).await
+    }
+
+    pub async fn delete(
+        &self,
+        q: DeleteCookingLogCommand,
+    ) -> Result<CookingLogEntry, Box<dyn Error>> {
+        self.cooking_log_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: CookingLogSearchQuery,
+    ) -> Result<SearchResult<CookingLogEntry>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.cooking_log_storage.search(q).await
+    }
+}
+
+/// Rejects dates in the future, with a day of slack for users ahead of the
+/// server's time zone.
+pub(super) fn check_date(date: NaiveDate) -> Result<(), Box<dyn Error>> {
+    if Utc::now().date_naive().checked_add_days(Days::new(1)) < Some(date) {
+        return Err(ServiceError::Invalid("date must not be in the future".into()).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,438 @@
+use std::{error::Error, sync::Arc};
+
+use chrono::{Datelike, Days, Months, NaiveDate, Utc};
+
+use crate::{
+    model::{
+        CookingLogEntry, CookingLogSearchQuery, DeleteFoodEntryCommand, DiaryDay, DiaryDayQuery,
+        DiaryEntry, DiaryEntryKind, FoodEntry, IntakeQuery, IntakeSummary, LogFoodCommand,
+        NutrientBasis, Nutrients, NutritionTargets, Pagination, PeriodSummary, Report,
+        ReportPeriod, ReportPeriodSummary, ReportQuery, ServiceError, SetGoalsCommand, Streak,
+    },
+    repository::{FoodEntryRepository, NutritionGoalRepository},
+};
+
+use super::{
+    cooking_log::check_date,
+    meal_plan::{check_targets, deviation},
+    nutrition::{add, scale},
+    CookingLogService,
+};
+
+const MAX_NAME_LENGTH: usize = 200;
+
+/// Longest range an intake summary covers.
+const MAX_INTAKE_DAYS: u64 = 92;
+
+/// Most periods a report covers.
+const MAX_REPORT_PERIODS: usize = 92;
+
+/// Relative difference to a goal a day may have and still be on target.
+const GOAL_TOLERANCE: f64 = 0.1;
+
+/// Days looked at for streaks, today included.
+const STREAK_DAYS: u64 = 365;
+
+pub struct Config {
+    pub cooking_log_service: Arc<CookingLogService>,
+    pub food_entry_storage: Arc<FoodEntryRepository>,
+    pub nutrition_goal_storage: Arc<NutritionGoalRepository>,
+}
+
+/// Food diary made of the cooking log and ad-hoc foods, with daily goals.
+pub struct DiaryService {
+    pub cooking_log_service: Arc<CookingLogService>,
+    pub food_entry_storage: Arc<FoodEntryRepository>,
+    pub nutrition_goal_storage: Arc<NutritionGoalRepository>,
+}
+
+/// Intake of a single day.
+struct DayIntake {
+    date: NaiveDate,
+    entries: i64,
+    nutrients: Nutrients,
+}
+
+impl DiaryService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            cooking_log_service: cfg.cooking_log_service,
+            food_entry_storage: cfg.food_entry_storage,
+            nutrition_goal_storage: cfg.nutrition_goal_storage,
+        }
+    }
+
+    /// Entries of a day with their totals and what is left of the goals.
+    pub async fn day(&self, q: DiaryDayQuery) -> Result<DiaryDay, Box<dyn Error>> {
+        let entries = self.entries(q.owner_id.clone(), q.date, q.date).await?;
+        let goals = self.nutrition_goal_storage.fetch(q.owner_id).await?;
+
+        let mut nutrients = Nutrients::default();
+        for entry in entries.iter() {
+            add(&mut nutrients, &entry.nutrients);
+        }
+
+        let remaining = goals.map(|goals| {
+            let over = deviation(&nutrients, &goals);
+            NutritionTargets {
+                kcal: -over.kcal,
+                proteins: over.proteins.map(|v| -v),
+                fats: over.fats.map(|v| -v),
+                carbohydrates: over.carbohydrates.map(|v| -v),
+            }
+        });
+
+        Ok(DiaryDay {
+            date: q.date,
+            entries,
+            nutrients,
+            goals,
+            remaining,
+        })
+    }
+
+    /// Logs a food that is not a recipe, its nutrients being the eaten
+    /// amount.
+    pub async fn log_food(&self, mut item: LogFoodCommand) -> Result<FoodEntry, Box<dyn Error>> {
+        check_date(item.eaten_on)?;
+
+        item.name = item.name.trim().to_string();
+        if item.name.is_empty() || item.name.chars().count() > MAX_NAME_LENGTH {
+            return Err(ServiceError::Invalid(format!(
+                "name must be between 1 and {} characters",
+                MAX_NAME_LENGTH
+            ))
+            .into());
+        }
+
+        let n = &item.nutrients;
+        if [
+            n.kcal,
+            n.proteins,
+            n.fats,
+            n.saturated_fat,
+            n.carbohydrates,
+            n.sugars,
+            n.fiber,
+            n.sodium,
+            n.cholesterol,
+        ]
+        .into_iter()
+        .chain(n.micronutrients.values().copied())
+        .any(|value| !value.is_finite() || value < 0.0)
+        {
+            return Err(ServiceError::Invalid("nutrients must not be negative".into()).into());
+        }
+        item.nutrients.basis = NutrientBasis::PerServing;
+
+        self.food_entry_storage.create(item).await
+    }
+
+    pub async fn delete_food(
+        &self,
+        q: DeleteFoodEntryCommand,
+    ) -> Result<FoodEntry, Box<dyn Error>> {
+        self.food_entry_storage.delete(q).await
+    }
+
+    pub async fn goals(
+        &self,
+        owner_id: String,
+    ) -> Result<Option<NutritionTargets>, Box<dyn Error>> {
+        self.nutrition_goal_storage.fetch(owner_id).await
+    }
+
+    pub async fn set_goals(
+        &self,
+        item: SetGoalsCommand,
+    ) -> Result<NutritionTargets, Box<dyn Error>> {
+        check_targets(&item.goals).map_err(|err| ServiceError::Invalid(err.to_string()))?;
+
+        self.nutrition_goal_storage.save(item).await
+    }
+
+    pub async fn clear_goals(&self, owner_id: String) -> Result<(), Box<dyn Error>> {
+        self.nutrition_goal_storage.delete(owner_id).await
+    }
+
+    /// Sums the eaten nutrients per day of the range.
+    pub async fn intake(&self, q: IntakeQuery) -> Result<IntakeSummary, Box<dyn Error>> {
+        if q.from > q.to {
+            return Err(ServiceError::Invalid("from must not be after to".into()).into());
+        }
+
+        if q.from.checked_add_days(Days::new(MAX_INTAKE_DAYS)) <= Some(q.to) {
+            return Err(ServiceError::Invalid(format!(
+                "range must not exceed {} days",
+                MAX_INTAKE_DAYS
+            ))
+            .into());
+        }
+
+        let days = self.daily(q.owner_id, q.from, q.to).await?;
+
+        let mut total = Nutrients::default();
+        for day in days.iter() {
+            add(&mut total, &day.nutrients);
+        }
+
+        Ok(IntakeSummary {
+            days: days
+                .into_iter()
+                .map(|day| PeriodSummary {
+                    start_date: day.date,
+                    end_date: day.date,
+                    nutrients: day.nutrients,
+                })
+                .collect(),
+            total,
+        })
+    }
+
+    /// Intake against the goals per day, week or month, with streaks.
+    pub async fn report(&self, q: ReportQuery) -> Result<Report, Box<dyn Error>> {
+        let today = Utc::now().date_naive();
+        let to = q.to.unwrap_or(today);
+        let from = match q.from {
+            Some(from) => from,
+            None => match q.period {
+                ReportPeriod::Day => to.checked_sub_days(Days::new(6)),
+                ReportPeriod::Week => to.checked_sub_days(Days::new(21)),
+                ReportPeriod::Month => to.checked_sub_months(Months::new(5)),
+            }
+            .unwrap_or(to),
+        };
+
+        if from > to {
+            return Err(ServiceError::Invalid("from must not be after to".into()).into());
+        }
+
+        let mut bounds = Vec::new();
+        let mut start = period_start(q.period, from);
+        while start <= to {
+            if bounds.len() == MAX_REPORT_PERIODS {
+                return Err(ServiceError::Invalid(format!(
+                    "report must not exceed {} periods",
+                    MAX_REPORT_PERIODS
+                ))
+                .into());
+            }
+
+            let next = next_period(q.period, start)
+                .ok_or_else(|| ServiceError::Invalid("date out of range".into()))?;
+            bounds.push((start, next.pred_opt().unwrap_or(start)));
+            start = next;
+        }
+
+        let goals = self
+            .nutrition_goal_storage
+            .fetch(q.owner_id.clone())
+            .await?;
+        let (first, last) = (bounds[0].0, bounds[bounds.len() - 1].1);
+        let days = self.daily(q.owner_id.clone(), first, last).await?;
+
+        let periods = bounds
+            .into_iter()
+            .map(|(start_date, end_date)| {
+                let period: Vec<&DayIntake> = days
+                    .iter()
+                    .filter(|day| day.date >= start_date && day.date <= end_date)
+                    .collect();
+                summarize(start_date, end_date, &period, goals.as_ref())
+            })
+            .collect();
+
+        let recent_from = today
+            .checked_sub_days(Days::new(STREAK_DAYS - 1))
+            .unwrap_or(today);
+        let recent = self.daily(q.owner_id, recent_from, today).await?;
+
+        Ok(Report {
+            period: q.period,
+            goals,
+            periods,
+            logging_streak: streak(&recent, |day| day.entries > 0),
+            goal_streak: match goals.as_ref() {
+                Some(goals) => streak(&recent, |day| on_target(day, goals)),
+                None => Streak::default(),
+            },
+        })
+    }
+
+    /// Entries of the owner within `from`..=`to`, by date and logging time.
+    async fn entries(
+        &self,
+        owner_id: String,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DiaryEntry>, Box<dyn Error>> {
+        let cooked = self
+            .cooking_log_service
+            .search(CookingLogSearchQuery {
+                owner_id: owner_id.clone(),
+                from: Some(from),
+                to: Some(to),
+                pagination: Pagination::default(),
+            })
+            .await?
+            .items;
+        let foods = self.food_entry_storage.list(owner_id, from, to).await?;
+
+        let mut entries: Vec<DiaryEntry> = cooked
+            .into_iter()
+            .map(from_cooking_log)
+            .chain(foods.into_iter().map(from_food))
+            .collect();
+        entries.sort_by_key(|entry| (entry.date, entry.created_at));
+
+        Ok(entries)
+    }
+
+    /// Intake per day of `from`..=`to`, days without entries included.
+    async fn daily(
+        &self,
+        owner_id: String,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DayIntake>, Box<dyn Error>> {
+        let mut days: Vec<DayIntake> = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| DayIntake {
+                date,
+                entries: 0,
+                nutrients: Nutrients::default(),
+            })
+            .collect();
+
+        for entry in self.entries(owner_id, from, to).await? {
+            let day = (entry.date - from).num_days() as usize;
+            if let Some(day) = days.get_mut(day) {
+                day.entries += 1;
+                add(&mut day.nutrients, &entry.nutrients);
+            }
+        }
+
+        Ok(days)
+    }
+}
+
+fn from_cooking_log(entry: CookingLogEntry) -> DiaryEntry {
+    DiaryEntry {
+        id: entry.id,
+        kind: DiaryEntryKind::Recipe,
+        recipe_id: Some(entry.recipe_id),
+        name: entry.recipe_title,
+        date: entry.cooked_on,
+        meal: entry.meal,
+        servings: Some(entry.servings),
+        nutrients: entry.nutrients,
+        created_at: entry.created_at,
+    }
+}
+
+fn from_food(entry: FoodEntry) -> DiaryEntry {
+    DiaryEntry {
+        id: entry.id,
+        kind: DiaryEntryKind::Food,
+        recipe_id: None,
+        name: entry.name,
+        date: entry.eaten_on,
+        meal: entry.meal,
+        servings: None,
+        nutrients: entry.nutrients,
+        created_at: entry.created_at,
+    }
+}
+
+fn period_start(period: ReportPeriod, date: NaiveDate) -> NaiveDate {
+    match period {
+        ReportPeriod::Day => date,
+        ReportPeriod::Week => date - Days::new(date.weekday().num_days_from_monday() as u64),
+        ReportPeriod::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_period(period: ReportPeriod, start: NaiveDate) -> Option<NaiveDate> {
+    match period {
+        ReportPeriod::Day => start.checked_add_days(Days::new(1)),
+        ReportPeriod::Week => start.checked_add_days(Days::new(7)),
+        ReportPeriod::Month => start.checked_add_months(Months::new(1)),
+    }
+}
+
+fn summarize(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    days: &[&DayIntake],
+    goals: Option<&NutritionTargets>,
+) -> ReportPeriodSummary {
+    let mut total = Nutrients::default();
+    let mut days_logged = 0;
+    let mut days_on_target = 0;
+
+    for day in days.iter() {
+        add(&mut total, &day.nutrients);
+        if day.entries > 0 {
+            days_logged += 1;
+        }
+        if goals.is_some_and(|goals| on_target(day, goals)) {
+            days_on_target += 1;
+        }
+    }
+
+    let average = if days_logged > 0 {
+        scale(total.clone(), 1.0 / days_logged as f64)
+    } else {
+        Nutrients::default()
+    };
+
+    ReportPeriodSummary {
+        start_date,
+        end_date,
+        days_logged,
+        days_on_target,
+        deviation: goals
+            .filter(|_| days_logged > 0)
+            .map(|goals| deviation(&average, goals)),
+        total,
+        average,
+    }
+}
+
+/// Whether a logged day is within [`GOAL_TOLERANCE`] of every goal.
+fn on_target(day: &DayIntake, goals: &NutritionTargets) -> bool {
+    let within = |value: f64, goal: f64| (value - goal).abs() <= goal * GOAL_TOLERANCE;
+
+    day.entries > 0
+        && within(day.nutrients.kcal, goals.kcal)
+        && goals
+            .proteins
+            .is_none_or(|goal| within(day.nutrients.proteins, goal))
+        && goals
+            .fats
+            .is_none_or(|goal| within(day.nutrients.fats, goal))
+        && goals
+            .carbohydrates
+            .is_none_or(|goal| within(day.nutrients.carbohydrates, goal))
+}
+
+/// Streak of days meeting `hit`, `days` being consecutive and ending today.
+fn streak(days: &[DayIntake], hit: impl Fn(&DayIntake) -> bool) -> Streak {
+    let mut longest = 0;
+    let mut run = 0;
+    for day in days.iter() {
+        run = if hit(day) { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+
+    let open = days.last().is_some_and(|today| today.entries == 0);
+    let current = days
+        .iter()
+        .rev()
+        .skip(open as usize)
+        .take_while(|day| hit(day))
+        .count() as i64;
+
+    Streak { current, longest }
+}
@@ -0,0 +1,138 @@
+use std::{error::Error, sync::Arc};
+
+use crate::model::{Diet, Ingredient, Nutrients};
+
+use super::{ingredient::Matcher, IngredientService};
+
+pub struct Config {
+    pub ingredient_service: Arc<IngredientService>,
+    /// Upper bound of net carbohydrates (carbohydrates minus fiber) per
+    /// serving for a recipe to be labelled keto.
+    pub keto_max_net_carbs: f64,
+}
+
+/// Facts a rule is evaluated against. `ingredients` holds the dictionary
+/// match for every ingredient line, `None` when a line was not recognised.
+struct Facts<'a> {
+    ingredients: Vec<Option<&'a Ingredient>>,
+    nutrients: &'a Nutrients,
+}
+
+trait Rule: Send + Sync {
+    fn diet(&self) -> Diet;
+    fn holds(&self, facts: &Facts) -> bool;
+}
+
+/// Holds when every ingredient is recognised and none of them is excluded.
+/// Unknown ingredients fail the rule so labels are only ever conservative.
+struct IngredientRule {
+    diet: Diet,
+    excludes: fn(&Ingredient) -> bool,
+}
+
+impl Rule for IngredientRule {
+    fn diet(&self) -> Diet {
+        self.diet
+    }
+
+    fn holds(&self, facts: &Facts) -> bool {
+        !facts.ingredients.is_empty()
+            && facts
+                .ingredients
+                .iter()
+                .all(|item| item.is_some_and(|item| !(self.excludes)(item)))
+    }
+}
+
+struct NetCarbsRule {
+    diet: Diet,
+    max: f64,
+}
+
+impl Rule for NetCarbsRule {
+    fn diet(&self) -> Diet {
+        self.diet
+    }
+
+    fn holds(&self, facts: &Facts) -> bool {
+        let net_carbs = facts.nutrients.carbohydrates - facts.nutrients.fiber;
+
+        facts.nutrients.kcal > 0.0 && net_carbs <= self.max
+    }
+}
+
+pub struct DietService {
+    pub ingredient_service: Arc<IngredientService>,
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl DietService {
+    pub fn new(cfg: Config) -> Self {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(IngredientRule {
+                diet: Diet::Vegan,
+                excludes: |item| item.animal,
+            }),
+            Box::new(IngredientRule {
+                diet: Diet::Vegetarian,
+                excludes: |item| item.meat,
+            }),
+            Box::new(NetCarbsRule {
+                diet: Diet::Keto,
+                max: cfg.keto_max_net_carbs,
+            }),
+            Box::new(IngredientRule {
+                diet: Diet::GlutenFree,
+                excludes: |item| item.gluten,
+            }),
+        ];
+
+        Self {
+            ingredient_service: cfg.ingredient_service,
+            rules,
+        }
+    }
+
+    /// Derives the dietary labels that apply to a recipe.
+    pub async fn classify(
+        &self,
+        ingredients: &[String],
+        nutrients: &Nutrients,
+    ) -> Result<Vec<Diet>, Box<dyn Error>> {
+        let matcher = self.ingredient_service.matcher().await?;
+
+        Ok(self.evaluate(&matcher, ingredients, nutrients))
+    }
+
+    /// Same as [`DietService::classify`] for a batch of recipes, loading the
+    /// ingredient dictionary once.
+    pub async fn classify_many(
+        &self,
+        items: &[(&[String], &Nutrients)],
+    ) -> Result<Vec<Vec<Diet>>, Box<dyn Error>> {
+        let matcher = self.ingredient_service.matcher().await?;
+
+        Ok(items
+            .iter()
+            .map(|(ingredients, nutrients)| self.evaluate(&matcher, ingredients, nutrients))
+            .collect())
+    }
+
+    fn evaluate(
+        &self,
+        matcher: &Matcher,
+        ingredients: &[String],
+        nutrients: &Nutrients,
+    ) -> Vec<Diet> {
+        let facts = Facts {
+            ingredients: ingredients.iter().map(|line| matcher.find(line)).collect(),
+            nutrients,
+        };
+
+        self.rules
+            .iter()
+            .filter(|rule| rule.holds(&facts))
+            .map(|rule| rule.diet())
+            .collect()
+    }
+}
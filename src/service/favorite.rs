@@ -0,0 +1,96 @@
+use std::{collections::HashMap, error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        FavoriteCommand, FavoritesQuery, Recipe, RecipeQuery, RecipeSearchQuery, RecipeVisibility,
+        SearchResult, ServiceError,
+    },
+    repository::FavoriteRepository,
+};
+
+use super::{check_pagination, RecipeService};
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub favorite_storage: Arc<FavoriteRepository>,
+}
+
+pub struct FavoriteService {
+    pub recipe_service: Arc<RecipeService>,
+    pub favorite_storage: Arc<FavoriteRepository>,
+}
+
+impl FavoriteService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            favorite_storage: cfg.favorite_storage,
+        }
+    }
+
+    pub async fn add(&self, item: FavoriteCommand) -> Result<(), Box<dyn Error>> {
+        self.recipe_service
+            .fetch(RecipeQuery {
+                id: item.recipe_id.clone(),
+                visibility: RecipeVisibility::PublishedOrAuthoredBy(item.owner_id.clone()),
+                ..RecipeQuery::default()
+            })
+            .await
+            .map_err(|_| {
+                ServiceError::Invalid(format!("recipe with id {} not found", item.recipe_id))
+            })?;
+
+        self.favorite_storage.add(item).await
+    }
+
+    /// Recipe ids with the number of users saving them, see
+    /// [`FavoriteRepository::recipe_counts`].
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        self.favorite_storage.recipe_counts(owner_id).await
+    }
+
+    pub async fn remove(&self, item: FavoriteCommand) -> Result<(), Box<dyn Error>> {
+        self.favorite_storage.remove(item).await
+    }
+
+    /// Favourite recipes, most recently saved first. Recipes deleted since
+    /// are left out of the page but still counted.
+    pub async fn list(&self, q: FavoritesQuery) -> Result<SearchResult<Recipe>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        let owner_id = q.owner_id.clone();
+        let ids = self.favorite_storage.list(q).await?;
+        if ids.items.is_empty() {
+            return Ok(SearchResult {
+                count: ids.count,
+                items: Vec::new(),
+            });
+        }
+
+        let mut recipes: HashMap<String, Recipe> = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                ids: Some(ids.items.clone()),
+                visibility: RecipeVisibility::PublishedOrAuthoredBy(owner_id.clone()),
+                viewer_id: Some(owner_id),
+                ..RecipeSearchQuery::default()
+            })
+            .await?
+            .items
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect();
+
+        Ok(SearchResult {
+            count: ids.count,
+            items: ids
+                .items
+                .iter()
+                .filter_map(|id| recipes.remove(id))
+                .collect(),
+        })
+    }
+}
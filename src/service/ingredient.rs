@@ -0,0 +1,561 @@
+use crate::{
+    model::ingredient::*,
+    model::{Role, SearchResult, ServiceError},
+    repository,
+};
+use std::{collections::HashMap, error::Error, sync::Arc};
+
+use super::{check_pagination, policy};
+
+pub struct Config {
+    pub ingredient_storage: Arc<repository::IngredientRepository>,
+}
+
+pub struct IngredientService {
+    pub ingredient_storage: Arc<repository::IngredientRepository>,
+}
+
+impl IngredientService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            ingredient_storage: cfg.ingredient_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: IngredientQuery) -> Result<Ingredient, Box<dyn Error>> {
+        self.ingredient_storage.fetch(q).await
+    }
+
+    pub async fn create(
+        &self,
+        mut q: CreateIngredientCommand,
+    ) -> Result<Ingredient, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        q.name = normalize(&q.name);
+        q.aliases = q.aliases.iter().map(|alias| normalize(alias)).collect();
+
+        if q.name.is_empty() {
+            return Err(ServiceError::Invalid("ingredient name must not be empty".into()).into());
+        }
+
+        self.ingredient_storage.create(q).await
+    }
+
+    pub async fn update(
+        &self,
+        mut q: UpdateIngredientCommand,
+    ) -> Result<Ingredient, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        q.name = q.name.map(|name| normalize(&name));
+        q.aliases = q
+            .aliases
+            .map(|aliases| aliases.iter().map(|alias| normalize(alias)).collect());
+
+        if q.name.as_deref() == Some("") {
+            return Err(ServiceError::Invalid("ingredient name must not be empty".into()).into());
+        }
+
+        self.ingredient_storage.update(q).await
+    }
+
+    pub async fn delete(&self, q: DeleteIngredientCommand) -> Result<Ingredient, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        self.ingredient_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: IngredientSearchQuery,
+    ) -> Result<SearchResult<Ingredient>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.ingredient_storage.search(q).await
+    }
+
+    /// Imports nutrition data from a CSV dump with a header row. Columns are
+    /// looked up by name, so USDA-style exports with extra columns work as
+    /// long as they contain a name and the energy and macro columns. Admins
+    /// only.
+    pub async fn import_csv(&self, q: ImportIngredientsCommand) -> Result<usize, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Admin)?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(q.data.as_bytes());
+
+        let headers = reader.headers()?.clone();
+        let column = |aliases: &[&str]| {
+            headers
+                .iter()
+                .position(|header| aliases.contains(&header.to_lowercase().as_str()))
+        };
+
+        let name = column(&["name", "description", "food", "ingredient"])
+            .ok_or_else(|| ServiceError::Invalid("csv has no name column".into()))?;
+        let kcal = column(&["kcal", "energy_kcal", "energy", "calories"])
+            .ok_or_else(|| ServiceError::Invalid("csv has no kcal column".into()))?;
+        let proteins = column(&["proteins", "protein", "protein_g"]);
+        let fats = column(&["fats", "fat", "total_fat", "fat_g", "total lipid (fat)"]);
+        let carbohydrates = column(&[
+            "carbohydrates",
+            "carbohydrate",
+            "carbs",
+            "carbohydrate_g",
+            "carbohydrate, by difference",
+        ]);
+        let fiber = column(&["fiber", "fibre", "fiber_g", "fiber, total dietary"]);
+        let saturated_fat = column(&[
+            "saturated_fat",
+            "saturates",
+            "saturated_fat_g",
+            "fatty acids, total saturated",
+        ]);
+        let sugars = column(&["sugars", "sugar", "sugars_g", "sugars, total"]);
+        let sodium = column(&["sodium", "sodium_mg", "sodium, na"]);
+        let cholesterol = column(&["cholesterol", "cholesterol_mg"]);
+        let grams_per_piece = column(&["grams_per_piece", "piece_g", "unit_weight"]);
+        let density = column(&["density", "g_per_ml"]);
+
+        let mut items: Vec<ImportIngredientCommand> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+
+        for (idx, record) in reader.records().enumerate() {
+            let record = record?;
+            let line = idx + 2;
+
+            let number = |col: Option<usize>| -> Result<Option<f64>, Box<dyn Error>> {
+                match col
+                    .and_then(|col| record.get(col))
+                    .filter(|v| !v.is_empty())
+                {
+                    Some(value) => value
+                        .replace(',', ".")
+                        .parse::<f64>()
+                        .map(Some)
+                        .map_err(|e| format!("line {}: {}: {:?}", line, e, value).into()),
+                    None => Ok(None),
+                }
+            };
+
+            let item = ImportIngredientCommand {
+                name: normalize(record.get(name).unwrap_or_default()),
+                nutrients: IngredientNutrients {
+                    proteins: number(proteins)?.unwrap_or_default(),
+                    fats: number(fats)?.unwrap_or_default(),
+                    saturated_fat: number(saturated_fat)?.unwrap_or_default(),
+                    carbohydrates: number(carbohydrates)?.unwrap_or_default(),
+                    sugars: number(sugars)?.unwrap_or_default(),
+                    fiber: number(fiber)?.unwrap_or_default(),
+                    sodium: number(sodium)?.unwrap_or_default(),
+                    cholesterol: number(cholesterol)?.unwrap_or_default(),
+                    kcal: number(Some(kcal))?.ok_or_else(|| {
+                        ServiceError::Invalid(format!("line {}: kcal is empty", line))
+                    })?,
+                },
+                grams_per_piece: number(grams_per_piece)?,
+                density: number(density)?,
+            };
+
+            if item.name.is_empty() {
+                return Err(ServiceError::Invalid(format!("line {}: name is empty", line)).into());
+            }
+
+            // later rows win, a single INSERT can't touch the same name twice
+            match positions.get(&item.name) {
+                Some(pos) => items[*pos] = item,
+                None => {
+                    positions.insert(item.name.clone(), items.len());
+                    items.push(item);
+                }
+            }
+        }
+
+        self.ingredient_storage.import(items).await
+    }
+
+    /// Loads the whole dictionary into a matcher for free-form ingredient lines.
+    pub async fn matcher(&self) -> Result<Matcher, Box<dyn Error>> {
+        let res = self
+            .ingredient_storage
+            .search(IngredientSearchQuery::default())
+            .await?;
+
+        Ok(Matcher::new(res.items))
+    }
+}
+
+/// Recognises dictionary entries inside recipe ingredient lines such as
+/// "200 g unsalted butter", preferring the longest matching name or alias.
+pub struct Matcher {
+    terms: Vec<(String, usize)>,
+    entries: Vec<Ingredient>,
+}
+
+impl Matcher {
+    pub(super) fn new(entries: Vec<Ingredient>) -> Self {
+        let mut terms: Vec<(String, usize)> = entries
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, entry)| {
+                std::iter::once(&entry.name)
+                    .chain(entry.aliases.iter())
+                    .map(move |term| (format!(" {} ", normalize(term)), idx))
+            })
+            .filter(|(term, _)| !term.trim().is_empty())
+            .collect();
+
+        terms.sort_by_key(|(term, _)| std::cmp::Reverse(term.len()));
+
+        Self { terms, entries }
+    }
+
+    pub fn find(&self, line: &str) -> Option<&Ingredient> {
+        let line = format!(" {} ", normalize(line));
+
+        self.terms
+            .iter()
+            .find(|(term, _)| line.contains(term.as_str()))
+            .map(|(_, idx)| &self.entries[*idx])
+    }
+
+    /// Parses a recipe ingredient line and names it after its dictionary
+    /// entry when there is one. `None` for lines without a name.
+    pub fn resolve(&self, line: &str) -> Option<ResolvedLine> {
+        let parsed = parse_line(line);
+        let found = self.find(&parsed.name);
+
+        let name = match found {
+            Some(ingredient) => ingredient.name.clone(),
+            None => normalize(&parsed.name),
+        };
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(ResolvedLine {
+            name,
+            aisle: found.map(|ingredient| ingredient.aisle.clone()),
+            amount: match (parsed.quantity, parsed.unit) {
+                (Some(quantity), Some(unit)) => Some((unit.kind(), quantity * unit.factor())),
+                _ => None,
+            },
+        })
+    }
+}
+
+/// Ingredient line matched against the dictionary. The amount is in grams,
+/// milliliters or pieces depending on the unit kind.
+pub struct ResolvedLine {
+    pub name: String,
+    pub aisle: Option<String>,
+    pub amount: Option<(UnitKind, f64)>,
+}
+
+/// Dictionary name of the ingredient, or the normalised input when unknown.
+pub fn canonical_name(matcher: &Matcher, name: &str) -> Result<String, Box<dyn Error>> {
+    let res = match matcher.find(name) {
+        Some(ingredient) => ingredient.name.clone(),
+        None => normalize(name),
+    };
+
+    if res.is_empty() {
+        return Err(ServiceError::Invalid("name must not be empty".into()).into());
+    }
+
+    Ok(res)
+}
+
+/// Lowercases the value and reduces punctuation to single spaces.
+pub fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '-'))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits an ingredient line like "1 1/2 cups milk" or "200g butter" into
+/// quantity, unit and the remaining name. A quantity without a known unit is
+/// read as a number of pieces, e.g. "2 eggs".
+pub fn parse_line(line: &str) -> IngredientLine {
+    let mut tokens: Vec<String> = line
+        .split_whitespace()
+        .flat_map(split_number_prefix)
+        .collect();
+    tokens.reverse();
+
+    let mut quantity: Option<f64> = None;
+    while let Some(value) = tokens.last().and_then(|token| parse_number(token)) {
+        quantity = Some(quantity.unwrap_or_default() + value);
+        tokens.pop();
+    }
+
+    let unit = match quantity {
+        Some(_) => match tokens.last().and_then(|token| parse_unit(token)) {
+            Some(unit) => {
+                tokens.pop();
+                Some(unit)
+            }
+            None => Some(Unit::Piece),
+        },
+        None => None,
+    };
+
+    if unit.is_some() && tokens.last().is_some_and(|token| token == "of") {
+        tokens.pop();
+    }
+
+    tokens.reverse();
+
+    IngredientLine {
+        quantity,
+        unit,
+        name: tokens.join(" "),
+    }
+}
+
+/// Splits tokens like "200g" into "200" and "g".
+fn split_number_prefix(token: &str) -> Vec<String> {
+    let idx = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',' || c == '/'))
+        .unwrap_or(token.len());
+
+    if idx == 0 || idx == token.len() {
+        return vec![token.to_string()];
+    }
+
+    vec![token[..idx].to_string(), token[idx..].to_string()]
+}
+
+fn parse_number(token: &str) -> Option<f64> {
+    let vulgar = match token {
+        "¼" => Some(0.25),
+        "½" => Some(0.5),
+        "¾" => Some(0.75),
+        "⅓" => Some(1.0 / 3.0),
+        "⅔" => Some(2.0 / 3.0),
+        _ => None,
+    };
+    if vulgar.is_some() {
+        return vulgar;
+    }
+
+    if let Some((num, den)) = token.split_once('/') {
+        let num: f64 = num.parse().ok()?;
+        let den: f64 = den.parse().ok()?;
+        return (den != 0.0).then(|| num / den);
+    }
+
+    token
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+fn parse_unit(token: &str) -> Option<Unit> {
+    let token = token.trim_end_matches('.').to_lowercase();
+
+    let unit = match token.as_str() {
+        "mg" | "milligram" | "milligrams" => Unit::Milligram,
+        "g" | "gr" | "gram" | "grams" => Unit::Gram,
+        "kg" | "kilogram" | "kilograms" => Unit::Kilogram,
+        "oz" | "ounce" | "ounces" => Unit::Ounce,
+        "lb" | "lbs" | "pound" | "pounds" => Unit::Pound,
+        "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" => Unit::Milliliter,
+        "l" | "liter" | "liters" | "litre" | "litres" => Unit::Liter,
+        "tsp" | "teaspoon" | "teaspoons" => Unit::Teaspoon,
+        "tbsp" | "tablespoon" | "tablespoons" => Unit::Tablespoon,
+        "cup" | "cups" => Unit::Cup,
+        "pc" | "pcs" | "piece" | "pieces" => Unit::Piece,
+        _ => return None,
+    };
+
+    Some(unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Actor;
+
+    fn entry(name: &str, aliases: &[&str], aisle: &str) -> Ingredient {
+        Ingredient {
+            name: name.to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            aisle: aisle.to_string(),
+            ..Ingredient::default()
+        }
+    }
+
+    #[test]
+    fn parse_line_splits_quantity_unit_and_name() {
+        let cases = [
+            ("200 g butter", Some(200.0), Some(Unit::Gram), "butter"),
+            ("200g butter", Some(200.0), Some(Unit::Gram), "butter"),
+            ("1,5 kg flour", Some(1.5), Some(Unit::Kilogram), "flour"),
+            ("1 1/2 cups milk", Some(1.5), Some(Unit::Cup), "milk"),
+            ("½ cup of sugar", Some(0.5), Some(Unit::Cup), "sugar"),
+            ("1 ¼ l water", Some(1.25), Some(Unit::Liter), "water"),
+            (
+                "2 Tbsp. olive oil",
+                Some(2.0),
+                Some(Unit::Tablespoon),
+                "olive oil",
+            ),
+            ("3 tsp salt", Some(3.0), Some(Unit::Teaspoon), "salt"),
+            ("8 oz cheese", Some(8.0), Some(Unit::Ounce), "cheese"),
+            ("1 lb beef", Some(1.0), Some(Unit::Pound), "beef"),
+            ("250 ml cream", Some(250.0), Some(Unit::Milliliter), "cream"),
+            (
+                "500 mg saffron",
+                Some(500.0),
+                Some(Unit::Milligram),
+                "saffron",
+            ),
+            ("2 eggs", Some(2.0), Some(Unit::Piece), "eggs"),
+            ("3 pcs garlic", Some(3.0), Some(Unit::Piece), "garlic"),
+            ("salt to taste", None, None, "salt to taste"),
+            ("1/0 cup milk", None, None, "1/0 cup milk"),
+            ("", None, None, ""),
+        ];
+
+        for (line, quantity, unit, name) in cases {
+            assert_eq!(
+                parse_line(line),
+                IngredientLine {
+                    quantity,
+                    unit,
+                    name: name.to_string(),
+                },
+                "{:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn parse_number_reads_fractions() {
+        assert_eq!(parse_number("3/4"), Some(0.75));
+        assert_eq!(parse_number("⅔"), Some(2.0 / 3.0));
+        assert_eq!(parse_number("0,5"), Some(0.5));
+        assert_eq!(parse_number("-1"), None);
+        assert_eq!(parse_number("1/0"), None);
+        assert_eq!(parse_number("cup"), None);
+    }
+
+    #[test]
+    fn matcher_prefers_the_longest_name() {
+        let matcher = Matcher::new(vec![
+            entry("butter", &[], "dairy"),
+            entry("peanut butter", &["pb"], "pantry"),
+        ]);
+
+        let name = |line: &str| matcher.find(line).map(|found| found.name.clone());
+
+        assert_eq!(name("200 g peanut butter"), Some("peanut butter".into()));
+        assert_eq!(name("2 tbsp PB"), Some("peanut butter".into()));
+        assert_eq!(name("50g unsalted butter, softened"), Some("butter".into()));
+        assert_eq!(name("1 cup buttermilk"), None);
+        assert_eq!(name("salt"), None);
+    }
+
+    #[test]
+    fn resolve_converts_amounts_to_base_units() {
+        let matcher = Matcher::new(vec![entry("milk", &[], "dairy")]);
+
+        let milk = matcher.resolve("2 cups milk").unwrap();
+        assert_eq!(milk.name, "milk");
+        assert_eq!(milk.aisle.as_deref(), Some("dairy"));
+        assert_eq!(
+            milk.amount,
+            Some((UnitKind::Volume, 2.0 * Unit::Cup.factor()))
+        );
+
+        let unknown = matcher.resolve("1 kg Blood Oranges").unwrap();
+        assert_eq!(unknown.name, "blood oranges");
+        assert_eq!(unknown.aisle, None);
+        assert_eq!(unknown.amount, Some((UnitKind::Mass, 1000.0)));
+
+        let unmeasured = matcher.resolve("milk to taste").unwrap();
+        assert_eq!(unmeasured.amount, None);
+
+        assert!(matcher.resolve("2 g").is_none());
+    }
+
+    fn is_forbidden(err: Box<dyn Error>) -> bool {
+        matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::Forbidden(_))
+        )
+    }
+
+    #[test]
+    fn changes_require_editor_and_import_admin() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // the pool connects on first use, the role checks fail before that
+            let manager = deadpool_diesel::postgres::Manager::new(
+                "postgres://localhost/unused",
+                deadpool_diesel::Runtime::Tokio1,
+            );
+            let pool = deadpool_diesel::postgres::Pool::builder(manager)
+                .build()
+                .unwrap();
+            let service = IngredientService::new(Config {
+                ingredient_storage: Arc::new(
+                    repository::IngredientRepository::new(Arc::new(pool)).await,
+                ),
+            });
+
+            let viewer = Actor {
+                user_id: "u1".into(),
+                role: Role::Viewer,
+            };
+            let editor = Actor {
+                user_id: "u2".into(),
+                role: Role::Editor,
+            };
+
+            for actor in [Actor::default(), viewer] {
+                let create = service
+                    .create(CreateIngredientCommand {
+                        name: "salt".into(),
+                        actor: actor.clone(),
+                        ..CreateIngredientCommand::default()
+                    })
+                    .await;
+                assert!(is_forbidden(create.unwrap_err()));
+
+                let update = service
+                    .update(UpdateIngredientCommand {
+                        id: "1".into(),
+                        actor: actor.clone(),
+                        ..UpdateIngredientCommand::default()
+                    })
+                    .await;
+                assert!(is_forbidden(update.unwrap_err()));
+
+                let delete = service
+                    .delete(DeleteIngredientCommand {
+                        id: "1".into(),
+                        actor: actor.clone(),
+                    })
+                    .await;
+                assert!(is_forbidden(delete.unwrap_err()));
+            }
+
+            let import = service
+                .import_csv(ImportIngredientsCommand {
+                    data: "name,kcal\nsalt,0\n".into(),
+                    actor: editor,
+                })
+                .await;
+            assert!(is_forbidden(import.unwrap_err()));
+        });
+    }
+}
@@ -0,0 +1,626 @@
+use std::{error::Error, fmt::Write, sync::Arc};
+
+use crate::model::{
+    LabelFormat, LabelRow, NutrientBasis, Nutrients, NutritionLabel, NutritionLabelQuery,
+    RecipeQuery, ReferenceIntake, ServiceError,
+};
+
+use super::{nutrition::convert, RecipeService};
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    /// Daily energy intake percent daily values refer to by default.
+    pub reference_kcal: f64,
+}
+
+pub struct LabelService {
+    pub recipe_service: Arc<RecipeService>,
+    pub reference_kcal: f64,
+}
+
+/// One row of a label layout.
+struct Line {
+    name: &'static str,
+    unit: &'static str,
+    indent: bool,
+    amount: fn(&Nutrients) -> f64,
+    round: fn(f64) -> f64,
+    reference: Option<f64>,
+}
+
+impl LabelService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            reference_kcal: cfg.reference_kcal,
+        }
+    }
+
+    /// Builds the nutrition facts panel of a recipe from its per serving
+    /// nutrients.
+    pub async fn label(&self, q: NutritionLabelQuery) -> Result<NutritionLabel, Box<dyn Error>> {
+        let reference_kcal = q.reference_kcal.unwrap_or(self.reference_kcal);
+        if reference_kcal.is_nan() || reference_kcal <= 0.0 {
+            return Err(ServiceError::Invalid("reference intake must be positive".into()).into());
+        }
+
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: q.recipe_id,
+                basis: NutrientBasis::PerServing,
+                ..RecipeQuery::default()
+            })
+            .await?;
+
+        let per_serving = recipe.nutrients;
+        let per_100g = convert(per_serving.clone(), NutrientBasis::Per100g, recipe.servings).ok();
+
+        let lines = match q.format {
+            LabelFormat::Eu => eu_lines(ReferenceIntake::eu().with_kcal(reference_kcal)),
+            LabelFormat::Us => us_lines(ReferenceIntake::us().with_kcal(reference_kcal)),
+        };
+
+        let rows = lines
+            .into_iter()
+            .map(|line| {
+                let amount = (line.amount)(&per_serving);
+
+                LabelRow {
+                    name: line.name.to_string(),
+                    unit: line.unit.to_string(),
+                    indent: line.indent,
+                    per_serving: (line.round)(amount),
+                    per_100g: per_100g
+                        .as_ref()
+                        .map(|nutrients| (line.round)((line.amount)(nutrients))),
+                    daily_value: line
+                        .reference
+                        .map(|reference| (amount / reference * 100.0).round()),
+                }
+            })
+            .collect();
+
+        Ok(NutritionLabel {
+            format: q.format,
+            title: recipe.title,
+            servings: recipe.servings,
+            serving_weight: per_serving.serving_weight.map(|weight| weight.round()),
+            reference_kcal,
+            rows,
+        })
+    }
+}
+
+fn eu_lines(ri: ReferenceIntake) -> Vec<Line> {
+    vec![
+        Line {
+            name: "Energy",
+            unit: "kJ",
+            indent: false,
+            amount: |n| n.kcal * 4.184,
+            round: f64::round,
+            reference: Some(ri.kcal * 4.184),
+        },
+        Line {
+            name: "Energy",
+            unit: "kcal",
+            indent: false,
+            amount: |n| n.kcal,
+            round: f64::round,
+            reference: Some(ri.kcal),
+        },
+        Line {
+            name: "Fat",
+            unit: "g",
+            indent: false,
+            amount: |n| n.fats,
+            round: eu_grams,
+            reference: Some(ri.fats),
+        },
+        Line {
+            name: "of which saturates",
+            unit: "g",
+            indent: true,
+            amount: |n| n.saturated_fat,
+            round: eu_grams,
+            reference: Some(ri.saturated_fat),
+        },
+        Line {
+            name: "Carbohydrate",
+            unit: "g",
+            indent: false,
+            amount: |n| n.carbohydrates,
+            round: eu_grams,
+            reference: Some(ri.carbohydrates),
+        },
+        Line {
+            name: "of which sugars",
+            unit: "g",
+            indent: true,
+            amount: |n| n.sugars,
+            round: eu_grams,
+            reference: ri.sugars,
+        },
+        Line {
+            name: "Fibre",
+            unit: "g",
+            indent: false,
+            amount: |n| n.fiber,
+            round: eu_grams,
+            reference: ri.fiber,
+        },
+        Line {
+            name: "Protein",
+            unit: "g",
+            indent: false,
+            amount: |n| n.proteins,
+            round: eu_grams,
+            reference: Some(ri.proteins),
+        },
+        Line {
+            name: "Salt",
+            unit: "g",
+            indent: false,
+            amount: |n| n.sodium * 2.5 / 1000.0,
+            round: eu_salt,
+            reference: Some(ri.sodium * 2.5 / 1000.0),
+        },
+    ]
+}
+
+fn us_lines(dv: ReferenceIntake) -> Vec<Line> {
+    vec![
+        Line {
+            name: "Calories",
+            unit: "",
+            indent: false,
+            amount: |n| n.kcal,
+            round: us_calories,
+            reference: None,
+        },
+        Line {
+            name: "Total Fat",
+            unit: "g",
+            indent: false,
+            amount: |n| n.fats,
+            round: us_fat,
+            reference: Some(dv.fats),
+        },
+        Line {
+            name: "Saturated Fat",
+            unit: "g",
+            indent: true,
+            amount: |n| n.saturated_fat,
+            round: us_fat,
+            reference: Some(dv.saturated_fat),
+        },
+        Line {
+            name: "Cholesterol",
+            unit: "mg",
+            indent: false,
+            amount: |n| n.cholesterol,
+            round: us_cholesterol,
+            reference: dv.cholesterol,
+        },
+        Line {
+            name: "Sodium",
+            unit: "mg",
+            indent: false,
+            amount: |n| n.sodium,
+            round: us_sodium,
+            reference: Some(dv.sodium),
+        },
+        Line {
+            name: "Total Carbohydrate",
+            unit: "g",
+            indent: false,
+            amount: |n| n.carbohydrates,
+            round: us_grams,
+            reference: Some(dv.carbohydrates),
+        },
+        Line {
+            name: "Dietary Fiber",
+            unit: "g",
+            indent: true,
+            amount: |n| n.fiber,
+            round: us_grams,
+            reference: dv.fiber,
+        },
+        Line {
+            name: "Total Sugars",
+            unit: "g",
+            indent: true,
+            amount: |n| n.sugars,
+            round: us_grams,
+            reference: dv.sugars,
+        },
+        Line {
+            name: "Protein",
+            unit: "g",
+            indent: false,
+            amount: |n| n.proteins,
+            round: us_grams,
+            reference: None,
+        },
+    ]
+}
+
+fn round_to(value: f64, step: f64) -> f64 {
+    (value / step).round() * step
+}
+
+/// EU guidance: whole grams from 10 g, one decimal below, 0 under 0.5 g.
+fn eu_grams(value: f64) -> f64 {
+    match value {
+        v if v < 0.5 => 0.0,
+        v if v < 10.0 => (v * 10.0).round() / 10.0,
+        v => v.round(),
+    }
+}
+
+/// EU guidance: one decimal from 1 g, two decimals below, 0 under 0.0125 g.
+fn eu_salt(value: f64) -> f64 {
+    match value {
+        v if v < 0.0125 => 0.0,
+        v if v < 1.0 => (v * 100.0).round() / 100.0,
+        v => (v * 10.0).round() / 10.0,
+    }
+}
+
+/// FDA: 0 under 5, nearest 5 up to 50, nearest 10 above.
+fn us_calories(value: f64) -> f64 {
+    match value {
+        v if v < 5.0 => 0.0,
+        v if v <= 50.0 => round_to(v, 5.0),
+        v => round_to(v, 10.0),
+    }
+}
+
+/// FDA: 0 under 0.5 g, nearest 0.5 g up to 5 g, whole grams above.
+fn us_fat(value: f64) -> f64 {
+    match value {
+        v if v < 0.5 => 0.0,
+        v if v < 5.0 => round_to(v, 0.5),
+        v => v.round(),
+    }
+}
+
+/// FDA: 0 under 2 mg, nearest 5 mg above.
+fn us_cholesterol(value: f64) -> f64 {
+    match value {
+        v if v < 2.0 => 0.0,
+        v => round_to(v, 5.0),
+    }
+}
+
+/// FDA: 0 under 5 mg, nearest 5 mg up to 140 mg, nearest 10 mg above.
+fn us_sodium(value: f64) -> f64 {
+    match value {
+        v if v < 5.0 => 0.0,
+        v if v <= 140.0 => round_to(v, 5.0),
+        v => round_to(v, 10.0),
+    }
+}
+
+/// FDA: 0 under 0.5 g, whole grams above.
+fn us_grams(value: f64) -> f64 {
+    match value {
+        v if v < 0.5 => 0.0,
+        v => v.round(),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn amount(value: f64, unit: &str) -> String {
+    format!("{}{}", value, unit)
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map(|v| format!("{}%", v)).unwrap_or_default()
+}
+
+fn heading(label: &NutritionLabel) -> (&'static str, String) {
+    let serving = match label.serving_weight {
+        Some(weight) => format!("{} servings, {}g each", label.servings, weight),
+        None => format!("{} servings", label.servings),
+    };
+
+    match label.format {
+        LabelFormat::Eu => ("Nutrition declaration", serving),
+        LabelFormat::Us => ("Nutrition Facts", serving),
+    }
+}
+
+fn footnote(label: &NutritionLabel) -> String {
+    match label.format {
+        LabelFormat::Eu => format!(
+            "*Reference intake of an average adult ({} kcal)",
+            label.reference_kcal
+        ),
+        LabelFormat::Us => format!(
+            "*Percent Daily Values are based on a {} calorie diet",
+            label.reference_kcal
+        ),
+    }
+}
+
+/// Cells of a row: name, per 100 g (EU only), per serving and percentage.
+fn cells(label: &NutritionLabel, row: &LabelRow) -> Vec<String> {
+    let mut res = vec![row.name.clone()];
+
+    if label.format == LabelFormat::Eu {
+        res.push(
+            row.per_100g
+                .map(|v| amount(v, &row.unit))
+                .unwrap_or_default(),
+        );
+    }
+
+    res.push(amount(row.per_serving, &row.unit));
+    res.push(percent(row.daily_value));
+
+    res
+}
+
+fn columns(label: &NutritionLabel) -> Vec<&'static str> {
+    match label.format {
+        LabelFormat::Eu => vec!["", "per 100g", "per serving", "RI*"],
+        LabelFormat::Us => vec!["", "per serving", "% DV*"],
+    }
+}
+
+/// Renders a label as a standalone SVG image.
+pub fn render_svg(label: &NutritionLabel) -> String {
+    const WIDTH: usize = 320;
+    const ROW: usize = 22;
+
+    let (title, serving) = heading(label);
+    let columns = columns(label);
+    let xs: Vec<usize> = match label.format {
+        LabelFormat::Eu => vec![10, 175, 245, 310],
+        LabelFormat::Us => vec![10, 240, 310],
+    };
+    let height = ROW * (label.rows.len() + 5);
+
+    let mut res = String::new();
+    let _ = write!(
+        res,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Helvetica, Arial, sans-serif" font-size="12">"#,
+        w = WIDTH,
+        h = height,
+    );
+    let _ = write!(
+        res,
+        r#"<rect x="0.5" y="0.5" width="{}" height="{}" fill="white" stroke="black"/>"#,
+        WIDTH - 1,
+        height - 1,
+    );
+    let _ = write!(
+        res,
+        r#"<text x="10" y="{}" font-size="18" font-weight="bold">{}</text>"#,
+        ROW, title,
+    );
+    let _ = write!(
+        res,
+        r#"<text x="10" y="{}">{} – {}</text>"#,
+        ROW * 2,
+        escape(&label.title),
+        serving,
+    );
+
+    let mut y = ROW * 3;
+    for (idx, column) in columns.iter().enumerate().skip(1) {
+        let _ = write!(
+            res,
+            r#"<text x="{}" y="{}" text-anchor="end" font-weight="bold">{}</text>"#,
+            xs[idx], y, column,
+        );
+    }
+
+    for row in label.rows.iter() {
+        let _ = write!(
+            res,
+            r#"<line x1="10" x2="{}" y1="{}" y2="{}" stroke="black" stroke-width="0.5"/>"#,
+            WIDTH - 10,
+            y + 6,
+            y + 6,
+        );
+        y += ROW;
+
+        for (idx, cell) in cells(label, row).iter().enumerate() {
+            let _ = match idx {
+                0 => write!(
+                    res,
+                    r#"<text x="{}" y="{}"{}>{}</text>"#,
+                    xs[0] + if row.indent { 12 } else { 0 },
+                    y,
+                    if row.indent {
+                        ""
+                    } else {
+                        r#" font-weight="bold""#
+                    },
+                    escape(cell),
+                ),
+                _ => write!(
+                    res,
+                    r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                    xs[idx],
+                    y,
+                    escape(cell),
+                ),
+            };
+        }
+    }
+
+    let _ = write!(
+        res,
+        r#"<text x="10" y="{}" font-size="9">{}</text></svg>"#,
+        y + ROW + 6,
+        escape(&footnote(label)),
+    );
+
+    res
+}
+
+/// Renders a label as an embeddable HTML fragment with inline styles.
+pub fn render_html(label: &NutritionLabel) -> String {
+    let (title, serving) = heading(label);
+
+    let mut res = String::new();
+    let _ = write!(
+        res,
+        r#"<section class="nutrition-label" style="display:inline-block;border:1px solid #000;padding:8px;font-family:Helvetica,Arial,sans-serif;font-size:12px"><h2 style="margin:0;font-size:18px">{}</h2><p style="margin:4px 0">{} – {}</p><table style="border-collapse:collapse;width:100%"><thead><tr>"#,
+        title,
+        escape(&label.title),
+        serving,
+    );
+
+    for column in columns(label) {
+        let _ = write!(res, r#"<th style="text-align:right">{}</th>"#, column);
+    }
+    res.push_str("</tr></thead><tbody>");
+
+    for row in label.rows.iter() {
+        res.push_str(r#"<tr style="border-top:1px solid #000">"#);
+
+        for (idx, cell) in cells(label, row).iter().enumerate() {
+            let _ = match idx {
+                0 if row.indent => write!(
+                    res,
+                    r#"<td style="padding-left:12px">{}</td>"#,
+                    escape(cell)
+                ),
+                0 => write!(res, r#"<th style="text-align:left">{}</th>"#, escape(cell)),
+                _ => write!(res, r#"<td style="text-align:right">{}</td>"#, escape(cell)),
+            };
+        }
+
+        res.push_str("</tr>");
+    }
+
+    let _ = write!(
+        res,
+        r#"</tbody></table><p style="margin:4px 0 0;font-size:9px">{}</p></section>"#,
+        escape(&footnote(label)),
+    );
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(round: fn(f64) -> f64, cases: &[(f64, f64)]) {
+        for (value, expected) in cases {
+            assert_eq!(round(*value), *expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn eu_grams_thresholds() {
+        check(
+            eu_grams,
+            &[
+                (0.0, 0.0),
+                (0.49, 0.0),
+                (0.5, 0.5),
+                (9.94, 9.9),
+                (9.96, 10.0),
+                (10.0, 10.0),
+                (10.4, 10.0),
+                (10.5, 11.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn eu_salt_thresholds() {
+        check(
+            eu_salt,
+            &[
+                (0.012, 0.0),
+                (0.0125, 0.01),
+                (0.994, 0.99),
+                (0.999, 1.0),
+                (1.0, 1.0),
+                (1.04, 1.0),
+                (1.06, 1.1),
+            ],
+        );
+    }
+
+    #[test]
+    fn us_calories_thresholds() {
+        check(
+            us_calories,
+            &[
+                (4.9, 0.0),
+                (5.0, 5.0),
+                (7.4, 5.0),
+                (7.6, 10.0),
+                (50.0, 50.0),
+                (50.1, 50.0),
+                (54.9, 50.0),
+                (55.0, 60.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn us_fat_thresholds() {
+        check(
+            us_fat,
+            &[
+                (0.49, 0.0),
+                (0.5, 0.5),
+                (0.74, 0.5),
+                (0.76, 1.0),
+                (4.9, 5.0),
+                (5.0, 5.0),
+                (5.4, 5.0),
+                (5.5, 6.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn us_cholesterol_thresholds() {
+        check(
+            us_cholesterol,
+            &[(1.9, 0.0), (2.5, 5.0), (7.4, 5.0), (7.6, 10.0)],
+        );
+    }
+
+    #[test]
+    fn us_sodium_thresholds() {
+        check(
+            us_sodium,
+            &[
+                (4.9, 0.0),
+                (5.0, 5.0),
+                (137.4, 135.0),
+                (140.0, 140.0),
+                (144.9, 140.0),
+                (145.0, 150.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn us_grams_thresholds() {
+        check(
+            us_grams,
+            &[(0.49, 0.0), (0.5, 1.0), (1.49, 1.0), (1.5, 2.0)],
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use std::{collections::HashMap, error::Error, sync::Arc};
+
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    model::{
+        CreateMealPlanCommand, DayDeviation, DeleteMealPlanCommand, GenerateMealPlanCommand,
+        GeneratedMealPlan, Meal, MealPlan, MealPlanQuery, MealPlanSearchQuery, MealPlanSummary,
+        MealSlot, Nutrients, NutritionTargets, PeriodSummary, Recipe, RecipeSearchQuery,
+        SearchResult, ServiceError, UpdateMealPlanCommand,
+    },
+    repository::MealPlanRepository,
+};
+
+use super::{
+    check_pagination,
+    nutrition::{add, round2, scale},
+    RecipeService,
+};
+
+/// Longest plan accepted, keeps summaries and shopping lists bounded.
+const MAX_PLAN_DAYS: u64 = 92;
+
+/// Score added per earlier use of a recipe in a generated plan. A score of 1
+/// equals missing one target by 100 %.
+const REPEAT_PENALTY: f64 = 0.5;
+
+/// Score added when a recipe is already planned for the same day.
+const SAME_DAY_PENALTY: f64 = 10.0;
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_storage: Arc<MealPlanRepository>,
+}
+
+pub struct MealPlanService {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_storage: Arc<MealPlanRepository>,
+}
+
+impl MealPlanService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            meal_plan_storage: cfg.meal_plan_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: MealPlanQuery) -> Result<MealPlan, Box<dyn Error>> {
+        self.meal_plan_storage.fetch(q).await
+    }
+
+    /// Recipe ids with the number of slots planning them, see
+    /// [`MealPlanRepository::recipe_counts`].
+    pub async fn recipe_counts(
+        &self,
+        owner_id: Option<String>,
+    ) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        self.meal_plan_storage.recipe_counts(owner_id).await
+    }
+
+    pub async fn create(&self, item: CreateMealPlanCommand) -> Result<MealPlan, Box<dyn Error>> {
+        self.check(item.start_date, item.end_date, &item.slots)
+            .await?;
+
+        self.meal_plan_storage.create(item).await
+    }
+
+    pub async fn update(&self, q: UpdateMealPlanCommand) -> Result<MealPlan, Box<dyn Error>> {
+        let current = self
+            .meal_plan_storage
+            .fetch(MealPlanQuery {
+                id: q.id.clone(),
+                owner_id: q.owner_id.clone(),
+            })
+            .await?;
+
+        self.check(
+            q.start_date.unwrap_or(current.start_date),
+            q.end_date.unwrap_or(current.end_date),
+            q.slots.as_ref().unwrap_or(&current.slots),
+        )
+        .await?;
+
+        self.meal_plan_storage.update(q).await
+    }
+
+    pub async fn delete(&self, q: DeleteMealPlanCommand) -> Result<MealPlan, Box<dyn Error>> {
+        self.meal_plan_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: MealPlanSearchQuery,
+    ) -> Result<SearchResult<MealPlan>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.meal_plan_storage.search(q).await
+    }
+
+    /// Daily, weekly and overall nutrient totals of the planned servings.
+    pub async fn summary(&self, q: MealPlanQuery) -> Result<MealPlanSummary, Box<dyn Error>> {
+        let plan = self.meal_plan_storage.fetch(q).await?;
+        let recipes = self.recipes(&plan.slots).await?;
+
+        let mut days: Vec<PeriodSummary> = plan
+            .start_date
+            .iter_days()
+            .take_while(|date| *date <= plan.end_date)
+            .map(|date| PeriodSummary {
+                start_date: date,
+                end_date: date,
+                nutrients: Nutrients::default(),
+            })
+            .collect();
+        let mut total = Nutrients::default();
+        let mut missing_recipes = Vec::new();
+
+        for slot in plan.slots.iter() {
+            let Some(recipe) = recipes.get(&slot.recipe_id) else {
+                missing_recipes.push(slot.recipe_id.clone());
+                continue;
+            };
+
+            let servings = slot.planned_servings() as f64;
+            let nutrients = scale(recipe.nutrients.clone(), servings);
+
+            let day = (slot.date - plan.start_date).num_days() as usize;
+            if let Some(day) = days.get_mut(day) {
+                add(&mut day.nutrients, &nutrients);
+            }
+            add(&mut total, &nutrients);
+        }
+
+        let weeks = days
+            .chunks(7)
+            .map(|week| {
+                let mut nutrients = Nutrients::default();
+                for day in week {
+                    add(&mut nutrients, &day.nutrients);
+                }
+
+                PeriodSummary {
+                    start_date: week[0].start_date,
+                    end_date: week[week.len() - 1].end_date,
+                    nutrients,
+                }
+            })
+            .collect();
+
+        missing_recipes.sort();
+        missing_recipes.dedup();
+
+        Ok(MealPlanSummary {
+            id: plan.id,
+            days,
+            weeks,
+            total,
+            missing_recipes,
+        })
+    }
+
+    /// Fills a new plan with one serving of a catalogue recipe per meal,
+    /// picking meal by meal the recipe that brings the day closest to the
+    /// targets so far while penalising repetitions.
+    pub async fn generate(
+        &self,
+        item: GenerateMealPlanCommand,
+    ) -> Result<GeneratedMealPlan, Box<dyn Error>> {
+        if !(1..=MAX_PLAN_DAYS as i64).contains(&item.days) {
+            return Err(ServiceError::Invalid(format!(
+                "days must be between 1 and {}",
+                MAX_PLAN_DAYS
+            ))
+            .into());
+        }
+
+        let targets = item.targets;
+        check_targets(&targets)?;
+
+        let mut meals = if item.meals.is_empty() {
+            vec![Meal::Breakfast, Meal::Lunch, Meal::Dinner]
+        } else {
+            item.meals.clone()
+        };
+        meals.sort();
+        meals.dedup();
+
+        let excluded: Vec<String> = item
+            .exclude_ingredients
+            .iter()
+            .map(|term| term.trim().to_lowercase())
+            .filter(|term| !term.is_empty())
+            .collect();
+
+        let mut candidates: Vec<Recipe> = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                diets: (!item.diets.is_empty()).then(|| item.diets.clone()),
+                exclude_allergens: (!item.exclude_allergens.is_empty())
+                    .then(|| item.exclude_allergens.clone()),
+                max_time_to_cook: item.max_time_to_cook,
+                ..RecipeSearchQuery::default()
+            })
+            .await?
+            .items
+            .into_iter()
+            .filter(|recipe| recipe.nutrients.kcal > 0.0 && !contains_any(recipe, &excluded))
+            .collect();
+        candidates.sort_by(|a, b| a.id.cmp(&b.id));
+
+        if candidates.is_empty() {
+            return Err(ServiceError::Invalid("no recipes match the restrictions".into()).into());
+        }
+
+        let total_share: f64 = meals.iter().map(|meal| meal_share(*meal)).sum();
+        let mut uses: HashMap<&str, usize> = HashMap::new();
+        let mut slots = Vec::new();
+        let mut days = Vec::new();
+
+        for date in item.start_date.iter_days().take(item.days as usize) {
+            let mut planned = Nutrients::default();
+            let mut share = 0.0;
+            let mut today: Vec<&str> = Vec::new();
+
+            for meal in meals.iter() {
+                share += meal_share(*meal) / total_share;
+                let goal = scale_targets(&targets, share);
+
+                let (_, recipe) = candidates
+                    .iter()
+                    .map(|recipe| {
+                        let repeats = uses.get(recipe.id.as_str()).copied().unwrap_or_default();
+                        let mut score = distance(&planned, &recipe.nutrients, &goal)
+                            + REPEAT_PENALTY * repeats as f64;
+                        if today.contains(&recipe.id.as_str()) {
+                            score += SAME_DAY_PENALTY;
+                        }
+
+                        (score, recipe)
+                    })
+                    .min_by(|a, b| a.0.total_cmp(&b.0))
+                    .ok_or_else(|| {
+                        ServiceError::Invalid("no recipes match the restrictions".into())
+                    })?;
+
+                add(&mut planned, &recipe.nutrients);
+                *uses.entry(recipe.id.as_str()).or_default() += 1;
+                today.push(recipe.id.as_str());
+
+                slots.push(MealSlot {
+                    date,
+                    meal: *meal,
+                    recipe_id: recipe.id.clone(),
+                    servings: Some(1),
+                });
+            }
+
+            days.push(DayDeviation {
+                date,
+                deviation: deviation(&planned, &targets),
+                nutrients: planned,
+            });
+        }
+
+        let plan = self
+            .meal_plan_storage
+            .create(CreateMealPlanCommand {
+                owner_id: item.owner_id,
+                name: item.name,
+                start_date: item.start_date,
+                end_date: item.start_date + Days::new(item.days as u64 - 1),
+                slots,
+            })
+            .await?;
+
+        Ok(GeneratedMealPlan { plan, days })
+    }
+
+    /// Recipes referenced by the slots, keyed by id and per serving.
+    pub async fn recipes(
+        &self,
+        slots: &[MealSlot],
+    ) -> Result<HashMap<String, Recipe>, Box<dyn Error>> {
+        let mut ids: Vec<String> = slots.iter().map(|slot| slot.recipe_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let res = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                ids: Some(ids),
+                ..RecipeSearchQuery::default()
+            })
+            .await?;
+
+        Ok(res
+            .items
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect())
+    }
+
+    async fn check(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        slots: &[MealSlot],
+    ) -> Result<(), Box<dyn Error>> {
+        if start_date > end_date {
+            return Err(
+                ServiceError::Invalid("start date must not be after end date".into()).into(),
+            );
+        }
+
+        if start_date.checked_add_days(Days::new(MAX_PLAN_DAYS)) <= Some(end_date) {
+            return Err(ServiceError::Invalid(format!(
+                "meal plan must not exceed {} days",
+                MAX_PLAN_DAYS
+            ))
+            .into());
+        }
+
+        for slot in slots.iter() {
+            if slot.date < start_date || slot.date > end_date {
+                return Err(ServiceError::Invalid(format!(
+                    "slot date {} is outside of the plan",
+                    slot.date
+                ))
+                .into());
+            }
+
+            if slot.servings.is_some_and(|servings| servings <= 0) {
+                return Err(ServiceError::Invalid("slot servings must be positive".into()).into());
+            }
+        }
+
+        let recipes = self.recipes(slots).await?;
+        if let Some(slot) = slots
+            .iter()
+            .find(|slot| !recipes.contains_key(&slot.recipe_id))
+        {
+            return Err(ServiceError::Invalid(format!(
+                "recipe with id {} not found",
+                slot.recipe_id
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) fn check_targets(targets: &NutritionTargets) -> Result<(), Box<dyn Error>> {
+    if targets.kcal.is_nan() || targets.kcal <= 0.0 {
+        return Err(ServiceError::Invalid("kcal target must be positive".into()).into());
+    }
+    if [targets.proteins, targets.fats, targets.carbohydrates]
+        .into_iter()
+        .flatten()
+        .any(|target| target.is_nan() || target < 0.0)
+    {
+        return Err(
+            ServiceError::Invalid("macronutrient targets must not be negative".into()).into(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Difference of the nutrients to the targets, positive where they exceed
+/// a target.
+pub(super) fn deviation(nutrients: &Nutrients, targets: &NutritionTargets) -> NutritionTargets {
+    NutritionTargets {
+        kcal: round2(nutrients.kcal - targets.kcal),
+        proteins: targets.proteins.map(|v| round2(nutrients.proteins - v)),
+        fats: targets.fats.map(|v| round2(nutrients.fats - v)),
+        carbohydrates: targets
+            .carbohydrates
+            .map(|v| round2(nutrients.carbohydrates - v)),
+    }
+}
+
+/// Share of the daily energy a meal aims for, relative to the other meals.
+fn meal_share(meal: Meal) -> f64 {
+    match meal {
+        Meal::Breakfast => 0.25,
+        Meal::Lunch => 0.35,
+        Meal::Dinner => 0.3,
+        Meal::Snack => 0.1,
+    }
+}
+
+fn scale_targets(targets: &NutritionTargets, factor: f64) -> NutritionTargets {
+    NutritionTargets {
+        kcal: targets.kcal * factor,
+        proteins: targets.proteins.map(|v| v * factor),
+        fats: targets.fats.map(|v| v * factor),
+        carbohydrates: targets.carbohydrates.map(|v| v * factor),
+    }
+}
+
+/// Sum of squared relative misses of the goal after adding `extra` to
+/// `planned`.
+fn distance(planned: &Nutrients, extra: &Nutrients, goal: &NutritionTargets) -> f64 {
+    let miss = |value: f64, target: f64| {
+        if target > 0.0 {
+            ((value - target) / target).powi(2)
+        } else {
+            0.0
+        }
+    };
+
+    miss(planned.kcal + extra.kcal, goal.kcal)
+        + goal
+            .proteins
+            .map_or(0.0, |t| miss(planned.proteins + extra.proteins, t))
+        + goal
+            .fats
+            .map_or(0.0, |t| miss(planned.fats + extra.fats, t))
+        + goal.carbohydrates.map_or(0.0, |t| {
+            miss(planned.carbohydrates + extra.carbohydrates, t)
+        })
+}
+
+fn contains_any(recipe: &Recipe, terms: &[String]) -> bool {
+    recipe.ingredients.iter().any(|line| {
+        let line = line.to_lowercase();
+        terms.iter().any(|term| line.contains(term.as_str()))
+    })
+}
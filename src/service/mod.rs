@@ -1,5 +1,82 @@
+pub(crate) mod allergen;
+pub(crate) mod api_key;
+pub(crate) mod auth;
 pub(crate) mod category;
+pub(crate) mod collection;
+pub(crate) mod cooking_log;
+pub(crate) mod diary;
+pub(crate) mod diet;
+pub(crate) mod favorite;
+pub(crate) mod ingredient;
+pub(crate) mod label;
+pub(crate) mod meal_plan;
+pub(crate) mod nutrition;
+pub(crate) mod pantry;
+pub(crate) mod policy;
+pub(crate) mod preference;
 pub(crate) mod recipe;
+pub(crate) mod recommendation;
+pub(crate) mod review;
+pub(crate) mod revision;
+pub(crate) mod shopping_list;
+pub(crate) mod similarity;
+pub(crate) mod substitution;
 
+pub use allergen::AllergenService;
+pub use api_key::ApiKeyService;
+pub use auth::AuthService;
 pub use category::CategoryService;
+pub use collection::CollectionService;
+pub use cooking_log::CookingLogService;
+pub use diary::DiaryService;
+pub use diet::DietService;
+pub use favorite::FavoriteService;
+pub use ingredient::IngredientService;
+pub use label::LabelService;
+pub use meal_plan::MealPlanService;
+pub use nutrition::NutritionService;
+pub use pantry::PantryService;
+pub use preference::PreferenceService;
 pub use recipe::RecipeService;
+pub use recommendation::RecommendationService;
+pub use review::ReviewService;
+pub use revision::RevisionService;
+pub use shopping_list::ShoppingListService;
+pub use substitution::SubstitutionService;
+
+use std::error::Error;
+
+use crate::model::{Pagination, ServiceError};
+
+const MAX_PAGE_SIZE: i64 = 1000;
+
+fn check_pagination(p: &Pagination) -> Result<(), Box<dyn Error>> {
+    if let Some(limit) = p.limit {
+        if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(ServiceError::Invalid(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            ))
+            .into());
+        }
+    }
+
+    if p.offset.is_some_and(|offset| offset < 0) {
+        return Err(ServiceError::Invalid("offset must not be negative".into()).into());
+    }
+
+    Ok(())
+}
+
+/// Drops repeated values, keeping the first occurrence.
+fn dedup<T: PartialEq>(values: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut res: Vec<T> = Vec::new();
+
+    for value in values {
+        if !res.contains(&value) {
+            res.push(value);
+        }
+    }
+
+    res
+}
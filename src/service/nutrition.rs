@@ -0,0 +1,294 @@
+use std::{error::Error, sync::Arc};
+
+use crate::model::{
+    CalculateNutrientsQuery, Ingredient, IngredientLine, IngredientNutrients, NutrientBasis,
+    Nutrients, NutrientsCalculation, ServiceError, UnitKind,
+};
+
+use super::{ingredient::parse_line, IngredientService};
+
+pub struct Config {
+    pub ingredient_service: Arc<IngredientService>,
+}
+
+pub struct NutritionService {
+    pub ingredient_service: Arc<IngredientService>,
+}
+
+impl NutritionService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            ingredient_service: cfg.ingredient_service,
+        }
+    }
+
+    /// Computes per serving nutrients from ingredient lines using the
+    /// nutrition data of the ingredient dictionary.
+    pub async fn calculate(
+        &self,
+        q: CalculateNutrientsQuery,
+    ) -> Result<NutrientsCalculation, Box<dyn Error>> {
+        if q.servings <= 0 {
+            return Err(ServiceError::Invalid("servings must be positive".into()).into());
+        }
+
+        let matcher = self.ingredient_service.matcher().await?;
+
+        let mut total = IngredientNutrients::default();
+        let mut weight = 0.0;
+        let mut unmatched = Vec::new();
+
+        for line in q.ingredients.iter() {
+            let parsed = parse_line(line);
+
+            let found = matcher.find(&parsed.name).and_then(|ingredient| {
+                let per_100g = ingredient.nutrients?;
+                let grams = grams(&parsed, ingredient)?;
+
+                Some((per_100g, grams))
+            });
+
+            match found {
+                Some((per_100g, grams)) => {
+                    let ratio = grams / 100.0;
+
+                    weight += grams;
+                    total.proteins += per_100g.proteins * ratio;
+                    total.fats += per_100g.fats * ratio;
+                    total.saturated_fat += per_100g.saturated_fat * ratio;
+                    total.carbohydrates += per_100g.carbohydrates * ratio;
+                    total.sugars += per_100g.sugars * ratio;
+                    total.fiber += per_100g.fiber * ratio;
+                    total.sodium += per_100g.sodium * ratio;
+                    total.cholesterol += per_100g.cholesterol * ratio;
+                    total.kcal += per_100g.kcal * ratio;
+                }
+                None => unmatched.push(line.clone()),
+            }
+        }
+
+        let servings = q.servings as f64;
+
+        Ok(NutrientsCalculation {
+            nutrients: Nutrients {
+                basis: NutrientBasis::PerServing,
+                serving_weight: (weight > 0.0).then(|| round2(weight / servings)),
+                kcal: round2(total.kcal / servings),
+                proteins: round2(total.proteins / servings),
+                fats: round2(total.fats / servings),
+                saturated_fat: round2(total.saturated_fat / servings),
+                carbohydrates: round2(total.carbohydrates / servings),
+                sugars: round2(total.sugars / servings),
+                fiber: round2(total.fiber / servings),
+                sodium: round2(total.sodium / servings),
+                cholesterol: round2(total.cholesterol / servings),
+                micronutrients: Default::default(),
+            },
+            unmatched,
+        })
+    }
+}
+
+/// Converts nutrient values to another basis. `servings` is the number of
+/// servings of the recipe the values belong to.
+pub fn convert(
+    nutrients: Nutrients,
+    to: NutrientBasis,
+    servings: i64,
+) -> Result<Nutrients, Box<dyn Error>> {
+    if nutrients.basis == to {
+        return Ok(nutrients);
+    }
+
+    let from = per_serving_factor(nutrients.basis, servings, nutrients.serving_weight)?;
+    let into = per_serving_factor(to, servings, nutrients.serving_weight)?;
+
+    let mut res = scale(nutrients, from / into);
+    res.basis = to;
+
+    Ok(res)
+}
+
+/// Factor turning values on `basis` into per serving values.
+fn per_serving_factor(
+    basis: NutrientBasis,
+    servings: i64,
+    serving_weight: Option<f64>,
+) -> Result<f64, Box<dyn Error>> {
+    match basis {
+        NutrientBasis::PerServing => Ok(1.0),
+        NutrientBasis::PerRecipe if servings > 0 => Ok(1.0 / servings as f64),
+        NutrientBasis::PerRecipe => {
+            Err(ServiceError::Invalid("servings must be positive".into()).into())
+        }
+        NutrientBasis::Per100g => match serving_weight {
+            Some(weight) if weight > 0.0 => Ok(weight / 100.0),
+            _ => Err(ServiceError::Invalid(
+                "serving weight is required for per 100 g values".into(),
+            )
+            .into()),
+        },
+    }
+}
+
+/// Multiplies every amount by `factor`, keeping basis and serving weight.
+pub fn scale(mut nutrients: Nutrients, factor: f64) -> Nutrients {
+    nutrients.kcal = round2(nutrients.kcal * factor);
+    nutrients.proteins = round2(nutrients.proteins * factor);
+    nutrients.fats = round2(nutrients.fats * factor);
+    nutrients.saturated_fat = round2(nutrients.saturated_fat * factor);
+    nutrients.carbohydrates = round2(nutrients.carbohydrates * factor);
+    nutrients.sugars = round2(nutrients.sugars * factor);
+    nutrients.fiber = round2(nutrients.fiber * factor);
+    nutrients.sodium = round2(nutrients.sodium * factor);
+    nutrients.cholesterol = round2(nutrients.cholesterol * factor);
+
+    for value in nutrients.micronutrients.values_mut() {
+        *value = round2(*value * factor);
+    }
+
+    nutrients
+}
+
+/// Adds the amounts of `other` to `total`, micronutrients by name.
+pub fn add(total: &mut Nutrients, other: &Nutrients) {
+    total.kcal = round2(total.kcal + other.kcal);
+    total.proteins = round2(total.proteins + other.proteins);
+    total.fats = round2(total.fats + other.fats);
+    total.saturated_fat = round2(total.saturated_fat + other.saturated_fat);
+    total.carbohydrates = round2(total.carbohydrates + other.carbohydrates);
+    total.sugars = round2(total.sugars + other.sugars);
+    total.fiber = round2(total.fiber + other.fiber);
+    total.sodium = round2(total.sodium + other.sodium);
+    total.cholesterol = round2(total.cholesterol + other.cholesterol);
+
+    for (name, value) in other.micronutrients.iter() {
+        let entry = total.micronutrients.entry(name.clone()).or_default();
+        *entry = round2(*entry + value);
+    }
+}
+
+/// Weight of an ingredient line in grams, when it can be derived.
+fn grams(line: &IngredientLine, ingredient: &Ingredient) -> Option<f64> {
+    let quantity = line.quantity?;
+    let unit = line.unit?;
+    let amount = quantity * unit.factor();
+
+    match unit.kind() {
+        UnitKind::Mass => Some(amount),
+        UnitKind::Volume => Some(amount * ingredient.density.unwrap_or(1.0)),
+        UnitKind::Count => ingredient.grams_per_piece.map(|grams| amount * grams),
+    }
+}
+
+pub fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nutrients(basis: NutrientBasis, serving_weight: Option<f64>, kcal: f64) -> Nutrients {
+        Nutrients {
+            basis,
+            serving_weight,
+            kcal,
+            proteins: kcal / 10.0,
+            micronutrients: [("iron".to_string(), kcal / 100.0)].into(),
+            ..Nutrients::default()
+        }
+    }
+
+    #[test]
+    fn convert_between_bases() {
+        let per_serving = nutrients(NutrientBasis::PerServing, Some(250.0), 400.0);
+
+        let cases = [
+            (NutrientBasis::PerServing, 400.0, 40.0, 4.0),
+            (NutrientBasis::PerRecipe, 1600.0, 160.0, 16.0),
+            (NutrientBasis::Per100g, 160.0, 16.0, 1.6),
+        ];
+
+        for (basis, kcal, proteins, iron) in cases {
+            let res = convert(per_serving.clone(), basis, 4).unwrap();
+
+            assert_eq!(res.basis, basis);
+            assert_eq!(res.serving_weight, Some(250.0));
+            assert_eq!(res.kcal, kcal, "{:?}", basis);
+            assert_eq!(res.proteins, proteins, "{:?}", basis);
+            assert_eq!(res.micronutrients["iron"], iron, "{:?}", basis);
+        }
+    }
+
+    #[test]
+    fn convert_round_trips() {
+        let per_100g = nutrients(NutrientBasis::Per100g, Some(250.0), 160.0);
+
+        let per_recipe = convert(per_100g, NutrientBasis::PerRecipe, 4).unwrap();
+        assert_eq!(per_recipe.kcal, 1600.0);
+
+        let back = convert(per_recipe, NutrientBasis::Per100g, 4).unwrap();
+        assert_eq!(back.kcal, 160.0);
+    }
+
+    #[test]
+    fn convert_rejects_missing_inputs() {
+        let without_weight = nutrients(NutrientBasis::PerServing, None, 400.0);
+        assert!(convert(without_weight, NutrientBasis::Per100g, 4).is_err());
+
+        let per_recipe = nutrients(NutrientBasis::PerRecipe, None, 400.0);
+        assert!(convert(per_recipe, NutrientBasis::PerServing, 0).is_err());
+    }
+
+    #[test]
+    fn scale_rounds_every_amount() {
+        let res = scale(
+            nutrients(NutrientBasis::PerServing, Some(250.0), 100.0),
+            1.0 / 3.0,
+        );
+
+        assert_eq!(res.kcal, 33.33);
+        assert_eq!(res.proteins, 3.33);
+        assert_eq!(res.micronutrients["iron"], 0.33);
+        assert_eq!(res.serving_weight, Some(250.0));
+    }
+
+    #[test]
+    fn add_merges_micronutrients_by_name() {
+        let mut total = nutrients(NutrientBasis::PerServing, None, 100.0);
+        let mut other = nutrients(NutrientBasis::PerServing, None, 50.0);
+        other.micronutrients.insert("zinc".into(), 0.2);
+
+        add(&mut total, &other);
+
+        assert_eq!(total.kcal, 150.0);
+        assert_eq!(total.proteins, 15.0);
+        assert_eq!(total.micronutrients["iron"], 1.5);
+        assert_eq!(total.micronutrients["zinc"], 0.2);
+    }
+
+    #[test]
+    fn grams_uses_density_and_piece_weight() {
+        let ingredient = Ingredient {
+            density: Some(1.03),
+            grams_per_piece: Some(50.0),
+            ..Ingredient::default()
+        };
+
+        let cases = [
+            ("2 kg flour", Some(2000.0)),
+            ("100 ml milk", Some(103.0)),
+            ("2 eggs", Some(100.0)),
+            ("salt to taste", None),
+        ];
+
+        for (line, expected) in cases {
+            let res = grams(&parse_line(line), &ingredient).map(round2);
+            assert_eq!(res, expected, "{:?}", line);
+        }
+
+        let without_piece_weight = Ingredient::default();
+        assert_eq!(grams(&parse_line("2 eggs"), &without_piece_weight), None);
+    }
+}
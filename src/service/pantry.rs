@@ -0,0 +1,192 @@
+use std::{error::Error, sync::Arc};
+
+use chrono::{Days, Utc};
+
+use crate::{
+    model::{
+        CookRecipeCommand, CreatePantryItemCommand, DeletePantryItemCommand, PantryItem,
+        PantryItemQuery, PantrySearchQuery, PantryUse, Recipe, RecipeQuery, RecipeSearchQuery,
+        SearchResult, ServiceError, Unit, UpdatePantryItemCommand,
+    },
+    repository::PantryRepository,
+};
+
+use super::{check_pagination, ingredient::canonical_name, IngredientService, RecipeService};
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub pantry_storage: Arc<PantryRepository>,
+}
+
+pub struct PantryService {
+    pub recipe_service: Arc<RecipeService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub pantry_storage: Arc<PantryRepository>,
+}
+
+impl PantryService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            ingredient_service: cfg.ingredient_service,
+            pantry_storage: cfg.pantry_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: PantryItemQuery) -> Result<PantryItem, Box<dyn Error>> {
+        self.pantry_storage.fetch(q).await
+    }
+
+    pub async fn create(
+        &self,
+        mut item: CreatePantryItemCommand,
+    ) -> Result<PantryItem, Box<dyn Error>> {
+        check_quantity(item.quantity)?;
+
+        let matcher = self.ingredient_service.matcher().await?;
+        item.name = canonical_name(&matcher, &item.name)?;
+
+        if item.quantity.is_some() && item.unit.is_none() {
+            item.unit = Some(Unit::Piece);
+        }
+
+        self.pantry_storage.create(item).await
+    }
+
+    pub async fn update(
+        &self,
+        mut q: UpdatePantryItemCommand,
+    ) -> Result<PantryItem, Box<dyn Error>> {
+        check_quantity(q.quantity)?;
+
+        if let Some(name) = q.name.as_deref() {
+            let matcher = self.ingredient_service.matcher().await?;
+            q.name = Some(canonical_name(&matcher, name)?);
+        }
+
+        if q.quantity.is_some() && q.unit.is_none() {
+            let current = self
+                .pantry_storage
+                .fetch(PantryItemQuery {
+                    id: q.id.clone(),
+                    owner_id: q.owner_id.clone(),
+                })
+                .await?;
+            if current.unit.is_none() {
+                q.unit = Some(Unit::Piece);
+            }
+        }
+
+        self.pantry_storage.update(q).await
+    }
+
+    pub async fn delete(&self, q: DeletePantryItemCommand) -> Result<PantryItem, Box<dyn Error>> {
+        self.pantry_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: PantrySearchQuery,
+    ) -> Result<SearchResult<PantryItem>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.pantry_storage.search(q).await
+    }
+
+    /// Every pantry item of the owner, soonest expiring first.
+    pub async fn items(&self, owner_id: &str) -> Result<Vec<PantryItem>, Box<dyn Error>> {
+        let res = self
+            .pantry_storage
+            .search(PantrySearchQuery {
+                owner_id: owner_id.to_string(),
+                ..PantrySearchQuery::default()
+            })
+            .await?;
+
+        Ok(res.items)
+    }
+
+    /// Takes the ingredients of a cooked recipe out of the pantry, using
+    /// the soonest expiring items first. Lines without an amount or with a
+    /// unit of another kind than the pantry item are left alone. Returns the
+    /// remaining pantry.
+    pub async fn cook(&self, item: CookRecipeCommand) -> Result<Vec<PantryItem>, Box<dyn Error>> {
+        if item.servings.is_some_and(|servings| servings <= 0) {
+            return Err(ServiceError::Invalid("servings must be positive".into()).into());
+        }
+
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: item.recipe_id,
+                ..RecipeQuery::default()
+            })
+            .await?;
+        let factor = match item.servings {
+            Some(servings) if recipe.servings > 0 => servings as f64 / recipe.servings as f64,
+            _ => 1.0,
+        };
+
+        let matcher = self.ingredient_service.matcher().await?;
+        let uses = recipe
+            .ingredients
+            .iter()
+            .filter_map(|line| matcher.resolve(line))
+            .filter_map(|resolved| {
+                let (kind, amount) = resolved.amount?;
+
+                Some(PantryUse {
+                    name: resolved.name,
+                    kind,
+                    amount: amount * factor,
+                })
+            })
+            .collect();
+
+        self.pantry_storage
+            .use_up(item.owner_id.clone(), uses)
+            .await?;
+
+        self.items(&item.owner_id).await
+    }
+
+    /// Recipes using pantry items that expire within `days`, the ones using
+    /// most of them first.
+    pub async fn expiring_recipes(
+        &self,
+        owner_id: &str,
+        days: i64,
+        mut q: RecipeSearchQuery,
+    ) -> Result<SearchResult<Recipe>, Box<dyn Error>> {
+        if days < 0 {
+            return Err(ServiceError::Invalid("days must not be negative".into()).into());
+        }
+
+        let expires_before = Utc::now()
+            .date_naive()
+            .checked_add_days(Days::new(days as u64))
+            .ok_or_else(|| ServiceError::Invalid("days out of range".into()))?;
+
+        let res = self
+            .pantry_storage
+            .search(PantrySearchQuery {
+                owner_id: owner_id.to_string(),
+                expires_before: Some(expires_before),
+                ..PantrySearchQuery::default()
+            })
+            .await?;
+
+        q.ingredients = Some(res.items.into_iter().map(|item| item.name).collect());
+
+        self.recipe_service.search(q).await
+    }
+}
+
+fn check_quantity(quantity: Option<f64>) -> Result<(), Box<dyn Error>> {
+    if quantity.is_some_and(|quantity| quantity.is_nan() || quantity <= 0.0) {
+        return Err(ServiceError::Invalid("quantity must be positive".into()).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::error::Error;
+
+use crate::model::{Actor, RecipeVisibility, Role, ServiceError};
+
+/// Fails unless the actor has at least `role`.
+pub fn require(actor: &Actor, role: Role) -> Result<(), Box<dyn Error>> {
+    if actor.role < role {
+        return Err(ServiceError::Forbidden(format!("{} role required", role.code())).into());
+    }
+
+    Ok(())
+}
+
+/// Editors change any recipe, authors only their own ones.
+pub fn require_recipe_access(actor: &Actor, author_id: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if actor.role >= Role::Editor {
+        return Ok(());
+    }
+
+    require(actor, Role::Author)?;
+
+    if author_id != Some(actor.user_id.as_str()) {
+        return Err(
+            ServiceError::Forbidden("only the author may change this recipe".into()).into(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Everyone sees published recipes, authors also their own ones in any
+/// status and editors every recipe.
+pub fn recipe_visibility(viewer: Option<&Actor>) -> RecipeVisibility {
+    match viewer {
+        Some(actor) if actor.role >= Role::Editor => RecipeVisibility::All,
+        Some(actor) => RecipeVisibility::PublishedOrAuthoredBy(actor.user_id.clone()),
+        None => RecipeVisibility::Published,
+    }
+}
@@ -0,0 +1,50 @@
+use std::{error::Error, sync::Arc};
+
+use crate::{model::UserPreferences, repository::PreferenceRepository};
+
+use super::{dedup, ingredient::normalize};
+
+pub struct Config {
+    pub preference_storage: Arc<PreferenceRepository>,
+}
+
+pub struct PreferenceService {
+    pub preference_storage: Arc<PreferenceRepository>,
+}
+
+impl PreferenceService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            preference_storage: cfg.preference_storage,
+        }
+    }
+
+    pub async fn fetch(&self, owner_id: &str) -> Result<UserPreferences, Box<dyn Error>> {
+        self.preference_storage.fetch(owner_id.to_string()).await
+    }
+
+    /// Replaces the preferences of the owner.
+    pub async fn save(&self, mut item: UserPreferences) -> Result<UserPreferences, Box<dyn Error>> {
+        item.diets = dedup(item.diets);
+        item.exclude_allergens = dedup(
+            item.exclude_allergens
+                .iter()
+                .map(|code| code.trim().to_lowercase())
+                .filter(|code| !code.is_empty()),
+        );
+        item.categories = dedup(
+            item.categories
+                .iter()
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty()),
+        );
+        item.disliked_ingredients = dedup(
+            item.disliked_ingredients
+                .iter()
+                .map(|name| normalize(name))
+                .filter(|name| !name.is_empty()),
+        );
+
+        self.preference_storage.save(item).await
+    }
+}
@@ -1,73 +1,460 @@
 use std::{collections::HashMap, error::Error, sync::Arc};
 
+use chrono::{Duration, Utc};
+
 use crate::{
-    model::{category::CategoryQuery, recipe::*, CategorySearchQuery, SearchResult},
-    repository::RecipeRepository,
+    model::{
+        category::CategoryQuery, recipe::*, Actor, Allergen, AllergenSearchQuery, Category,
+        CategorySearchQuery, RecipeSnapshot, Role, SearchResult, ServiceError,
+    },
+    repository::{FavoriteRepository, RecipeRepository},
 };
 
-use super::CategoryService;
+use super::{
+    nutrition::convert, policy, AllergenService, CategoryService, DietService, IngredientService,
+    NutritionService,
+};
 
 pub struct Config {
     pub category_service: Arc<CategoryService>,
+    pub allergen_service: Arc<AllergenService>,
+    pub diet_service: Arc<DietService>,
+    pub nutrition_service: Arc<NutritionService>,
+    pub ingredient_service: Arc<IngredientService>,
     pub recipe_storage: Arc<RecipeRepository>,
+    pub favorite_storage: Arc<FavoriteRepository>,
+    /// How long deleted recipes and categories stay restorable.
+    pub deleted_retention: Duration,
 }
 
 pub struct RecipeService {
     pub category_service: Arc<CategoryService>,
+    pub allergen_service: Arc<AllergenService>,
+    pub diet_service: Arc<DietService>,
+    pub nutrition_service: Arc<NutritionService>,
+    pub ingredient_service: Arc<IngredientService>,
     pub recipe_storage: Arc<RecipeRepository>,
+    pub favorite_storage: Arc<FavoriteRepository>,
+    pub deleted_retention: Duration,
 }
 
 impl RecipeService {
     pub fn new(cfg: Config) -> Self {
         Self {
             category_service: cfg.category_service,
+            allergen_service: cfg.allergen_service,
+            diet_service: cfg.diet_service,
+            nutrition_service: cfg.nutrition_service,
+            ingredient_service: cfg.ingredient_service,
             recipe_storage: cfg.recipe_storage,
+            favorite_storage: cfg.favorite_storage,
+            deleted_retention: cfg.deleted_retention,
         }
     }
 
     pub async fn fetch(&self, q: RecipeQuery) -> Result<Recipe, Box<dyn Error>> {
+        let basis = q.basis;
+        let viewer_id = q.viewer_id.clone();
+        let visibility = q.visibility.clone();
         let mut item = self.recipe_storage.fetch(q).await?;
 
+        if !visibility.allows(&item) {
+            return Err(format!("recipe with id {} not found", item.id).into());
+        }
+
+        item.nutrients = convert(item.nutrients, basis, item.servings)?;
+
         item.category = self
             .category_service
             .fetch(CategoryQuery {
                 id: item.category.id,
             })
             .await?;
+        item.allergens = self.allergen_service.resolve(&codes(&item)).await?;
+
+        if let Some(viewer_id) = viewer_id {
+            self.flag_favorites(viewer_id, std::slice::from_mut(&mut item))
+                .await?;
+        }
 
         Ok(item)
     }
 
-    pub async fn create(&self, item: CreateRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+    pub async fn create(&self, mut item: CreateRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        policy::require(&item.actor, Role::Author)?;
+
+        item.source_url = item
+            .source_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        if let Some(url) = item.source_url.as_deref() {
+            check_source_url(url)?;
+        }
+        item.attribution = item.attribution.trim().to_string();
+        item.license = item.license.trim().to_string();
+
         let cat = self
             .category_service
             .fetch(CategoryQuery {
                 id: item.category.clone(),
             })
             .await
-            .map_err(|_| format!("category with id {} not found", item.category.clone()))?;
+            .map_err(|_| {
+                ServiceError::Invalid(format!(
+                    "category with id {} not found",
+                    item.category.clone()
+                ))
+            })?;
+        let allergens = self.allergen_service.resolve(&item.allergens).await?;
+
+        item.nutrients = if item.compute_nutrients {
+            self.nutrition_service
+                .calculate(CalculateNutrientsQuery {
+                    ingredients: item.ingredients.clone(),
+                    servings: item.servings,
+                })
+                .await?
+                .nutrients
+        } else {
+            convert(item.nutrients, NutrientBasis::PerServing, item.servings)?
+        };
+
+        item.diets = self
+            .diet_service
+            .classify(&item.ingredients, &item.nutrients)
+            .await?;
 
         let mut res = self.recipe_storage.create(item).await?;
 
         res.category = cat;
+        res.allergens = allergens;
 
         Ok(res)
     }
 
-    pub async fn update(&self, q: UpdateRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
-        self.recipe_storage.update(q).await
+    pub async fn update(&self, mut q: UpdateRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        let current = self
+            .recipe_storage
+            .fetch(RecipeQuery {
+                id: q.id.clone(),
+                ..RecipeQuery::default()
+            })
+            .await?;
+        policy::require_recipe_access(&q.actor, current.author_id.as_deref())?;
+
+        q.source_url = q.source_url.map(|url| url.trim().to_string());
+        if let Some(url) = q.source_url.as_deref().filter(|url| !url.is_empty()) {
+            check_source_url(url)?;
+        }
+        q.attribution = q.attribution.map(|v| v.trim().to_string());
+        q.license = q.license.map(|v| v.trim().to_string());
+
+        if let Some(allergens) = q.allergens.as_deref() {
+            self.allergen_service.resolve(allergens).await?;
+        }
+        let ingredients = q.ingredients.as_ref().unwrap_or(&current.ingredients);
+
+        let manual = q.nutrients
+            != UpdateNutrients {
+                basis: q.nutrients.basis,
+                ..UpdateNutrients::default()
+            };
+        let computed = q
+            .compute_nutrients
+            .unwrap_or(current.nutrients_computed && !manual);
+        q.compute_nutrients = Some(computed);
+
+        let servings = q.servings.unwrap_or(current.servings);
+
+        let nutrients = if computed {
+            self.nutrition_service
+                .calculate(CalculateNutrientsQuery {
+                    ingredients: ingredients.clone(),
+                    servings,
+                })
+                .await?
+                .nutrients
+        } else {
+            let mut current = current.nutrients.clone();
+            if let Some(serving_weight) = q.nutrients.serving_weight {
+                current.serving_weight = Some(serving_weight);
+            }
+
+            let current = convert(current, q.nutrients.basis, servings)?;
+            let merged = merge_nutrients(current, &q.nutrients);
+
+            convert(merged, NutrientBasis::PerServing, servings)?
+        };
+        q.nutrients = full_update(nutrients.clone());
+
+        q.diets = Some(self.diet_service.classify(ingredients, &nutrients).await?);
+
+        let res = self.recipe_storage.update(q).await?;
+
+        self.fetch(RecipeQuery {
+            id: res.id,
+            visibility: RecipeVisibility::All,
+            ..RecipeQuery::default()
+        })
+        .await
+    }
+
+    /// Writes back the content of a revision as it was, without deriving
+    /// nutrients or labels again.
+    pub async fn apply_snapshot(
+        &self,
+        id: String,
+        actor: Actor,
+        snapshot: RecipeSnapshot,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        self.category_service
+            .fetch(CategoryQuery {
+                id: snapshot.category_id.clone(),
+            })
+            .await
+            .map_err(|_| {
+                ServiceError::Invalid(format!(
+                    "category with id {} no longer exists",
+                    snapshot.category_id
+                ))
+            })?;
+
+        let res = self
+            .recipe_storage
+            .update(UpdateRecipeCommand {
+                id,
+                actor,
+                cover: Some(snapshot.cover),
+                title: Some(snapshot.title),
+                description: Some(snapshot.description),
+                time_to_cook: Some(snapshot.time_to_cook),
+                difficulty: Some(snapshot.difficulty),
+                servings: Some(snapshot.servings),
+                category: Some(snapshot.category_id),
+                ingredients: Some(snapshot.ingredients),
+                allergens: Some(snapshot.allergens),
+                diets: Some(snapshot.diets),
+                nutrients: full_update(snapshot.nutrients),
+                compute_nutrients: Some(snapshot.nutrients_computed),
+                guideline: Some(snapshot.guideline),
+                source_url: Some(snapshot.source_url.unwrap_or_default()),
+                attribution: Some(snapshot.attribution),
+                license: Some(snapshot.license),
+            })
+            .await?;
+
+        self.fetch(RecipeQuery {
+            id: res.id,
+            visibility: RecipeVisibility::All,
+            ..RecipeQuery::default()
+        })
+        .await
+    }
+
+    /// Moves the recipe along the publication workflow. Authors submit,
+    /// withdraw and archive their own recipes, publishing and rejecting is
+    /// up to editors.
+    pub async fn transition(&self, q: TransitionRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        let current = self
+            .recipe_storage
+            .fetch(RecipeQuery {
+                id: q.id.clone(),
+                ..RecipeQuery::default()
+            })
+            .await?;
+
+        match q.transition {
+            RecipeTransition::Submit | RecipeTransition::Withdraw | RecipeTransition::Archive => {
+                policy::require_recipe_access(&q.actor, current.author_id.as_deref())?
+            }
+            RecipeTransition::Reject | RecipeTransition::Publish => {
+                policy::require(&q.actor, Role::Editor)?
+            }
+        }
+
+        if !q.transition.sources().contains(&current.status) {
+            return Err(ServiceError::Invalid(format!(
+                "recipe is {}, expected {}",
+                current.status.code(),
+                q.transition
+                    .sources()
+                    .iter()
+                    .map(|status| status.code())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ))
+            .into());
+        }
+
+        let res = self
+            .recipe_storage
+            .set_status(
+                q.id,
+                q.transition.target(),
+                Some(q.actor.user_id).filter(|id| !id.is_empty()),
+            )
+            .await?;
+
+        self.fetch(RecipeQuery {
+            id: res.id,
+            visibility: RecipeVisibility::All,
+            ..RecipeQuery::default()
+        })
+        .await
     }
 
     pub async fn delete(&self, q: DeleteRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        let current = self
+            .recipe_storage
+            .fetch(RecipeQuery {
+                id: q.id.clone(),
+                ..RecipeQuery::default()
+            })
+            .await?;
+        policy::require_recipe_access(&q.actor, current.author_id.as_deref())?;
+
         self.recipe_storage.delete(q).await
     }
 
+    pub async fn restore(&self, q: RestoreRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        let current = self
+            .recipe_storage
+            .fetch(RecipeQuery {
+                id: q.id.clone(),
+                include_deleted: true,
+                ..RecipeQuery::default()
+            })
+            .await?;
+        policy::require_recipe_access(&q.actor, current.author_id.as_deref())?;
+
+        if current.deleted_at.is_none() {
+            return Err(
+                ServiceError::Invalid(format!("recipe with id {} is not deleted", q.id)).into(),
+            );
+        }
+
+        self.category_service
+            .fetch(CategoryQuery {
+                id: current.category.id.clone(),
+            })
+            .await
+            .map_err(|_| {
+                ServiceError::Invalid(format!(
+                    "category with id {} no longer exists",
+                    current.category.id
+                ))
+            })?;
+
+        let res = self
+            .recipe_storage
+            .restore(q.id, Some(q.actor.user_id).filter(|id| !id.is_empty()))
+            .await?;
+
+        self.fetch(RecipeQuery {
+            id: res.id,
+            visibility: RecipeVisibility::All,
+            ..RecipeQuery::default()
+        })
+        .await
+    }
+
+    pub async fn purge(&self, q: PurgeDeletedCommand) -> Result<PurgeResult, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Admin)?;
+
+        self.purge_expired().await
+    }
+
+    /// Hard-deletes recipes, then categories, deleted longer ago than the
+    /// retention period. Also run periodically by the purge job.
+    pub async fn purge_expired(&self) -> Result<PurgeResult, Box<dyn Error>> {
+        let before = Utc::now().naive_utc() - self.deleted_retention;
+
+        let recipes = self.recipe_storage.purge(before).await?;
+        let categories = self.category_service.purge(before).await?;
+
+        Ok(PurgeResult {
+            recipes,
+            categories,
+        })
+    }
+
+    pub async fn calculate_nutrients(
+        &self,
+        q: CalculateNutrientsQuery,
+    ) -> Result<NutrientsCalculation, Box<dyn Error>> {
+        self.nutrition_service.calculate(q).await
+    }
+
+    /// Re-derives dietary labels of every recipe, e.g. after the ingredient
+    /// dictionary changed. Returns the number of recipes whose labels changed.
+    /// Admins only.
+    pub async fn reclassify(&self, q: ReclassifyRecipesCommand) -> Result<i64, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Admin)?;
+
+        let recipes = self
+            .recipe_storage
+            .search(RecipeSearchQuery {
+                visibility: RecipeVisibility::All,
+                ..RecipeSearchQuery::default()
+            })
+            .await?;
+
+        let facts: Vec<(&[String], &Nutrients)> = recipes
+            .items
+            .iter()
+            .map(|item| (item.ingredients.as_slice(), &item.nutrients))
+            .collect();
+        let labels = self.diet_service.classify_many(&facts).await?;
+
+        let mut changed = 0;
+
+        for (item, diets) in recipes.items.iter().zip(labels) {
+            if diets != item.diets {
+                self.recipe_storage
+                    .update(UpdateRecipeCommand {
+                        id: item.id.clone(),
+                        diets: Some(diets),
+                        ..UpdateRecipeCommand::default()
+                    })
+                    .await?;
+                changed += 1;
+            }
+        }
+
+        Ok(changed)
+    }
+
     pub async fn search(
         &self,
         q: RecipeSearchQuery,
     ) -> Result<SearchResult<Recipe>, Box<dyn Error>> {
+        let basis = q.basis;
+        let ingredients = q.ingredients.clone();
+        let viewer_id = q.viewer_id.clone();
         let mut res = self.recipe_storage.search(q).await?;
 
+        if let Some(names) = ingredients {
+            let matcher = self.ingredient_service.matcher().await?;
+            let uses = |item: &Recipe| {
+                item.ingredients
+                    .iter()
+                    .filter_map(|line| matcher.find(line))
+                    .filter(|found| names.contains(&found.name))
+                    .count()
+            };
+
+            let mut ranked: Vec<(usize, Recipe)> = res
+                .items
+                .into_iter()
+                .map(|item| (uses(&item), item))
+                .filter(|(count, _)| *count > 0)
+                .collect();
+            ranked.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+
+            res.count = ranked.len() as i64;
+            res.items = ranked.into_iter().map(|(_, item)| item).collect();
+        }
+
         let category_ids = res
             .items
             .iter()
@@ -75,18 +462,148 @@ impl RecipeService {
             .collect();
         let categories = self
             .category_service
-            .search(CategorySearchQuery { ids: category_ids })
+            .search(CategorySearchQuery {
+                ids: category_ids,
+                ..CategorySearchQuery::default()
+            })
+            .await?;
+        let categories = categories
+            .items
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect::<HashMap<String, Category>>();
+
+        let allergen_codes = res.items.iter().flat_map(codes).collect();
+        let allergens = self
+            .allergen_service
+            .search(AllergenSearchQuery {
+                codes: Some(allergen_codes),
+            })
             .await?;
-        let category_to_name = categories
+        let allergens = allergens
             .items
             .into_iter()
-            .map(|item| (item.id, item.name))
-            .collect::<HashMap<String, String>>();
+            .map(|item| (item.code.clone(), item))
+            .collect::<HashMap<String, Allergen>>();
+
+        // recipes whose category was deleted since they were read are left out
+        let found = res.items.len();
+        res.items
+            .retain(|item| categories.contains_key(&item.category.id));
+        res.count -= (found - res.items.len()) as i64;
 
         for item in res.items.iter_mut() {
-            item.category.name = category_to_name.get(&item.category.id).unwrap().clone();
+            if let Some(category) = categories.get(&item.category.id) {
+                item.category = category.clone();
+            }
+
+            // recipes lacking a serving weight stay per serving instead of
+            // failing the whole search, the basis field tells them apart
+            if let Ok(nutrients) = convert(item.nutrients.clone(), basis, item.servings) {
+                item.nutrients = nutrients;
+            }
+
+            for allergen in item.allergens.iter_mut() {
+                if let Some(found) = allergens.get(&allergen.code) {
+                    *allergen = found.clone();
+                }
+            }
+        }
+
+        if let Some(viewer_id) = viewer_id {
+            self.flag_favorites(viewer_id, &mut res.items).await?;
         }
 
         Ok(res)
     }
+
+    async fn flag_favorites(
+        &self,
+        owner_id: String,
+        items: &mut [Recipe],
+    ) -> Result<(), Box<dyn Error>> {
+        let ids = items.iter().map(|item| item.id.clone()).collect();
+        let saved = self.favorite_storage.saved(owner_id, ids).await?;
+
+        for item in items.iter_mut() {
+            item.is_favorite = saved.contains(&item.id);
+        }
+
+        Ok(())
+    }
+}
+
+fn check_source_url(url: &str) -> Result<(), Box<dyn Error>> {
+    let valid = ["http://", "https://"].iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+    });
+
+    if !valid {
+        return Err(ServiceError::Invalid("sourceUrl must be an http(s) URL".into()).into());
+    }
+
+    Ok(())
+}
+
+fn codes(item: &Recipe) -> Vec<String> {
+    item.allergens
+        .iter()
+        .map(|allergen| allergen.code.clone())
+        .collect()
+}
+
+fn merge_nutrients(mut current: Nutrients, update: &UpdateNutrients) -> Nutrients {
+    if let Some(serving_weight) = update.serving_weight {
+        current.serving_weight = Some(serving_weight);
+    }
+    if let Some(kcal) = update.kcal {
+        current.kcal = kcal;
+    }
+    if let Some(proteins) = update.proteins {
+        current.proteins = proteins;
+    }
+    if let Some(fats) = update.fats {
+        current.fats = fats;
+    }
+    if let Some(saturated_fat) = update.saturated_fat {
+        current.saturated_fat = saturated_fat;
+    }
+    if let Some(carbohydrates) = update.carbohydrates {
+        current.carbohydrates = carbohydrates;
+    }
+    if let Some(sugars) = update.sugars {
+        current.sugars = sugars;
+    }
+    if let Some(fiber) = update.fiber {
+        current.fiber = fiber;
+    }
+    if let Some(sodium) = update.sodium {
+        current.sodium = sodium;
+    }
+    if let Some(cholesterol) = update.cholesterol {
+        current.cholesterol = cholesterol;
+    }
+    if let Some(micronutrients) = update.micronutrients.as_ref() {
+        current.micronutrients = micronutrients.clone();
+    }
+
+    current
+}
+
+pub(super) fn full_update(value: Nutrients) -> UpdateNutrients {
+    UpdateNutrients {
+        basis: value.basis,
+        serving_weight: value.serving_weight,
+        kcal: Some(value.kcal),
+        proteins: Some(value.proteins),
+        fats: Some(value.fats),
+        saturated_fat: Some(value.saturated_fat),
+        carbohydrates: Some(value.carbohydrates),
+        sugars: Some(value.sugars),
+        fiber: Some(value.fiber),
+        sodium: Some(value.sodium),
+        cholesterol: Some(value.cholesterol),
+        micronutrients: Some(value.micronutrients),
+    }
 }
@@ -0,0 +1,426 @@
+use std::{collections::HashMap, error::Error, sync::Arc};
+
+use crate::model::{
+    Recipe, RecipeQuery, RecipeSearchQuery, Recommendation, RecommendationQuery,
+    RecommendationReason, ServiceError, SimilarRecipe, SimilarRecipesQuery, MAX_RATING, MIN_RATING,
+};
+
+use super::{
+    similarity::{compare, Features},
+    CookingLogService, FavoriteService, IngredientService, MealPlanService, PreferenceService,
+    RecipeService, ReviewService,
+};
+
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 100;
+
+/// Score added for recipes of a preferred category.
+const CATEGORY_BONUS: f64 = 0.2;
+
+/// Weight of popularity next to the similarity to the user's recipes.
+const POPULARITY_WEIGHT: f64 = 0.1;
+
+/// Applied to recipes the user already picked, so new ones come first.
+const SEEN_FACTOR: f64 = 0.5;
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_service: Arc<MealPlanService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub preference_service: Arc<PreferenceService>,
+    pub favorite_service: Arc<FavoriteService>,
+    pub review_service: Arc<ReviewService>,
+    pub cooking_log_service: Arc<CookingLogService>,
+}
+
+pub struct RecommendationService {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_service: Arc<MealPlanService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub preference_service: Arc<PreferenceService>,
+    pub favorite_service: Arc<FavoriteService>,
+    pub review_service: Arc<ReviewService>,
+    pub cooking_log_service: Arc<CookingLogService>,
+}
+
+/// How users engaged with recipes, by recipe id.
+#[derive(Default)]
+struct Engagement {
+    planned: Vec<(String, i64)>,
+    cooked: Vec<(String, i64)>,
+    saved: Vec<(String, i64)>,
+    /// Average rating with the number of ratings behind it.
+    rated: Vec<(String, f64, i64)>,
+}
+
+impl Engagement {
+    /// Weight of each recipe engaged with. Planning, cooking and saving add
+    /// the log of their count, so repeats count less than the first time.
+    /// Ratings add the same for their count, scaled from -1 for the lowest
+    /// rating to 1 for the highest.
+    fn weights(&self) -> HashMap<String, f64> {
+        let mut res: HashMap<String, f64> = HashMap::new();
+
+        for (id, count) in self
+            .planned
+            .iter()
+            .chain(self.cooked.iter())
+            .chain(self.saved.iter())
+        {
+            *res.entry(id.clone()).or_default() += (1.0 + *count as f64).ln();
+        }
+
+        let middle = (MIN_RATING + MAX_RATING) as f64 / 2.0;
+        let half = (MAX_RATING - MIN_RATING) as f64 / 2.0;
+        for (id, average, count) in self.rated.iter() {
+            *res.entry(id.clone()).or_default() +=
+                (average - middle) / half * (1.0 + *count as f64).ln();
+        }
+
+        res
+    }
+}
+
+impl RecommendationService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            meal_plan_service: cfg.meal_plan_service,
+            ingredient_service: cfg.ingredient_service,
+            preference_service: cfg.preference_service,
+            favorite_service: cfg.favorite_service,
+            review_service: cfg.review_service,
+            cooking_log_service: cfg.cooking_log_service,
+        }
+    }
+
+    /// Recipes matching the user's preferences, scored by their similarity
+    /// to the recipes the user planned, cooked, saved or rated well. Recipes
+    /// the user rated badly are left out. Users nobody knows anything about
+    /// get the most popular recipes.
+    pub async fn recommend(
+        &self,
+        q: RecommendationQuery,
+    ) -> Result<Vec<Recommendation>, Box<dyn Error>> {
+        let limit = check_limit(q.limit)?;
+        let preferences = self.preference_service.fetch(&q.owner_id).await?;
+        let matcher = self.ingredient_service.matcher().await?;
+
+        let candidates: Vec<(Recipe, Features)> = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                diets: (!preferences.diets.is_empty()).then(|| preferences.diets.clone()),
+                exclude_allergens: (!preferences.exclude_allergens.is_empty())
+                    .then(|| preferences.exclude_allergens.clone()),
+                ..RecipeSearchQuery::default()
+            })
+            .await?
+            .items
+            .into_iter()
+            .map(|recipe| {
+                let features = Features::new(&recipe, &matcher);
+                (recipe, features)
+            })
+            .filter(|(_, features)| {
+                !features.ingredients.iter().any(|name| {
+                    preferences
+                        .disliked_ingredients
+                        .iter()
+                        .any(|disliked| name.contains(disliked.as_str()))
+                })
+            })
+            .collect();
+
+        let mut popularity = self.engagement(None).await?;
+        popularity.rated = candidates
+            .iter()
+            .filter_map(|(recipe, _)| {
+                recipe
+                    .rating_average
+                    .map(|average| (recipe.id.clone(), average, recipe.rating_count))
+            })
+            .collect();
+        let popularity = popularity.weights();
+        let max_popularity = popularity.values().copied().fold(0.0, f64::max);
+        let popular = |id: &str| match max_popularity > 0.0 {
+            true => popularity.get(id).copied().unwrap_or_default().max(0.0) / max_popularity,
+            false => 0.0,
+        };
+
+        let mut history = self.engagement(Some(q.owner_id.clone())).await?;
+        history.rated = self
+            .review_service
+            .ratings(&q.owner_id)
+            .await?
+            .into_iter()
+            .map(|(id, rating)| (id, rating as f64, 1))
+            .collect();
+        let history = history.weights();
+
+        let candidates: Vec<(Recipe, Features)> = candidates
+            .into_iter()
+            .filter(|(recipe, _)| history.get(&recipe.id).is_none_or(|weight| *weight >= 0.0))
+            .collect();
+        let profile: Vec<(&Features, f64)> = candidates
+            .iter()
+            .filter_map(|(recipe, features)| {
+                history
+                    .get(&recipe.id)
+                    .filter(|weight| **weight > 0.0)
+                    .map(|weight| (features, *weight))
+            })
+            .collect();
+        let profile_weight: f64 = profile.iter().map(|(_, weight)| weight).sum();
+
+        let cold = profile.is_empty() && preferences.categories.is_empty();
+
+        let mut res: Vec<Recommendation> = candidates
+            .iter()
+            .map(|(recipe, features)| {
+                if cold {
+                    return Recommendation {
+                        recipe: recipe.clone(),
+                        score: popular(&recipe.id),
+                        reason: RecommendationReason::Popular,
+                    };
+                }
+
+                let mut score = POPULARITY_WEIGHT * popular(&recipe.id);
+
+                if profile_weight > 0.0 {
+                    score += profile
+                        .iter()
+                        .map(|(other, weight)| compare(features, other).score * weight)
+                        .sum::<f64>()
+                        / profile_weight;
+                }
+
+                if preferences.categories.contains(&recipe.category.id) {
+                    score += CATEGORY_BONUS;
+                }
+
+                if history.contains_key(&recipe.id) {
+                    score *= SEEN_FACTOR;
+                }
+
+                Recommendation {
+                    recipe: recipe.clone(),
+                    score,
+                    reason: RecommendationReason::Profile,
+                }
+            })
+            .collect();
+
+        res.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.recipe.title.cmp(&b.recipe.title))
+        });
+        res.truncate(limit);
+
+        for item in res.iter_mut() {
+            item.score = round3(item.score);
+        }
+
+        Ok(res)
+    }
+
+    /// The recipes closest to the given one, most similar first.
+    pub async fn similar(
+        &self,
+        q: SimilarRecipesQuery,
+    ) -> Result<Vec<SimilarRecipe>, Box<dyn Error>> {
+        let limit = check_limit(q.limit)?;
+
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: q.id,
+                ..RecipeQuery::default()
+            })
+            .await?;
+        let matcher = self.ingredient_service.matcher().await?;
+        let features = Features::new(&recipe, &matcher);
+
+        let mut res: Vec<SimilarRecipe> = self
+            .recipe_service
+            .search(RecipeSearchQuery::default())
+            .await?
+            .items
+            .into_iter()
+            .filter(|other| other.id != recipe.id)
+            .map(|other| {
+                let similarity = compare(&features, &Features::new(&other, &matcher));
+
+                SimilarRecipe {
+                    recipe: other,
+                    similarity,
+                }
+            })
+            .collect();
+
+        res.sort_by(|a, b| {
+            b.similarity
+                .score
+                .total_cmp(&a.similarity.score)
+                .then_with(|| a.recipe.title.cmp(&b.recipe.title))
+        });
+        res.truncate(limit);
+
+        for item in res.iter_mut() {
+            let similarity = &mut item.similarity;
+            for value in [
+                &mut similarity.ingredients,
+                &mut similarity.category,
+                &mut similarity.tags,
+                &mut similarity.nutrients,
+                &mut similarity.time,
+                &mut similarity.difficulty,
+                &mut similarity.score,
+            ] {
+                *value = round3(*value);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// How often recipes were planned, cooked and saved, by the owner or by
+    /// anyone when unset. Ratings are left to the caller.
+    async fn engagement(&self, owner_id: Option<String>) -> Result<Engagement, Box<dyn Error>> {
+        let planned = self
+            .meal_plan_service
+            .recipe_counts(owner_id.clone())
+            .await?;
+        let cooked = self
+            .cooking_log_service
+            .recipe_counts(owner_id.clone())
+            .await?;
+        let saved = self.favorite_service.recipe_counts(owner_id).await?;
+
+        Ok(Engagement {
+            planned,
+            cooked,
+            saved,
+            rated: Vec::new(),
+        })
+    }
+}
+
+fn check_limit(limit: Option<i64>) -> Result<usize, Box<dyn Error>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(
+            ServiceError::Invalid(format!("limit must be between 1 and {}", MAX_LIMIT)).into(),
+        );
+    }
+
+    Ok(limit as usize)
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(values: &[(&str, i64)]) -> Vec<(String, i64)> {
+        values
+            .iter()
+            .map(|(id, count)| (id.to_string(), *count))
+            .collect()
+    }
+
+    fn weight(engagement: Engagement, id: &str) -> f64 {
+        round3(engagement.weights().get(id).copied().unwrap_or_default())
+    }
+
+    #[test]
+    fn planned_recipes_weigh_less_for_repeats() {
+        let once = weight(
+            Engagement {
+                planned: counts(&[("a", 1)]),
+                ..Engagement::default()
+            },
+            "a",
+        );
+        let thrice = weight(
+            Engagement {
+                planned: counts(&[("a", 3)]),
+                ..Engagement::default()
+            },
+            "a",
+        );
+
+        assert_eq!(once, 0.693);
+        assert_eq!(thrice, 1.386);
+    }
+
+    #[test]
+    fn cooked_recipes_count() {
+        let engagement = Engagement {
+            cooked: counts(&[("a", 1)]),
+            ..Engagement::default()
+        };
+
+        assert_eq!(weight(engagement, "a"), 0.693);
+    }
+
+    #[test]
+    fn saved_recipes_count() {
+        let engagement = Engagement {
+            saved: counts(&[("a", 1)]),
+            ..Engagement::default()
+        };
+
+        assert_eq!(weight(engagement, "a"), 0.693);
+    }
+
+    #[test]
+    fn ratings_scale_from_minus_one_to_one() {
+        let cases = [
+            (1.0, -0.693),
+            (2.0, -0.347),
+            (3.0, 0.0),
+            (4.0, 0.347),
+            (5.0, 0.693),
+        ];
+
+        for (rating, expected) in cases {
+            let engagement = Engagement {
+                rated: vec![("a".to_string(), rating, 1)],
+                ..Engagement::default()
+            };
+
+            assert_eq!(weight(engagement, "a"), expected, "{:?}", rating);
+        }
+    }
+
+    #[test]
+    fn more_ratings_weigh_more() {
+        let engagement = Engagement {
+            rated: vec![("a".to_string(), 5.0, 1), ("b".to_string(), 5.0, 10)],
+            ..Engagement::default()
+        };
+        let weights = engagement.weights();
+
+        assert!(weights["b"] > weights["a"]);
+    }
+
+    #[test]
+    fn signals_add_up() {
+        let engagement = Engagement {
+            planned: counts(&[("a", 1)]),
+            cooked: counts(&[("a", 1), ("b", 1)]),
+            saved: counts(&[("a", 1)]),
+            rated: vec![("a".to_string(), 5.0, 1)],
+        };
+        let weights = engagement.weights();
+
+        assert_eq!(round3(weights["a"]), 2.773);
+        assert_eq!(round3(weights["b"]), 0.693);
+        assert!(!weights.contains_key("c"));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        CreateReviewCommand, DeleteReviewCommand, FlagReviewCommand, FlaggedReviewsQuery,
+        ModerateReviewCommand, RecipeQuery, Review, ReviewQuery, ReviewSearchQuery, Role,
+        SearchResult, ServiceError, UpdateReviewCommand, MAX_RATING, MIN_RATING,
+    },
+    repository::ReviewRepository,
+};
+
+use super::{check_pagination, policy, RecipeService};
+
+const MAX_BODY_LENGTH: usize = 5000;
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub review_storage: Arc<ReviewRepository>,
+}
+
+pub struct ReviewService {
+    pub recipe_service: Arc<RecipeService>,
+    pub review_storage: Arc<ReviewRepository>,
+}
+
+impl ReviewService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            review_storage: cfg.review_storage,
+        }
+    }
+
+    pub async fn search(
+        &self,
+        q: ReviewSearchQuery,
+    ) -> Result<SearchResult<Review>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.review_storage.search(q).await
+    }
+
+    /// Recipe ids with the rating the owner gave them.
+    pub async fn ratings(&self, owner_id: &str) -> Result<Vec<(String, i32)>, Box<dyn Error>> {
+        self.review_storage.ratings(owner_id.to_string()).await
+    }
+
+    /// Reviews waiting for moderation, editors only.
+    pub async fn flagged(
+        &self,
+        q: FlaggedReviewsQuery,
+    ) -> Result<SearchResult<Review>, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        self.search(ReviewSearchQuery {
+            recipe_id: None,
+            flagged: true,
+            pagination: q.pagination,
+        })
+        .await
+    }
+
+    /// Adds the review of a user, there is one per user and recipe.
+    pub async fn create(&self, mut item: CreateReviewCommand) -> Result<Review, Box<dyn Error>> {
+        check_rating(item.rating)?;
+        item.body = check_body(&item.body)?;
+
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: item.recipe_id.clone(),
+                ..RecipeQuery::default()
+            })
+            .await
+            .map_err(|_| {
+                ServiceError::Invalid(format!("recipe with id {} not found", item.recipe_id))
+            })?;
+        if recipe.author_id.as_deref() == Some(item.owner_id.as_str()) {
+            return Err(
+                ServiceError::Forbidden("authors can't review their own recipes".into()).into(),
+            );
+        }
+
+        if self
+            .review_storage
+            .find(item.recipe_id.clone(), item.owner_id.clone())
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::Invalid(
+                "recipe is already reviewed, change the review instead".into(),
+            )
+            .into());
+        }
+
+        self.review_storage.create(item).await
+    }
+
+    pub async fn update(&self, mut q: UpdateReviewCommand) -> Result<Review, Box<dyn Error>> {
+        if let Some(rating) = q.rating {
+            check_rating(rating)?;
+        }
+        if let Some(body) = q.body.as_deref() {
+            q.body = Some(check_body(body)?);
+        }
+
+        let current = self
+            .review_storage
+            .fetch(ReviewQuery {
+                id: q.id.clone(),
+                recipe_id: q.recipe_id.clone(),
+            })
+            .await?;
+        if current.owner_id != q.owner_id {
+            return Err(
+                ServiceError::Forbidden("only the author may change this review".into()).into(),
+            );
+        }
+
+        self.review_storage.update(q).await
+    }
+
+    pub async fn delete(&self, q: DeleteReviewCommand) -> Result<Review, Box<dyn Error>> {
+        let current = self
+            .review_storage
+            .fetch(ReviewQuery {
+                id: q.id.clone(),
+                recipe_id: q.recipe_id.clone(),
+            })
+            .await?;
+        if current.owner_id != q.actor.user_id {
+            policy::require(&q.actor, Role::Editor)?;
+        }
+
+        self.review_storage.delete(q).await
+    }
+
+    pub async fn flag(&self, mut q: FlagReviewCommand) -> Result<Review, Box<dyn Error>> {
+        q.reason = q.reason.trim().to_string();
+
+        self.review_storage.flag(q).await
+    }
+
+    pub async fn moderate(&self, q: ModerateReviewCommand) -> Result<Review, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        self.review_storage.moderate(q).await
+    }
+}
+
+fn check_rating(rating: i32) -> Result<(), Box<dyn Error>> {
+    if !(MIN_RATING..=MAX_RATING).contains(&rating) {
+        return Err(ServiceError::Invalid(format!(
+            "rating must be between {} and {}",
+            MIN_RATING, MAX_RATING
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+fn check_body(body: &str) -> Result<String, Box<dyn Error>> {
+    let body = body.trim();
+    if body.chars().count() > MAX_BODY_LENGTH {
+        return Err(ServiceError::Invalid(format!(
+            "review must not exceed {} characters",
+            MAX_BODY_LENGTH
+        ))
+        .into());
+    }
+
+    Ok(body.to_string())
+}
@@ -0,0 +1,370 @@
+use std::{collections::BTreeSet, error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        Actor, FieldChange, ListChange, Recipe, RecipeQuery, RecipeRevision, RecipeSnapshot,
+        RecipeVisibility, RevisionDiff, RevisionDiffQuery, RevisionQuery, RevisionSearchQuery,
+        RollbackRecipeCommand, SearchResult,
+    },
+    repository::RevisionRepository,
+};
+
+use super::{check_pagination, policy, RecipeService};
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub revision_storage: Arc<RevisionRepository>,
+}
+
+/// History of recipe changes. Only those who may change a recipe see its
+/// history.
+pub struct RevisionService {
+    pub recipe_service: Arc<RecipeService>,
+    pub revision_storage: Arc<RevisionRepository>,
+}
+
+impl RevisionService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            revision_storage: cfg.revision_storage,
+        }
+    }
+
+    pub async fn search(
+        &self,
+        q: RevisionSearchQuery,
+    ) -> Result<SearchResult<RecipeRevision>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+        self.check_access(&q.recipe_id, &q.actor).await?;
+
+        self.revision_storage
+            .search(q.recipe_id, q.pagination)
+            .await
+    }
+
+    pub async fn fetch(&self, q: RevisionQuery) -> Result<RecipeRevision, Box<dyn Error>> {
+        self.check_access(&q.recipe_id, &q.actor).await?;
+
+        self.revision_storage.fetch(q.recipe_id, q.number).await
+    }
+
+    pub async fn diff(&self, q: RevisionDiffQuery) -> Result<RevisionDiff, Box<dyn Error>> {
+        self.check_access(&q.recipe_id, &q.actor).await?;
+
+        let from = self
+            .revision_storage
+            .fetch(q.recipe_id.clone(), q.from)
+            .await?;
+        let to = self
+            .revision_storage
+            .fetch(q.recipe_id.clone(), q.to)
+            .await?;
+
+        Ok(RevisionDiff {
+            recipe_id: q.recipe_id,
+            from: q.from,
+            to: q.to,
+            fields: field_changes(&from.snapshot, &to.snapshot),
+            lists: list_changes(&from.snapshot, &to.snapshot),
+        })
+    }
+
+    /// Restores the content of an earlier revision. The publication status
+    /// is left as it is.
+    pub async fn rollback(&self, q: RollbackRecipeCommand) -> Result<Recipe, Box<dyn Error>> {
+        self.check_access(&q.recipe_id, &q.actor).await?;
+
+        let revision = self
+            .revision_storage
+            .fetch(q.recipe_id.clone(), q.number)
+            .await?;
+
+        self.recipe_service
+            .apply_snapshot(q.recipe_id, q.actor, revision.snapshot)
+            .await
+    }
+
+    async fn check_access(&self, recipe_id: &str, actor: &Actor) -> Result<(), Box<dyn Error>> {
+        let recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: recipe_id.to_string(),
+                visibility: RecipeVisibility::All,
+                ..RecipeQuery::default()
+            })
+            .await?;
+
+        policy::require_recipe_access(actor, recipe.author_id.as_deref())
+    }
+}
+
+fn field_changes(from: &RecipeSnapshot, to: &RecipeSnapshot) -> Vec<FieldChange> {
+    let text = |value: &Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut pairs = vec![
+        ("title", from.title.clone(), to.title.clone()),
+        (
+            "description",
+            from.description.clone(),
+            to.description.clone(),
+        ),
+        (
+            "cover",
+            String::from_utf8_lossy(&from.cover).into_owned(),
+            String::from_utf8_lossy(&to.cover).into_owned(),
+        ),
+        (
+            "timeToCook",
+            from.time_to_cook.to_string(),
+            to.time_to_cook.to_string(),
+        ),
+        ("difficulty", from.difficulty.clone(), to.difficulty.clone()),
+        (
+            "servings",
+            from.servings.to_string(),
+            to.servings.to_string(),
+        ),
+        ("category", from.category_id.clone(), to.category_id.clone()),
+        ("guideline", from.guideline.clone(), to.guideline.clone()),
+        (
+            "sourceUrl",
+            from.source_url.clone().unwrap_or_default(),
+            to.source_url.clone().unwrap_or_default(),
+        ),
+        (
+            "attribution",
+            from.attribution.clone(),
+            to.attribution.clone(),
+        ),
+        ("license", from.license.clone(), to.license.clone()),
+        (
+            "status",
+            from.status
+                .map(|s| s.code().to_string())
+                .unwrap_or_default(),
+            to.status.map(|s| s.code().to_string()).unwrap_or_default(),
+        ),
+        (
+            "nutrientsComputed",
+            from.nutrients_computed.to_string(),
+            to.nutrients_computed.to_string(),
+        ),
+        (
+            "nutrients.servingWeight",
+            text(&from.nutrients.serving_weight),
+            text(&to.nutrients.serving_weight),
+        ),
+    ];
+
+    let (a, b) = (&from.nutrients, &to.nutrients);
+    for (field, before, after) in [
+        ("nutrients.kcal", a.kcal, b.kcal),
+        ("nutrients.proteins", a.proteins, b.proteins),
+        ("nutrients.fats", a.fats, b.fats),
+        ("nutrients.saturatedFat", a.saturated_fat, b.saturated_fat),
+        ("nutrients.carbohydrates", a.carbohydrates, b.carbohydrates),
+        ("nutrients.sugars", a.sugars, b.sugars),
+        ("nutrients.fiber", a.fiber, b.fiber),
+        ("nutrients.sodium", a.sodium, b.sodium),
+        ("nutrients.cholesterol", a.cholesterol, b.cholesterol),
+    ] {
+        pairs.push((field, before.to_string(), after.to_string()));
+    }
+
+    let mut changes: Vec<FieldChange> = pairs
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(field, before, after)| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect();
+
+    let names: BTreeSet<&String> = a
+        .micronutrients
+        .keys()
+        .chain(b.micronutrients.keys())
+        .collect();
+    for name in names {
+        let before = text(&a.micronutrients.get(name).copied());
+        let after = text(&b.micronutrients.get(name).copied());
+        if before != after {
+            changes.push(FieldChange {
+                field: format!("nutrients.micronutrients.{}", name),
+                before,
+                after,
+            });
+        }
+    }
+
+    changes
+}
+
+fn list_changes(from: &RecipeSnapshot, to: &RecipeSnapshot) -> Vec<ListChange> {
+    let codes = |snapshot: &RecipeSnapshot| -> Vec<String> {
+        snapshot
+            .diets
+            .iter()
+            .map(|diet| diet.code().to_string())
+            .collect()
+    };
+
+    [
+        (
+            "ingredients",
+            from.ingredients.clone(),
+            to.ingredients.clone(),
+        ),
+        ("allergens", from.allergens.clone(), to.allergens.clone()),
+        ("diets", codes(from), codes(to)),
+    ]
+    .into_iter()
+    .filter(|(_, before, after)| before != after)
+    .map(|(field, before, after)| {
+        let removed = missing(&before, &after);
+        let added = missing(&after, &before);
+
+        ListChange {
+            field: field.to_string(),
+            reordered: added.is_empty() && removed.is_empty(),
+            added,
+            removed,
+        }
+    })
+    .collect()
+}
+
+/// Entries of `list` that `other` lacks, counting repeated entries.
+fn missing(list: &[String], other: &[String]) -> Vec<String> {
+    let mut rest: Vec<&String> = other.iter().collect();
+
+    list.iter()
+        .filter(|entry| match rest.iter().position(|found| found == entry) {
+            Some(i) => {
+                rest.swap_remove(i);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Diet, RecipeStatus};
+
+    fn snapshot() -> RecipeSnapshot {
+        RecipeSnapshot {
+            title: "Pancakes".to_string(),
+            servings: 4,
+            ingredients: vec![
+                "200 g wheat flour".to_string(),
+                "2 eggs".to_string(),
+                "300 ml milk".to_string(),
+            ],
+            allergens: vec!["gluten".to_string(), "eggs".to_string()],
+            diets: vec![Diet::Vegetarian],
+            status: Some(RecipeStatus::Draft),
+            ..RecipeSnapshot::default()
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn change(field: &str, before: &str, after: &str) -> FieldChange {
+        FieldChange {
+            field: field.to_string(),
+            before: before.to_string(),
+            after: after.to_string(),
+        }
+    }
+
+    #[test]
+    fn unchanged_snapshot_has_no_changes() {
+        assert!(field_changes(&snapshot(), &snapshot()).is_empty());
+        assert!(list_changes(&snapshot(), &snapshot()).is_empty());
+    }
+
+    #[test]
+    fn field_changes_lists_changed_fields() {
+        let mut to = snapshot();
+        to.title = "Crêpes".to_string();
+        to.servings = 6;
+        to.status = Some(RecipeStatus::Published);
+        to.nutrients.kcal = 250.0;
+        to.nutrients.micronutrients.insert("iron".to_string(), 1.5);
+
+        assert_eq!(
+            field_changes(&snapshot(), &to),
+            vec![
+                change("title", "Pancakes", "Crêpes"),
+                change("servings", "4", "6"),
+                change("status", "draft", "published"),
+                change("nutrients.kcal", "0", "250"),
+                change("nutrients.micronutrients.iron", "", "1.5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_changes_reports_added_and_removed_items() {
+        let mut to = snapshot();
+        to.ingredients = strings(&["200 g wheat flour", "300 ml oat milk", "1 tbsp sugar"]);
+        to.diets = vec![Diet::Vegetarian, Diet::Vegan];
+
+        assert_eq!(
+            list_changes(&snapshot(), &to),
+            vec![
+                ListChange {
+                    field: "ingredients".to_string(),
+                    added: strings(&["300 ml oat milk", "1 tbsp sugar"]),
+                    removed: strings(&["2 eggs", "300 ml milk"]),
+                    reordered: false,
+                },
+                ListChange {
+                    field: "diets".to_string(),
+                    added: strings(&["vegan"]),
+                    removed: Vec::new(),
+                    reordered: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn list_changes_reports_reordering() {
+        let mut to = snapshot();
+        to.allergens.reverse();
+
+        assert_eq!(
+            list_changes(&snapshot(), &to),
+            vec![ListChange {
+                field: "allergens".to_string(),
+                added: Vec::new(),
+                removed: Vec::new(),
+                reordered: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_counts_repeated_entries() {
+        let list = strings(&["egg", "egg", "milk"]);
+
+        assert_eq!(
+            missing(&list, &strings(&["egg"])),
+            strings(&["egg", "milk"])
+        );
+        assert_eq!(
+            missing(&list, &strings(&["milk", "egg", "egg"])),
+            strings(&[])
+        );
+        assert_eq!(missing(&strings(&[]), &list), strings(&[]));
+    }
+}
@@ -0,0 +1,460 @@
+use std::{collections::BTreeMap, error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        CheckShoppingListItemCommand, CreateShoppingListCommand, DeleteShoppingListCommand,
+        MealPlanQuery, PantryItem, Recipe, RecipeSearchQuery, RecipeServings, SearchResult,
+        ServiceError, ShoppingList, ShoppingListItem, ShoppingListQuery, ShoppingListSearchQuery,
+        Unit, UnitKind,
+    },
+    repository::ShoppingListRepository,
+};
+
+use super::{
+    check_pagination, ingredient::Matcher, nutrition::round2, IngredientService, MealPlanService,
+    PantryService, RecipeService,
+};
+
+/// Aisle of ingredients missing from the dictionary.
+const DEFAULT_AISLE: &str = "other";
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_service: Arc<MealPlanService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub pantry_service: Arc<PantryService>,
+    pub shopping_list_storage: Arc<ShoppingListRepository>,
+}
+
+pub struct ShoppingListService {
+    pub recipe_service: Arc<RecipeService>,
+    pub meal_plan_service: Arc<MealPlanService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub pantry_service: Arc<PantryService>,
+    pub shopping_list_storage: Arc<ShoppingListRepository>,
+}
+
+/// Amount of one ingredient summed in the base unit of its kind.
+struct Total {
+    kind: Option<UnitKind>,
+    amount: f64,
+}
+
+impl ShoppingListService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            meal_plan_service: cfg.meal_plan_service,
+            ingredient_service: cfg.ingredient_service,
+            pantry_service: cfg.pantry_service,
+            shopping_list_storage: cfg.shopping_list_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: ShoppingListQuery) -> Result<ShoppingList, Box<dyn Error>> {
+        self.shopping_list_storage.fetch(q).await
+    }
+
+    /// Consolidates the ingredients of the requested recipes, or of every
+    /// slot of a meal plan, into a new list less what is in the pantry.
+    pub async fn create(
+        &self,
+        item: CreateShoppingListCommand,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        let recipes = match item.meal_plan_id.as_ref() {
+            Some(id) => self
+                .meal_plan_service
+                .fetch(MealPlanQuery {
+                    id: id.clone(),
+                    owner_id: item.owner_id.clone(),
+                })
+                .await?
+                .slots
+                .into_iter()
+                .map(|slot| RecipeServings {
+                    servings: Some(slot.planned_servings()),
+                    recipe_id: slot.recipe_id,
+                })
+                .collect(),
+            None => item.recipes,
+        };
+
+        let pantry = match item.ignore_pantry {
+            true => Vec::new(),
+            false => self.pantry_service.items(&item.owner_id).await?,
+        };
+
+        let (items, missing_recipes) = self.consolidate(&recipes, &pantry).await?;
+
+        self.shopping_list_storage
+            .create(ShoppingList {
+                owner_id: item.owner_id,
+                name: item.name,
+                meal_plan_id: item.meal_plan_id,
+                items,
+                missing_recipes,
+                ..ShoppingList::default()
+            })
+            .await
+    }
+
+    pub async fn check_item(
+        &self,
+        q: CheckShoppingListItemCommand,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        self.shopping_list_storage.check_item(q).await
+    }
+
+    pub async fn delete(
+        &self,
+        q: DeleteShoppingListCommand,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        self.shopping_list_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        q: ShoppingListSearchQuery,
+    ) -> Result<SearchResult<ShoppingList>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        self.shopping_list_storage.search(q).await
+    }
+
+    /// Fetches the requested recipes and merges their ingredients, see
+    /// `merge`. Recipes that are missing, deleted or unpublished are left
+    /// out and returned next to the items.
+    async fn consolidate(
+        &self,
+        requested: &[RecipeServings],
+        pantry: &[PantryItem],
+    ) -> Result<(Vec<ShoppingListItem>, Vec<String>), Box<dyn Error>> {
+        if requested.is_empty() {
+            return Err(
+                ServiceError::Invalid("shopping list needs at least one recipe".into()).into(),
+            );
+        }
+
+        if requested
+            .iter()
+            .any(|item| item.servings.is_some_and(|servings| servings <= 0))
+        {
+            return Err(ServiceError::Invalid("servings must be positive".into()).into());
+        }
+
+        let recipes = self
+            .recipe_service
+            .search(RecipeSearchQuery {
+                ids: Some(
+                    requested
+                        .iter()
+                        .map(|item| item.recipe_id.clone())
+                        .collect(),
+                ),
+                ..RecipeSearchQuery::default()
+            })
+            .await?
+            .items;
+        if recipes.is_empty() {
+            return Err(ServiceError::Invalid("none of the recipes were found".into()).into());
+        }
+
+        let matcher = self.ingredient_service.matcher().await?;
+
+        Ok(merge(requested, &recipes, &matcher, pantry))
+    }
+}
+
+/// Merges the ingredient lines of the recipes scaled to the requested
+/// servings, keyed by dictionary entry and unit kind, ordered by aisle.
+/// Pantry amounts of the same entry and kind are taken off, entries the
+/// pantry holds without a quantity are left out. Requested recipes not in
+/// `recipes` are skipped and returned sorted as the second value.
+fn merge(
+    requested: &[RecipeServings],
+    recipes: &[Recipe],
+    matcher: &Matcher,
+    pantry: &[PantryItem],
+) -> (Vec<ShoppingListItem>, Vec<String>) {
+    // (aisle, name, kind rank) keeps the list ordered for shopping
+    let mut totals: BTreeMap<(String, String, u8), Total> = BTreeMap::new();
+    let mut missing_recipes = Vec::new();
+
+    for item in requested.iter() {
+        let Some(recipe) = recipes.iter().find(|recipe| recipe.id == item.recipe_id) else {
+            missing_recipes.push(item.recipe_id.clone());
+            continue;
+        };
+
+        let factor = match item.servings {
+            Some(servings) if recipe.servings > 0 => servings as f64 / recipe.servings as f64,
+            _ => 1.0,
+        };
+
+        for line in recipe.ingredients.iter() {
+            let Some(resolved) = matcher.resolve(line) else {
+                continue;
+            };
+
+            let aisle = resolved.aisle.unwrap_or_else(|| DEFAULT_AISLE.to_string());
+            let (kind, amount) = match resolved.amount {
+                Some((kind, amount)) => (Some(kind), amount * factor),
+                None => (None, 0.0),
+            };
+
+            totals
+                .entry((aisle, resolved.name, kind_rank(kind)))
+                .or_insert(Total { kind, amount: 0.0 })
+                .amount += amount;
+        }
+    }
+
+    missing_recipes.sort();
+    missing_recipes.dedup();
+
+    for ((_, name, _), total) in totals.iter_mut() {
+        for stock in pantry.iter().filter(|stock| &stock.name == name) {
+            match (stock.quantity, stock.unit) {
+                (Some(quantity), Some(unit)) if total.kind == Some(unit.kind()) => {
+                    total.amount -= quantity * unit.factor();
+                }
+                (None, _) => total.amount = f64::NEG_INFINITY,
+                _ => {}
+            }
+        }
+    }
+    totals.retain(|(_, name, _), total| {
+        total.amount > 0.0
+            || (total.kind.is_none() && !pantry.iter().any(|stock| &stock.name == name))
+    });
+
+    let quantified: Vec<(String, String)> = totals
+        .iter()
+        .filter(|(_, total)| total.kind.is_some())
+        .map(|((aisle, name, _), _)| (aisle.clone(), name.clone()))
+        .collect();
+
+    let items = totals
+        .into_iter()
+        // "salt to taste" adds nothing next to "1 tsp salt"
+        .filter(|((aisle, name, _), total)| {
+            total.kind.is_some() || !quantified.contains(&(aisle.clone(), name.clone()))
+        })
+        .map(|((aisle, name, _), total)| {
+            let (quantity, unit) = match total.kind {
+                Some(kind) => {
+                    let (quantity, unit) = display(kind, total.amount);
+                    (Some(quantity), Some(unit))
+                }
+                None => (None, None),
+            };
+
+            ShoppingListItem {
+                name,
+                quantity,
+                unit,
+                aisle,
+                ..ShoppingListItem::default()
+            }
+        })
+        .collect();
+
+    (items, missing_recipes)
+}
+
+fn kind_rank(kind: Option<UnitKind>) -> u8 {
+    match kind {
+        Some(UnitKind::Mass) => 0,
+        Some(UnitKind::Volume) => 1,
+        Some(UnitKind::Count) => 2,
+        None => 3,
+    }
+}
+
+/// Picks a readable unit for an amount in the base unit of its kind. Pieces
+/// are rounded up as they are bought whole.
+fn display(kind: UnitKind, amount: f64) -> (f64, Unit) {
+    match kind {
+        UnitKind::Mass if amount >= 1000.0 => (round2(amount / 1000.0), Unit::Kilogram),
+        UnitKind::Mass => (round2(amount), Unit::Gram),
+        UnitKind::Volume if amount >= 1000.0 => (round2(amount / 1000.0), Unit::Liter),
+        UnitKind::Volume => (round2(amount), Unit::Milliliter),
+        UnitKind::Count => (amount.ceil(), Unit::Piece),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Ingredient;
+
+    fn entry(name: &str, aisle: &str) -> Ingredient {
+        Ingredient {
+            name: name.to_string(),
+            aisle: aisle.to_string(),
+            ..Ingredient::default()
+        }
+    }
+
+    fn recipe(id: &str, servings: i64, ingredients: &[&str]) -> Recipe {
+        Recipe {
+            id: id.to_string(),
+            servings,
+            ingredients: ingredients.iter().map(|line| line.to_string()).collect(),
+            ..Recipe::default()
+        }
+    }
+
+    fn requested(id: &str, servings: Option<i64>) -> RecipeServings {
+        RecipeServings {
+            recipe_id: id.to_string(),
+            servings,
+        }
+    }
+
+    fn item(
+        aisle: &str,
+        name: &str,
+        quantity: Option<f64>,
+        unit: Option<Unit>,
+    ) -> ShoppingListItem {
+        ShoppingListItem {
+            name: name.to_string(),
+            quantity,
+            unit,
+            aisle: aisle.to_string(),
+            ..ShoppingListItem::default()
+        }
+    }
+
+    fn matcher() -> Matcher {
+        Matcher::new(vec![
+            entry("butter", "dairy"),
+            entry("milk", "dairy"),
+            Ingredient {
+                aliases: vec!["eggs".to_string()],
+                ..entry("egg", "dairy")
+            },
+            entry("salt", "condiments"),
+        ])
+    }
+
+    #[test]
+    fn display_scales_to_larger_units() {
+        let cases = [
+            (UnitKind::Mass, 999.0, 999.0, Unit::Gram),
+            (UnitKind::Mass, 1000.0, 1.0, Unit::Kilogram),
+            (UnitKind::Mass, 1234.5, 1.23, Unit::Kilogram),
+            (UnitKind::Volume, 250.0, 250.0, Unit::Milliliter),
+            (UnitKind::Volume, 1500.0, 1.5, Unit::Liter),
+            (UnitKind::Count, 2.0, 2.0, Unit::Piece),
+            (UnitKind::Count, 2.1, 3.0, Unit::Piece),
+        ];
+
+        for (kind, amount, quantity, unit) in cases {
+            assert_eq!(display(kind, amount), (quantity, unit), "{:?}", amount);
+        }
+    }
+
+    #[test]
+    fn merge_sums_lines_in_base_units() {
+        let recipes = [
+            recipe("a", 2, &["200 g butter", "1 cup milk", "salt to taste"]),
+            recipe("b", 4, &["1 kg butter", "250 ml milk", "1 tsp salt"]),
+        ];
+
+        let (items, missing) = merge(
+            &[requested("a", None), requested("b", Some(2))],
+            &recipes,
+            &matcher(),
+            &[],
+        );
+
+        assert_eq!(
+            items,
+            vec![
+                item("condiments", "salt", Some(2.46), Some(Unit::Milliliter)),
+                item("dairy", "butter", Some(700.0), Some(Unit::Gram)),
+                item("dairy", "milk", Some(361.59), Some(Unit::Milliliter)),
+            ]
+        );
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_unit_kinds_apart() {
+        let recipes = [recipe(
+            "a",
+            1,
+            &["100 g butter", "2 tbsp butter", "2.5 eggs"],
+        )];
+
+        let (items, _) = merge(&[requested("a", None)], &recipes, &matcher(), &[]);
+
+        assert_eq!(
+            items,
+            vec![
+                item("dairy", "butter", Some(100.0), Some(Unit::Gram)),
+                item("dairy", "butter", Some(29.57), Some(Unit::Milliliter)),
+                item("dairy", "egg", Some(3.0), Some(Unit::Piece)),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_takes_off_pantry_stock() {
+        let recipes = [recipe(
+            "a",
+            1,
+            &["500 g butter", "1 l milk", "salt to taste"],
+        )];
+        let pantry = [
+            PantryItem {
+                name: "butter".to_string(),
+                quantity: Some(200.0),
+                unit: Some(Unit::Gram),
+                ..PantryItem::default()
+            },
+            PantryItem {
+                name: "milk".to_string(),
+                quantity: Some(2.0),
+                unit: Some(Unit::Liter),
+                ..PantryItem::default()
+            },
+            PantryItem {
+                name: "salt".to_string(),
+                ..PantryItem::default()
+            },
+        ];
+
+        let (items, _) = merge(&[requested("a", None)], &recipes, &matcher(), &pantry);
+
+        assert_eq!(
+            items,
+            vec![item("dairy", "butter", Some(300.0), Some(Unit::Gram))]
+        );
+    }
+
+    #[test]
+    fn merge_skips_missing_recipes() {
+        let recipes = [recipe("a", 1, &["2 eggs"])];
+
+        let (items, missing) = merge(
+            &[
+                requested("b", None),
+                requested("a", None),
+                requested("c", Some(2)),
+                requested("b", Some(1)),
+            ],
+            &recipes,
+            &matcher(),
+            &[],
+        );
+
+        assert_eq!(
+            items,
+            vec![item("dairy", "egg", Some(2.0), Some(Unit::Piece))]
+        );
+        assert_eq!(missing, vec!["b".to_string(), "c".to_string()]);
+    }
+}
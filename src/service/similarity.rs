@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+use crate::model::{Diet, Recipe, RecipeSimilarity};
+
+use super::ingredient::Matcher;
+
+/// Share of the score taken by each part of [`RecipeSimilarity`].
+const INGREDIENTS_WEIGHT: f64 = 0.35;
+const CATEGORY_WEIGHT: f64 = 0.2;
+const TAGS_WEIGHT: f64 = 0.1;
+const NUTRIENTS_WEIGHT: f64 = 0.2;
+const TIME_WEIGHT: f64 = 0.1;
+const DIFFICULTY_WEIGHT: f64 = 0.05;
+
+/// Known difficulty levels, in increasing order.
+const DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
+
+/// What recipes are compared on, computed once per recipe.
+pub struct Features {
+    pub category_id: String,
+    /// Dictionary names, or normalised names for unknown ingredients.
+    pub ingredients: HashSet<String>,
+    pub tags: HashSet<Diet>,
+    /// Shares of energy from proteins, fats and carbohydrates.
+    pub energy: Option<[f64; 3]>,
+    pub time_to_cook: i64,
+    pub difficulty: String,
+}
+
+impl Features {
+    pub fn new(recipe: &Recipe, matcher: &Matcher) -> Self {
+        let nutrients = &recipe.nutrients;
+        let energy = [
+            nutrients.proteins * 4.0,
+            nutrients.fats * 9.0,
+            nutrients.carbohydrates * 4.0,
+        ];
+        let total: f64 = energy.iter().sum();
+
+        Self {
+            category_id: recipe.category.id.clone(),
+            ingredients: recipe
+                .ingredients
+                .iter()
+                .filter_map(|line| matcher.resolve(line))
+                .map(|resolved| resolved.name)
+                .collect(),
+            tags: recipe.diets.iter().copied().collect(),
+            energy: (total > 0.0).then(|| energy.map(|value| value / total)),
+            time_to_cook: recipe.time_to_cook,
+            difficulty: recipe.difficulty.trim().to_lowercase(),
+        }
+    }
+}
+
+pub fn compare(a: &Features, b: &Features) -> RecipeSimilarity {
+    let mut res = RecipeSimilarity {
+        ingredients: jaccard(&a.ingredients, &b.ingredients),
+        category: same(&a.category_id, &b.category_id),
+        tags: jaccard(&a.tags, &b.tags),
+        nutrients: match (a.energy, b.energy) {
+            (Some(a), Some(b)) => {
+                let distance = a
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+
+                // shares sum up to 1, so no two are further apart than √2
+                1.0 - distance / 2f64.sqrt()
+            }
+            _ => 0.0,
+        },
+        time: match a.time_to_cook.max(b.time_to_cook) {
+            longest if longest > 0 => {
+                1.0 - (a.time_to_cook - b.time_to_cook).abs() as f64 / longest as f64
+            }
+            _ => 1.0,
+        },
+        difficulty: {
+            let level = |value: &str| DIFFICULTIES.iter().position(|known| *known == value);
+
+            match (level(&a.difficulty), level(&b.difficulty)) {
+                (Some(a), Some(b)) => 1.0 - a.abs_diff(b) as f64 / (DIFFICULTIES.len() - 1) as f64,
+                _ => same(&a.difficulty, &b.difficulty),
+            }
+        },
+        score: 0.0,
+    };
+
+    res.score = res.ingredients * INGREDIENTS_WEIGHT
+        + res.category * CATEGORY_WEIGHT
+        + res.tags * TAGS_WEIGHT
+        + res.nutrients * NUTRIENTS_WEIGHT
+        + res.time * TIME_WEIGHT
+        + res.difficulty * DIFFICULTY_WEIGHT;
+
+    res
+}
+
+/// Size of the intersection over size of the union, 0 for two empty sets.
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn same(a: &str, b: &str) -> f64 {
+    match a == b {
+        true => 1.0,
+        false => 0.0,
+    }
+}
@@ -0,0 +1,249 @@
+use std::{error::Error, sync::Arc};
+
+use crate::{
+    model::{
+        CalculateNutrientsQuery, CreateSubstitutionCommand, DeleteSubstitutionCommand,
+        LineSubstitutions, RecipeQuery, RecipeSubstitutions, RecipeSubstitutionsQuery, Role,
+        SearchResult, ServiceError, Substitution, SubstitutionQuery, SubstitutionSearchQuery,
+        SuggestedSwap, UpdateSubstitutionCommand,
+    },
+    repository::SubstitutionRepository,
+};
+
+use super::{
+    check_pagination,
+    ingredient::{canonical_name, parse_line},
+    nutrition::{add, round2, scale},
+    policy, IngredientService, NutritionService, RecipeService,
+};
+
+pub struct Config {
+    pub recipe_service: Arc<RecipeService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub nutrition_service: Arc<NutritionService>,
+    pub substitution_storage: Arc<SubstitutionRepository>,
+}
+
+pub struct SubstitutionService {
+    pub recipe_service: Arc<RecipeService>,
+    pub ingredient_service: Arc<IngredientService>,
+    pub nutrition_service: Arc<NutritionService>,
+    pub substitution_storage: Arc<SubstitutionRepository>,
+}
+
+impl SubstitutionService {
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            recipe_service: cfg.recipe_service,
+            ingredient_service: cfg.ingredient_service,
+            nutrition_service: cfg.nutrition_service,
+            substitution_storage: cfg.substitution_storage,
+        }
+    }
+
+    pub async fn fetch(&self, q: SubstitutionQuery) -> Result<Substitution, Box<dyn Error>> {
+        self.substitution_storage.fetch(q).await
+    }
+
+    pub async fn create(
+        &self,
+        mut item: CreateSubstitutionCommand,
+    ) -> Result<Substitution, Box<dyn Error>> {
+        policy::require(&item.actor, Role::Editor)?;
+        check_ratio(item.ratio)?;
+
+        let matcher = self.ingredient_service.matcher().await?;
+        item.ingredient = canonical_name(&matcher, &item.ingredient)?;
+        item.substitute = canonical_name(&matcher, &item.substitute)?;
+        item.avoids = restrictions(&item.avoids);
+        item.notes = item.notes.trim().to_string();
+
+        if item.ingredient == item.substitute {
+            return Err(
+                ServiceError::Invalid("an ingredient can not substitute itself".into()).into(),
+            );
+        }
+
+        self.substitution_storage.create(item).await
+    }
+
+    pub async fn update(
+        &self,
+        mut q: UpdateSubstitutionCommand,
+    ) -> Result<Substitution, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        if let Some(ratio) = q.ratio {
+            check_ratio(ratio)?;
+        }
+
+        let matcher = self.ingredient_service.matcher().await?;
+        if let Some(ingredient) = q.ingredient.as_deref() {
+            q.ingredient = Some(canonical_name(&matcher, ingredient)?);
+        }
+        if let Some(substitute) = q.substitute.as_deref() {
+            q.substitute = Some(canonical_name(&matcher, substitute)?);
+        }
+        q.avoids = q.avoids.as_deref().map(restrictions);
+        q.notes = q.notes.map(|notes| notes.trim().to_string());
+
+        self.substitution_storage.update(q).await
+    }
+
+    pub async fn delete(
+        &self,
+        q: DeleteSubstitutionCommand,
+    ) -> Result<Substitution, Box<dyn Error>> {
+        policy::require(&q.actor, Role::Editor)?;
+
+        self.substitution_storage.delete(q).await
+    }
+
+    pub async fn search(
+        &self,
+        mut q: SubstitutionSearchQuery,
+    ) -> Result<SearchResult<Substitution>, Box<dyn Error>> {
+        check_pagination(&q.pagination)?;
+
+        if let Some(ingredient) = q.ingredient.as_deref() {
+            let matcher = self.ingredient_service.matcher().await?;
+            q.ingredient = Some(canonical_name(&matcher, ingredient)?);
+        }
+        q.avoids = q.avoids.as_deref().map(restrictions);
+
+        self.substitution_storage.search(q).await
+    }
+
+    /// Suggests swaps for the recipe lines whose ingredient has a knowledge
+    /// base entry satisfying any of the restrictions to avoid.
+    pub async fn for_recipe(
+        &self,
+        q: RecipeSubstitutionsQuery,
+    ) -> Result<RecipeSubstitutions, Box<dyn Error>> {
+        let avoid = restrictions(&q.avoid);
+        if avoid.is_empty() {
+            return Err(
+                ServiceError::Invalid("avoid must name at least one restriction".into()).into(),
+            );
+        }
+
+        let mut recipe = self
+            .recipe_service
+            .fetch(RecipeQuery {
+                id: q.recipe_id,
+                ..RecipeQuery::default()
+            })
+            .await?;
+        let entries = self
+            .substitution_storage
+            .search(SubstitutionSearchQuery {
+                avoids: Some(avoid),
+                ..SubstitutionSearchQuery::default()
+            })
+            .await?
+            .items;
+        let matcher = self.ingredient_service.matcher().await?;
+
+        let mut lines = Vec::new();
+        let mut adjusted = Vec::with_capacity(recipe.ingredients.len());
+
+        for line in recipe.ingredients.iter() {
+            let suggestions: Vec<SuggestedSwap> = match matcher.resolve(line) {
+                Some(resolved) => entries
+                    .iter()
+                    .filter(|entry| entry.ingredient == resolved.name)
+                    .map(|entry| SuggestedSwap {
+                        substitution: entry.clone(),
+                        line: swap_line(line, entry),
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            match suggestions.first() {
+                Some(first) => {
+                    adjusted.push(first.line.clone());
+                    lines.push(LineSubstitutions {
+                        line: line.clone(),
+                        ingredient: first.substitution.ingredient.clone(),
+                        suggestions,
+                    });
+                }
+                None => adjusted.push(line.clone()),
+            }
+        }
+
+        let before = self
+            .nutrition_service
+            .calculate(CalculateNutrientsQuery {
+                ingredients: recipe.ingredients.clone(),
+                servings: recipe.servings,
+            })
+            .await?
+            .nutrients;
+        let after = self
+            .nutrition_service
+            .calculate(CalculateNutrientsQuery {
+                ingredients: adjusted.clone(),
+                servings: recipe.servings,
+            })
+            .await?
+            .nutrients;
+
+        let mut nutrients_delta = after;
+        add(&mut nutrients_delta, &scale(before, -1.0));
+        nutrients_delta.serving_weight = None;
+
+        add(&mut recipe.nutrients, &nutrients_delta);
+        recipe.ingredients = adjusted;
+
+        Ok(RecipeSubstitutions {
+            recipe,
+            lines,
+            nutrients_delta,
+        })
+    }
+}
+
+/// The recipe line with the ingredient swapped and its amount scaled by the
+/// ratio of the entry.
+fn swap_line(line: &str, entry: &Substitution) -> String {
+    let parsed = parse_line(line);
+
+    let quantity = parsed
+        .quantity
+        .map(|quantity| round2(quantity * entry.ratio));
+    let unit = entry.unit.or(parsed.unit);
+
+    [
+        quantity.map(|quantity| quantity.to_string()),
+        unit.filter(|_| quantity.is_some())
+            .map(|unit| unit.code().to_string()),
+        Some(entry.substitute.clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn check_ratio(ratio: f64) -> Result<(), Box<dyn Error>> {
+    if ratio.is_nan() || ratio <= 0.0 {
+        return Err(ServiceError::Invalid("ratio must be positive".into()).into());
+    }
+
+    Ok(())
+}
+
+/// Lowercased restriction codes without blanks and repetitions.
+fn restrictions(values: &[String]) -> Vec<String> {
+    let mut res: Vec<String> = Vec::new();
+
+    for value in values.iter().map(|value| value.trim().to_lowercase()) {
+        if !value.is_empty() && !res.contains(&value) {
+            res.push(value);
+        }
+    }
+
+    res
+}
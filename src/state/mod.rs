@@ -4,6 +4,23 @@ use crate::service;
 
 #[derive(Clone)]
 pub struct AppState {
+    pub auth_service: Arc<service::AuthService>,
+    pub api_key_service: Arc<service::ApiKeyService>,
     pub recipe_service: Arc<service::RecipeService>,
     pub category_service: Arc<service::CategoryService>,
+    pub allergen_service: Arc<service::AllergenService>,
+    pub ingredient_service: Arc<service::IngredientService>,
+    pub label_service: Arc<service::LabelService>,
+    pub meal_plan_service: Arc<service::MealPlanService>,
+    pub shopping_list_service: Arc<service::ShoppingListService>,
+    pub pantry_service: Arc<service::PantryService>,
+    pub preference_service: Arc<service::PreferenceService>,
+    pub recommendation_service: Arc<service::RecommendationService>,
+    pub substitution_service: Arc<service::SubstitutionService>,
+    pub favorite_service: Arc<service::FavoriteService>,
+    pub collection_service: Arc<service::CollectionService>,
+    pub review_service: Arc<service::ReviewService>,
+    pub cooking_log_service: Arc<service::CookingLogService>,
+    pub diary_service: Arc<service::DiaryService>,
+    pub revision_service: Arc<service::RevisionService>,
 }